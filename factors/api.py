@@ -4,16 +4,73 @@ FastAPI wrapper for Factor Models
 Run with: uvicorn api:app --reload
 """
 
+import asyncio
+import contextvars
+import copy
+import functools
+import hashlib
+import hmac
+import io
+import ipaddress
+import itertools
+import json
+import logging
+import math
 import os
+import re
+import signal
+import socket
+import tempfile
+import threading
+import time
+from contextlib import asynccontextmanager
+from dataclasses import dataclass
 from datetime import datetime
+from enum import StrEnum
 from pathlib import Path
-from typing import Dict, List, Optional
+from typing import Callable, Dict, Iterator, List, Literal, Optional, get_args
+from urllib.parse import urlsplit
 
 import numpy as np
 import pandas as pd
-from fastapi import FastAPI, HTTPException, Query
+import requests
+from fastapi import Body, FastAPI, Header, HTTPException, Query, Request, Response
+from fastapi.encoders import jsonable_encoder
+from fastapi.exception_handlers import request_validation_exception_handler
+from fastapi.exceptions import RequestValidationError
 from fastapi.middleware.cors import CORSMiddleware
-from pydantic import BaseModel, Field
+from fastapi.middleware.gzip import GZipMiddleware
+from fastapi.responses import JSONResponse, StreamingResponse
+from pydantic import BaseModel, ConfigDict, Field, ValidationError, field_validator, model_validator
+
+from stats import (
+    annualize_return,
+    calmar_ratio,
+    combine_zscores,
+    cross_sectional_winsorize,
+    cross_sectional_zscore,
+    cumulative_returns as compound_returns,
+    drawdown_series,
+    excess_kurtosis,
+    ic_information_ratio,
+    information_ratio,
+    leave_one_out_zscore,
+    max_drawdown,
+    newey_west_regression,
+    ols_regression,
+    rolling_correlation,
+    rolling_beta,
+    rolling_mean,
+    rolling_sharpe,
+    rolling_volatility,
+    sharpe_ratio,
+    simple_returns,
+    skewness,
+    sortino_ratio,
+    tracking_error,
+    value_at_risk,
+    worst_drawdown,
+)
 
 # Lazy imports for heavy dependencies
 _ApiData = None
@@ -23,27 +80,166 @@ _cumulative_returns = None
 
 
 _fetch_merged_crypto_data = None
+_EmptyUniverseError = None
+_EmptyLegError = None
+_OverlappingLegsError = None
 
 
 def _load_utils():
     """Lazy load utils module to handle optional dependencies"""
-    global _ApiData, _FactorModel, _Logger, _cumulative_returns, _fetch_merged_crypto_data
+    global _ApiData, _FactorModel, _Logger, _cumulative_returns, _fetch_merged_crypto_data, _EmptyUniverseError, _EmptyLegError
+    global _OverlappingLegsError
     if _FactorModel is None:
-        from utils import ApiData, FactorModel, Logger, cumulative_returns, fetch_merged_crypto_data
+        from utils import (
+            ApiData,
+            EmptyLegError,
+            EmptyUniverseError,
+            FactorModel,
+            Logger,
+            OverlappingLegsError,
+            cumulative_returns,
+            fetch_merged_crypto_data,
+        )
         _ApiData = ApiData
         _FactorModel = FactorModel
         _Logger = Logger
         _cumulative_returns = cumulative_returns
         _fetch_merged_crypto_data = fetch_merged_crypto_data
+        _EmptyUniverseError = EmptyUniverseError
+        _EmptyLegError = EmptyLegError
+        _OverlappingLegsError = OverlappingLegsError
     return _ApiData, _FactorModel, _Logger, _cumulative_returns
 
+def ensure_logs_dir_writable(log_dir: Path):
+    """
+    Create the factor logs directory if needed and check a file can be written to it.
+
+    Raises RuntimeError naming the directory when it can't be created or written,
+    so a read-only FACTOR_LOGS_DIR fails at startup instead of in every compute.
+    """
+    try:
+        log_dir.mkdir(parents=True, exist_ok=True)
+        with tempfile.NamedTemporaryFile(dir=log_dir, prefix=".write_check_"):
+            pass
+    except OSError as e:
+        raise RuntimeError(
+            f"FACTOR_LOGS_DIR '{log_dir}' is not writable ({e.strerror or e}); "
+            "point FACTOR_LOGS_DIR at a writable directory"
+        ) from e
+
+
+@asynccontextmanager
+async def lifespan(app: FastAPI):
+    """
+    Create the factor logs directory once at startup rather than on every request, failing
+    if it's read-only. utils is imported here too, so its invalid settings (e.g.
+    ARTEMIS_MAX_CONCURRENT_REQUESTS=0) stop the server instead of failing every compute.
+    """
+    ensure_logs_dir_writable(FACTOR_LOGS_DIR)
+    _load_utils()
+    yield
+    # Requests have drained or outlived SHUTDOWN_TIMEOUT_SECONDS; let computes still
+    # running in worker threads stop at their next rebalance and log what they have as
+    # a partial run, which listings, /latest and /factors/compare leave out
+    COMPUTE_STOP.set()
+
+
 # Initialize FastAPI app
 app = FastAPI(
     title="Factor Models API",
     description="API for cryptocurrency factor model analysis",
     version="1.0.0",
+    lifespan=lifespan,
 )
 
+# Routes that fetch from Artemis and run a compute: everything under /compute, and reproducing a run
+COMPUTE_ROUTES = re.compile(r"^/compute(/|$)|^/factors/[^/]+/runs/[^/]+/reproduce$")
+
+# Routes that delete or overwrite logs when called with anything but GET (every DELETE does too)
+WRITE_ROUTES = re.compile(r"^/admin(/|$)|^/factors/[^/]+/runs/[^/]+/time-series$")
+
+
+def requires_auth_token(request: Request) -> bool:
+    """Whether a request needs the API_AUTH_TOKEN: computes, and anything that deletes or overwrites logs"""
+    path = request.url.path
+    if request.method == "OPTIONS":
+        return False
+    if COMPUTE_ROUTES.match(path) or request.method == "DELETE":
+        return True
+    return request.method not in ("GET", "HEAD") and bool(WRITE_ROUTES.match(path))
+
+
+# Registered before CORS so the 401 responses still carry CORS headers
+@app.middleware("http")
+async def require_api_auth_token(request: Request, call_next):
+    """
+    When API_AUTH_TOKEN is set, require `Authorization: Bearer <token>` on routes that
+    spend Artemis quota or change logs, answering others with a 401. No-op when it isn't set.
+    """
+    if API_AUTH_TOKEN and requires_auth_token(request):
+        scheme, _, token = request.headers.get("Authorization", "").partition(" ")
+        if scheme.lower() != "bearer" or not hmac.compare_digest(token.strip().encode(), API_AUTH_TOKEN.encode()):
+            return JSONResponse(
+                status_code=401,
+                content={"detail": "Missing or invalid bearer token"},
+                headers={"WWW-Authenticate": "Bearer"},
+            )
+    return await call_next(request)
+
+
+class RateLimiter:
+    """
+    Per-client token buckets: each client can make `per_minute` requests in a burst,
+    refilled continuously at per_minute / 60 a second.
+    """
+
+    MAX_CLIENTS = 10_000  # beyond this, clients whose bucket has refilled are forgotten
+
+    def __init__(self, per_minute: int):
+        self.capacity = per_minute
+        self.refill_per_second = per_minute / 60
+        self._buckets: Dict[str, tuple[float, float]] = {}  # client -> (tokens, time.monotonic() of update)
+        self._lock = threading.Lock()
+
+    def acquire(self, client: str) -> Optional[float]:
+        """Take a token for client: None if allowed, else the seconds until one is available"""
+        now = time.monotonic()
+        with self._lock:
+            tokens, updated = self._buckets.get(client, (self.capacity, now))
+            tokens = min(self.capacity, tokens + (now - updated) * self.refill_per_second)
+            if tokens < 1:
+                self._buckets[client] = (tokens, now)
+                return (1 - tokens) / self.refill_per_second
+            self._buckets[client] = (tokens - 1, now)
+            if len(self._buckets) > self.MAX_CLIENTS:
+                self._forget_refilled(now)
+            return None
+
+    def _forget_refilled(self, now: float):
+        """Helper to drop buckets that have refilled to capacity, which behave like new clients"""
+        self._buckets = {
+            client: (tokens, updated)
+            for client, (tokens, updated) in self._buckets.items()
+            if tokens + (now - updated) * self.refill_per_second < self.capacity
+        }
+
+
+# Registered after auth so it runs first, and before CORS so 429s still carry CORS headers
+@app.middleware("http")
+async def limit_compute_rate(request: Request, call_next):
+    """Cap compute requests per client IP at COMPUTE_RATE_LIMIT_PER_MINUTE, answering the excess with a 429"""
+    if COMPUTE_RATE_LIMITER and request.method != "OPTIONS" and COMPUTE_ROUTES.match(request.url.path):
+        client = request.client.host if request.client else "unknown"
+        retry_after = COMPUTE_RATE_LIMITER.acquire(client)
+        if retry_after is not None:
+            return JSONResponse(
+                status_code=429,
+                content={"detail": f"Rate limit of {COMPUTE_RATE_LIMIT_PER_MINUTE} compute requests per minute exceeded"},
+                headers={"Retry-After": str(math.ceil(retry_after))},
+            )
+    return await call_next(request)
+
+
 # Configure CORS
 app.add_middleware(
     CORSMiddleware,
@@ -58,27 +254,416 @@ app.add_middleware(
     allow_headers=["*"],
 )
 
+# Server-sent event streams, which GZip would hold back until enough events compress
+EVENT_STREAM_ROUTE = re.compile(r"^/compute/[^/]+/stream$")
+
+
+class EventStreamGZipMiddleware(GZipMiddleware):
+    """GZipMiddleware that passes event streams through uncompressed so each event is sent as it happens"""
+
+    async def __call__(self, scope, receive, send):
+        if scope["type"] == "http" and EVENT_STREAM_ROUTE.match(scope["path"]):
+            await self.app(scope, receive, send)
+        else:
+            await super().__call__(scope, receive, send)
+
+
+# Compress larger responses; streamed bodies are compressed chunk by chunk
+app.add_middleware(EventStreamGZipMiddleware, minimum_size=1024)
+
+
+@app.exception_handler(RequestValidationError)
+async def reject_unknown_fields(request: Request, exc: RequestValidationError):
+    """
+    Answer a request body with unknown fields, e.g. a typo'd config field, with a 400
+    naming them instead of running with defaults. Other validation errors keep the 422.
+    """
+    unknown = [".".join(map(str, err["loc"][1:])) for err in exc.errors() if err["type"] == "extra_forbidden"]
+    if unknown:
+        return JSONResponse(
+            status_code=400,
+            content={"detail": f"Unknown field(s) in request body: {', '.join(unknown)}"},
+        )
+    return await request_validation_exception_handler(request, exc)
+
+
+# Requests being handled, reported when a shutdown starts draining them
+_in_flight_requests = 0
+
+
+@app.middleware("http")
+async def track_in_flight_requests(request: Request, call_next):
+    """Count requests being handled so a graceful shutdown can log how many it waits for"""
+    global _in_flight_requests
+    _in_flight_requests += 1
+    try:
+        return await call_next(request)
+    finally:
+        _in_flight_requests -= 1
+
+
 # Constants
-FACTOR_LOGS_DIR = Path(__file__).parent / "factor_logs"
+FACTOR_LOGS_DIR = Path(os.getenv("FACTOR_LOGS_DIR") or Path(__file__).parent / "factor_logs")
 API_KEY = os.getenv("ARTEMIS_API_KEY")
+# Bearer token required on compute routes when set (see require_api_auth_token)
+API_AUTH_TOKEN = os.getenv("API_AUTH_TOKEN") or None
+# Compute requests allowed per client IP per minute (0 disables the limit)
+COMPUTE_RATE_LIMIT_PER_MINUTE = int(os.getenv("COMPUTE_RATE_LIMIT_PER_MINUTE", "0"))
+COMPUTE_RATE_LIMITER = RateLimiter(COMPUTE_RATE_LIMIT_PER_MINUTE) if COMPUTE_RATE_LIMIT_PER_MINUTE > 0 else None
+
+# Per-request timeout of the /health/deep dependency checks, short enough for a readiness probe
+HEALTH_CHECK_TIMEOUT_SECONDS = 5
+
+# Interface the server listens on when run directly (python api.py)
+BIND_ADDRESS = os.getenv("BIND_ADDRESS", "0.0.0.0")
+BIND_PORT = 8000
+# Seconds a SIGTERM/SIGINT waits for in-flight requests, e.g. long computes, before cancelling them
+SHUTDOWN_TIMEOUT_SECONDS = float(os.getenv("SHUTDOWN_TIMEOUT_SECONDS", "30"))
+HOSTNAME_PATTERN = re.compile(r"^(?=.{1,253}$)[A-Za-z0-9](?:[A-Za-z0-9-]{0,61}[A-Za-z0-9])?(?:\.[A-Za-z0-9](?:[A-Za-z0-9-]{0,61}[A-Za-z0-9])?)*$")
+
+# Time-series responses with at least this many rows are streamed rather than buffered
+STREAM_MIN_ROWS = int(os.getenv("STREAM_MIN_ROWS", "5000"))
+STREAM_CHUNK_ROWS = 1000
+
+# Version of the FactorConfig schema saved with each run's config; bump it and add
+# the field to CONFIG_FIELDS_ADDED when a field is added whose default could change
+# a run's results. Version 1 is the schema configs were first saved under.
+CONFIG_SCHEMA_VERSION = 6
+
+# Result-affecting FactorConfig fields added since version 1, by the version that added them
+CONFIG_FIELDS_ADDED = {
+    "leg_overlap_policy": 2,
+    "portfolio_mode": 3,
+    "universe": 4,
+    "benchmark": 5,
+    "winsorize_pct": 6,
+}
+
+# FactorConfig fields that don't affect results, so reproducing without them isn't worth a warning.
+# timeout_seconds only decides whether a run finishes, and partial runs are never reproduced.
+RESULT_NEUTRAL_CONFIG_FIELDS = {
+    "run_id",
+    "callback_url",
+    "lookback_period",
+    "log_format",
+    "returns_format",
+    "timeout_seconds",
+}
+
+# Seconds GET /universe reuses the tradeable symbol list, which changes slowly
+UNIVERSE_CACHE_SECONDS = 300
+
+# Most parameter combinations a single /compute/{factor}/sweep request may run
+SWEEP_MAX_RUNS = 50
+
+# Compute result webhooks: attempts are spaced by exponential backoff from the base delay
+CALLBACK_MAX_ATTEMPTS = 5
+CALLBACK_BACKOFF_SECONDS = 1.0
+CALLBACK_TIMEOUT_SECONDS = 10
+
+# Rebalance cadences: pandas resample rule (labelled by period end) and periods per year
+REBALANCE_FREQUENCIES = {
+    "daily": ("D", 365),
+    "weekly": ("W", 52),
+    "monthly": ("ME", 12),
+}
 
-# Available factors
-AVAILABLE_FACTORS = ["smb", "market", "value", "momentum", "momentum_v2", "growth"]
+# Daily equity runs hold trading days only, so they annualize over a trading year
+EQUITY_TRADING_DAYS_PER_YEAR = 252
+DAYS_PER_PERIOD = {"daily": 1, "weekly": 7, "monthly": 365 / 12}
+
+# Factors whose signal is a trailing return over config.lookback_days
+LOOKBACK_DAYS_FACTORS = ("momentum", "equity")
+
+# Fewest periods a run must share with its benchmark for alpha and beta
+BENCHMARK_MIN_PERIODS = 3
+
+# Set on shutdown so running computes stop early with a partial result
+COMPUTE_STOP = threading.Event()
+
+log = logging.getLogger(__name__)
+
+
+class Factor(StrEnum):
+    """Factor names the API serves; the single source of truth for routing and validation"""
+
+    SMB = "smb"
+    MARKET = "market"
+    VALUE = "value"
+    MOMENTUM = "momentum"
+    MOMENTUM_V2 = "momentum_v2"
+    GROWTH = "growth"
+    COMPOSITE = "composite"
+    EQUITY = "equity"
+
+    @classmethod
+    def parse(cls, name: str) -> "Factor":
+        """Parse a factor name, raising a 404 for unknown names"""
+        try:
+            return cls(name)
+        except ValueError:
+            raise HTTPException(status_code=404, detail=f"Factor '{name}' not found")
+
+
+# Base factors that can be combined by /compute/composite
+COMPOSITE_BASE_FACTORS = [Factor.SMB, Factor.MOMENTUM, Factor.VALUE, Factor.GROWTH]
+
+# Factors whose signals honor normalize_by_mc (composite only when weighted on growth).
+# Value's MC/fees ratio is already size-neutral.
+NORMALIZE_BY_MC_FACTORS = [Factor.GROWTH, Factor.COMPOSITE]
+
+# Artemis metrics each factor fetches (price and 24h_volume always come from Coinbase).
+# Growth's fundamentals are the default growth_weights; requests can change them.
+# Composite also fetches the metrics of the base factors it's weighted on.
+FACTOR_METRICS = {
+    Factor.SMB: ["mc"],
+    Factor.MARKET: ["mc"],
+    Factor.VALUE: ["mc", "fees"],
+    Factor.MOMENTUM: ["mc"],
+    Factor.MOMENTUM_V2: ["mc"],
+    Factor.GROWTH: ["mc", "fees", "dau", "revenue"],
+    Factor.COMPOSITE: ["mc"],
+    Factor.EQUITY: [],  # prices and volume only, from Yahoo Finance
+}
+PRICE_FIELDS = ["price", "24h_volume"]
+# Where each factor's price fields come from
+PRICE_SOURCES = {factor: "coinbase" for factor in Factor} | {Factor.EQUITY: "yahoo"}
+
+# Which end of each factor's ranking signal goes long: "high" longs the largest
+# values, "low" the smallest. Requests can override it with signal_direction.
+# Value ranks on MC / annualized fees, so "low" longs the cheapest protocols.
+SIGNAL_DIRECTIONS = {
+    Factor.SMB: "low",  # market cap: long small, short big
+    Factor.MARKET: "high",  # long-only largest assets; not overridable
+    Factor.VALUE: "low",  # MC/fees ratio: long cheap, short expensive
+    Factor.MOMENTUM: "high",
+    Factor.MOMENTUM_V2: "high",
+    Factor.GROWTH: "high",
+    Factor.COMPOSITE: "high",  # base signals are oriented so high goes long
+    Factor.EQUITY: "high",  # trailing return: long winners, short losers
+}
+
+
+RELATIVE_PERIOD_PATTERN = re.compile(r"^(\d+)([dwmy])$")
+RELATIVE_PERIOD_UNITS = {"d": "days", "w": "weeks", "m": "months", "y": "years"}
+
+
+def parse_relative_period(period: str) -> pd.DateOffset:
+    """Parse a period like 30d, 4w, 6m or 2y into a calendar offset"""
+    match = RELATIVE_PERIOD_PATTERN.match(period.strip().lower())
+    if not match:
+        raise ValueError(f"Invalid period '{period}', expected e.g. 30d, 4w, 6m or 2y")
+    amount, unit = match.groups()
+    return pd.DateOffset(**{RELATIVE_PERIOD_UNITS[unit]: int(amount)})
+
+
+def resolve_relative_date(value: str, anchor: pd.Timestamp) -> pd.Timestamp:
+    """Resolve a concrete date, or a relative one like -2y counted back from anchor"""
+    if value.startswith("-"):
+        return anchor - parse_relative_period(value[1:])
+    try:
+        return pd.Timestamp(value)
+    except ValueError:
+        raise ValueError(f"Invalid date '{value}', expected YYYY-MM-DD or a relative period like -2y")
+
+
+# Custom run ids: letters, digits, '_' and '-'
+RUN_ID_PATTERN = r"^[A-Za-z0-9_-]{1,64}$"
 
 
 # Pydantic Models
 class FactorConfig(BaseModel):
     """Configuration for running a factor model"""
 
+    # A misspelled field would otherwise be dropped silently and its default used
+    model_config = ConfigDict(extra="forbid")
+
     factor: str = Field(..., description="Factor name (smb, market, value, momentum, growth)")
     breakpoint: float = Field(0.5, ge=0.1, le=0.5, description="Percentile breakpoint for portfolio splits")
     min_assets: int = Field(30, ge=5, description="Minimum assets per period")
-    weighting_method: str = Field("equal", description="Weighting method: equal, market_cap, inverse_variance")
-    start_date: str = Field(..., description="Start date (YYYY-MM-DD)")
-    end_date: str = Field(..., description="End date (YYYY-MM-DD)")
+    weighting_method: Literal["equal", "market_cap", "inverse_variance"] = Field(
+        "equal", description="Weighting method: equal, market_cap, inverse_variance"
+    )
+    start_date: Optional[str] = Field(
+        None, description="Start date (YYYY-MM-DD), or relative to today like -2y, -6m, -4w, -30d"
+    )
+    end_date: Optional[str] = Field(None, description="End date (YYYY-MM-DD or relative to today); default today (UTC)")
+    lookback_period: Optional[str] = Field(
+        None, description="Alternative to start_date: window before end_date, e.g. 2y, 6m, 4w, 30d"
+    )
     market_cap_threshold: Optional[int] = Field(100_000_000, description="Minimum market cap filter")
     liquidity_threshold: Optional[int] = Field(35_000_000, description="Minimum 24h volume filter")
     min_lifetime_days: Optional[int] = Field(30, description="Minimum asset lifetime in days")
+    min_valid_periods: Optional[int] = Field(
+        None, ge=1, description="Minimum rebalance periods with a full signal window per asset"
+    )
+    min_annualization_days: int = Field(
+        30, ge=0, description="Runs shorter than this many days get an unreliable annualized return"
+    )
+    short_window_annualization: Literal["flag", "suppress"] = Field(
+        "flag", description="For short runs: flag the annualized return as unreliable, or suppress it"
+    )
+    normalize_by_mc: bool = Field(
+        False, description="Growth (also in a composite): divide its metrics by market cap before measuring growth"
+    )
+    run_id: Optional[str] = Field(
+        None,
+        pattern=RUN_ID_PATTERN,
+        description="Custom run id (letters, digits, '_' and '-'); must not already exist for the factor",
+    )
+    callback_url: Optional[str] = Field(
+        None,
+        pattern=r"^https?://",
+        description="Public http(s) URL to POST the result, or the failure, to when the run finishes",
+    )
+    lookback_days: int = Field(
+        90, ge=7, le=730, description="Momentum: trailing return window in days (rounded to whole rebalance periods)"
+    )
+    top_n: int = Field(10, ge=1, le=100, description="Market: number of largest assets held each period")
+    covariance_lookback_days: Optional[int] = Field(
+        None,
+        ge=14,
+        le=1095,
+        description="Inverse-variance: trailing window in days for each asset's return variance, "
+        "independent of the signal lookback (default: full history)",
+    )
+    universe: Optional[List[str]] = Field(
+        None,
+        min_length=1,
+        max_length=500,
+        description="Crypto: assets to restrict the universe to, e.g. a client's custom index, instead of "
+        "every Artemis symbol with a Coinbase product (tickers and product ids are accepted)",
+    )
+    tickers: Optional[List[str]] = Field(
+        None,
+        min_length=1,
+        max_length=500,
+        description="Equity: Yahoo Finance tickers to rank (default: the built-in EQUITY_TICKERS universe)",
+    )
+    growth_weights: Dict[str, float] = Field(
+        default_factory=lambda: {"fees": 1.0, "dau": 1.0, "revenue": 1.0},
+        description="Growth: Artemis metrics whose growth rates are blended, with their weights",
+    )
+    winsorize_pct: Optional[float] = Field(
+        None,
+        ge=0,
+        lt=0.5,
+        description="Value/growth: clamp each date's signal values to this percentile in both tails before "
+        "ranking, e.g. 0.01, so outliers can't dominate (default: off)",
+    )
+    partial_metric_policy: Optional[Literal["require_all", "average_available", "impute_mean"]] = Field(
+        None,
+        description="Assets missing some signal components: drop them, average what's present, "
+        "or impute the cross-sectional mean (default: the factor's own policy)",
+    )
+    signal_direction: Optional[Literal["high", "low"]] = Field(
+        None,
+        description="Whether high or low signal values go long (default: the factor's convention, see SIGNAL_DIRECTIONS)",
+    )
+    empty_leg_policy: Literal["skip", "error"] = Field(
+        "skip",
+        description="When the breakpoint selects no assets for a leg: skip the rebalance and hold the prior legs, or fail the run",
+    )
+    leg_overlap_policy: Literal["net", "error"] = Field(
+        "net",
+        description="When an asset is selected for both legs of a rebalance: net its long and short weights "
+        "into one signed position, or fail the run",
+    )
+    portfolio_mode: Literal["long_short", "long_only", "short_only"] = Field(
+        "long_short",
+        description="Which legs the factor return holds: long minus short, only the long leg, "
+        "or only the short position",
+    )
+    timeout_seconds: Optional[float] = Field(
+        None,
+        gt=0,
+        description="Stop portfolio formation after this many seconds and return a partial result "
+        "covering the completed rebalance periods (default: no timeout)",
+    )
+    transaction_cost_bps: float = Field(
+        0.0, ge=0, le=1000, description="Cost per unit of turnover in basis points, taken out of each rebalance's return"
+    )
+    price_join: Literal["inner", "left"] = Field(
+        "inner",
+        description="Joining Artemis metrics to Coinbase prices by UTC day: keep only days with both, "
+        "or keep every metric row with null prices where there's no candle",
+    )
+    rebalance_frequency: Literal["daily", "weekly", "monthly"] = Field(
+        "weekly", description="How often portfolios are rebalanced; monthly rebalances on calendar month-ends"
+    )
+    benchmark: str = Field(
+        "bitcoin",
+        min_length=1,
+        description="Asset the factor's alpha and beta are measured against (tickers and product ids are accepted)",
+    )
+    log_format: Literal["csv", "parquet"] = Field(
+        "csv", description="File format of the run's saved returns time series"
+    )
+    returns_format: Literal["map", "ordered"] = Field(
+        "map", description="Recent returns as a {date: return} object, or an ordered list of [date, return] pairs"
+    )
+
+    @field_validator("callback_url")
+    @classmethod
+    def check_callback_url(cls, url: Optional[str]) -> Optional[str]:
+        """Reject callback URLs without a host, or whose host is an internal IP address"""
+        if url is None:
+            return url
+        host = urlsplit(url).hostname
+        if not host:
+            raise ValueError("callback_url must include a host")
+        try:
+            allowed = callback_address_allowed(host)
+        except ValueError:
+            # A hostname: its addresses are checked when the callback is delivered
+            return url
+        if not allowed:
+            raise ValueError(f"callback_url host {host} is a private, loopback or otherwise internal address")
+        return url
+
+    @model_validator(mode="after")
+    def resolve_date_range(self):
+        """Resolve relative dates and lookback_period to concrete YYYY-MM-DD dates at request time"""
+        today = pd.Timestamp.now(tz="UTC").tz_localize(None).normalize()
+        end = resolve_relative_date(self.end_date, today) if self.end_date else today
+
+        if self.lookback_period:
+            if self.start_date:
+                raise ValueError("Provide either start_date or lookback_period, not both")
+            start = end - parse_relative_period(self.lookback_period)
+        elif self.start_date:
+            start = resolve_relative_date(self.start_date, today)
+        else:
+            raise ValueError("start_date or lookback_period is required")
+
+        if start >= end:
+            raise ValueError(f"start_date {start:%Y-%m-%d} must be before end_date {end:%Y-%m-%d}")
+        if end > today:
+            raise ValueError(f"end_date {end:%Y-%m-%d} is in the future (today is {today:%Y-%m-%d} UTC)")
+
+        # A return needs a full rebalance period, after the trailing window for factors whose signal has one
+        min_days = DAYS_PER_PERIOD[self.rebalance_frequency]
+        window = f"one {self.rebalance_frequency} rebalance"
+        if self.factor.lower() in LOOKBACK_DAYS_FACTORS:
+            min_days += self.lookback_days
+            window += f" after the {self.lookback_days}-day lookback"
+        days = (end - start).days
+        if days < min_days:
+            raise ValueError(
+                f"Date range {start:%Y-%m-%d} to {end:%Y-%m-%d} is {days} days, "
+                f"shorter than the {min_days:g} days needed for {window}"
+            )
+
+        self.start_date = start.strftime("%Y-%m-%d")
+        self.end_date = end.strftime("%Y-%m-%d")
+        return self
+
+    @model_validator(mode="after")
+    def default_market_weighting(self):
+        """The market factor is market-cap weighted, so that's its weighting_method unless one is given"""
+        if self.factor.lower() == Factor.MARKET and "weighting_method" not in self.model_fields_set:
+            self.weighting_method = "market_cap"
+        return self
 
 
 class FactorPerformance(BaseModel):
@@ -94,19 +679,59 @@ class FactorPerformance(BaseModel):
     years: Optional[float]
     sharpe_ratio: Optional[float]
     sortino_ratio: Optional[float]
+    calmar_ratio: Optional[float] = None
+    alpha: Optional[float] = None
+    beta: Optional[float] = None
+    tracking_error: Optional[float] = None
+    information_ratio: Optional[float] = None
     long_only_returns: Optional[float]
     short_only_returns: Optional[float]
     start_date: Optional[str]
     end_date: Optional[str]
+    partial: bool = False
+
+
+class RunSummary(FactorPerformance):
+    """A logged run with its creation time, for cross-factor listings"""
+
+    created_at: Optional[str] = None
+
+
+class FactorRunListing(BaseModel):
+    """A factor's logged runs, most recent first, for a run picker"""
+
+    factor: str
+    total: int
+    runs: List[RunSummary]
+
+
+class RunListing(BaseModel):
+    """A page of runs across factors"""
+
+    total: int
+    offset: int
+    limit: int
+    runs: List[RunSummary]
 
 
 class FactorReturns(BaseModel):
     """Factor returns time series"""
 
     factor: str
+    run_id: str
+    total: int
     dates: List[str]
     returns: List[float]
     cumulative_returns: List[float]
+    rolling_sharpe: Optional[List[Optional[float]]] = Field(
+        None, description="Trailing annualized Sharpe ratio per period, when rolling_sharpe_window is requested"
+    )
+
+
+class RunSeries(FactorReturns):
+    """A run's full stored returns time series"""
+
+    ics: Optional[List[Optional[float]]] = Field(None, description="Per-period rank ICs, when the run saved them")
 
 
 class PortfolioComposition(BaseModel):
@@ -117,6 +742,53 @@ class PortfolioComposition(BaseModel):
     short_portfolio: Dict[str, dict]
 
 
+class SeriesPoint(BaseModel):
+    """A single observation of an external time series"""
+
+    date: str
+    value: float
+
+
+class CorrelateRequest(BaseModel):
+    """External series to correlate with a factor's returns"""
+
+    series: List[SeriesPoint] = Field(..., min_length=1, description="Observations as [{date, value}]")
+    run_id: Optional[str] = Field(None, description="Run to read (default: latest run with a time series)")
+    alignment: Literal["exact", "asof"] = Field(
+        "asof", description="Match factor dates exactly, or to the latest observation within the prior week"
+    )
+    min_observations: int = Field(10, ge=3, description="Minimum aligned observations required")
+
+
+class ExposuresRequest(BaseModel):
+    """Target returns to attribute to the factors: an uploaded series or a factor's latest run"""
+
+    series: Optional[List[SeriesPoint]] = Field(None, min_length=1, description="Target returns as [{date, value}]")
+    target_factor: Optional[str] = Field(None, description="Use this factor's latest run as the target instead")
+    factors: Optional[List[str]] = Field(
+        None, min_length=1, description="Factors to regress on (default: every other factor with a time series)"
+    )
+    newey_west_lags: Optional[int] = Field(None, ge=0, le=52, description="HAC lags (default: floor(4 * (n/100)^(2/9)))")
+    min_observations: int = Field(20, ge=5, description="Minimum dates shared by the target and every factor")
+    rebalance_frequency: Optional[Literal["daily", "weekly", "monthly"]] = Field(
+        None,
+        description="Frequency of the factor runs to regress on (default: the target factor's run's, "
+        "else weekly for an uploaded series)",
+    )
+
+    @model_validator(mode="after")
+    def check_target(self):
+        if (self.series is None) == (self.target_factor is None):
+            raise ValueError("Provide exactly one of series or target_factor")
+        return self
+
+
+class TimeSeriesUpload(BaseModel):
+    """Per-period returns to backfill for a logged run"""
+
+    returns: List[SeriesPoint] = Field(..., min_length=1, description="Factor returns as [{date, value}]")
+
+
 # Helper functions
 def load_factor_logs(factor: str) -> pd.DataFrame:
     """Load factor logs from CSV, handling evolving column formats"""
@@ -156,7 +828,8 @@ def load_factor_logs(factor: str) -> pd.DataFrame:
     # Convert numeric columns
     numeric_cols = [
         "breakpoint", "min_assets", "cumulative_returns", "annualized_return",
-        "years", "long_only_returns", "short_only_returns", "sharpe_ratio", "sortino_ratio"
+        "years", "long_only_returns", "short_only_returns", "sharpe_ratio", "sortino_ratio", "calmar_ratio",
+        "alpha", "beta", "tracking_error", "information_ratio",
     ]
     for col in numeric_cols:
         if col in df.columns:
@@ -165,124 +838,78 @@ def load_factor_logs(factor: str) -> pd.DataFrame:
     return df
 
 
-def get_api_data():
-    """Get ApiData instance"""
-    if not API_KEY:
-        raise HTTPException(
-            status_code=500,
-            detail="ARTEMIS_API_KEY environment variable not set",
-        )
-    ApiData, _, _, _ = _load_utils()
-    return ApiData(API_KEY)
-
+def complete_runs(df: pd.DataFrame) -> pd.DataFrame:
+    """
+    Leave out log rows of runs stopped early by their timeout or a shutdown (partial=True),
+    so a truncated backtest is never taken as a factor's latest run or ranked against full ones
+    """
+    if "partial" not in df.columns:
+        return df
+    return df[df["partial"].astype(str) != "True"]
 
-# Endpoints
-@app.get("/")
-async def root():
-    """API root endpoint"""
-    return {
-        "name": "Factor Models API",
-        "version": "1.0.0",
-        "available_factors": AVAILABLE_FACTORS,
-        "endpoints": {
-            "/factors": "List all available factors",
-            "/factors/{factor}/logs": "Get historical performance logs for a factor",
-            "/factors/{factor}/latest": "Get latest performance for a factor",
-            "/factors/compare": "Compare performance across all factors",
-            "/compute": "Compute a new factor model (POST)",
-        },
-    }
 
+def time_series_path(factor: str, run_id: str) -> Optional[Path]:
+    """The run's saved returns file, CSV or Parquet (whichever log_format it was logged with), if any"""
+    for extension in ("csv", "parquet"):
+        file_path = FACTOR_LOGS_DIR / f"{factor}_{run_id}_returns.{extension}"
+        if file_path.exists():
+            return file_path
+    return None
 
-@app.get("/factors")
-async def list_factors():
-    """List all available factors with descriptions"""
-    return {
-        "factors": [
-            {
-                "name": "smb",
-                "description": "Small Minus Big - Size factor based on market capitalization",
-                "signal": "Market cap (long small, short large)",
-            },
-            {
-                "name": "market",
-                "description": "Market factor - Top 10 assets by market cap",
-                "signal": "Market cap weighted top assets",
-            },
-            {
-                "name": "value",
-                "description": "Value factor based on MC-to-fees ratio",
-                "signal": "MC/Fees ratio (long high, short low)",
-            },
-            {
-                "name": "momentum",
-                "description": "Momentum factor - Simple trend following",
-                "signal": "Price momentum over lookback period",
-            },
-            {
-                "name": "momentum_v2",
-                "description": "Momentum V2 - Volatility-adjusted momentum",
-                "signal": "Momentum * (|mean_return| / std)",
-            },
-            {
-                "name": "growth",
-                "description": "Growth factor - Composite of fundamental metrics",
-                "signal": "Fees, DAU, revenue growth rates",
-            },
-        ]
-    }
 
+def load_time_series(factor: str, run_id: str) -> Optional[pd.DataFrame]:
+    """Load the returns time series saved for a run, or None if the run has no series file"""
+    file_path = time_series_path(factor, run_id)
+    if file_path is None:
+        return None
+    if file_path.suffix == ".parquet":
+        _, _, Logger, _ = _load_utils()
+        return Logger.load_time_series_parquet(file_path)
+    return pd.read_csv(file_path, parse_dates=["date"])
 
-@app.get("/factors/{factor}/logs", response_model=List[FactorPerformance])
-async def get_factor_logs(
-    factor: str,
-    limit: int = Query(10, ge=1, le=100, description="Number of recent runs to return"),
-):
-    """Get historical performance logs for a factor"""
-    if factor not in AVAILABLE_FACTORS:
-        raise HTTPException(status_code=404, detail=f"Factor '{factor}' not found")
 
-    df = load_factor_logs(factor)
-    df = df.tail(limit)
+def load_asset_returns(factor: str, run_id: str) -> Optional[pd.DataFrame]:
+    """Load the per-asset returns saved for a run, or None if the run has none (runs logged before they were saved)"""
+    file_path = FACTOR_LOGS_DIR / f"{factor}_{run_id}_asset_returns.csv"
+    if not file_path.exists():
+        return None
+    return pd.read_csv(file_path, parse_dates=["date"])
 
-    # Handle NaN values
-    df = df.replace({np.nan: None})
 
-    results = []
-    for _, row in df.iterrows():
-        results.append(
-            FactorPerformance(
-                run_id=str(row.get("run_id", "")),
-                factor=str(row.get("factor", factor)),
-                breakpoint=row.get("breakpoint"),
-                min_assets=row.get("min_assets"),
-                weighting_method=row.get("weighting_method"),
-                cumulative_returns=row.get("cumulative_returns"),
-                annualized_return=row.get("annualized_return"),
-                years=row.get("years"),
-                sharpe_ratio=row.get("sharpe_ratio"),
-                sortino_ratio=row.get("sortino_ratio"),
-                long_only_returns=row.get("long_only_returns"),
-                short_only_returns=row.get("short_only_returns"),
-                start_date=str(row.get("start_date")) if pd.notna(row.get("start_date")) else None,
-                end_date=str(row.get("end_date")) if pd.notna(row.get("end_date")) else None,
-            )
-        )
-    return results
+def load_compositions(factor: str, run_id: str) -> Optional[List[dict]]:
+    """Load the per-rebalance compositions saved for a run, or None if the run has none"""
+    file_path = FACTOR_LOGS_DIR / f"{factor}_{run_id}_compositions.json"
+    if not file_path.exists():
+        return None
+    with open(file_path, "r") as f:
+        return json.load(f)
 
 
-@app.get("/factors/{factor}/latest", response_model=FactorPerformance)
-async def get_factor_latest(factor: str):
-    """Get the latest performance metrics for a factor"""
-    if factor not in AVAILABLE_FACTORS:
-        raise HTTPException(status_code=404, detail=f"Factor '{factor}' not found")
+def load_latest_time_series(
+    factor: str, rebalance_frequency: Optional[str] = None
+) -> Optional[tuple[str, pd.DataFrame]]:
+    """
+    Find the most recent complete logged run of a factor that has a saved time series,
+    only considering runs at `rebalance_frequency` when given
+    """
+    try:
+        df = complete_runs(load_factor_logs(factor))
+    except HTTPException:
+        return None
+    if df.empty or "run_id" not in df.columns:
+        return None
 
-    df = load_factor_logs(factor)
-    if df.empty:
-        raise HTTPException(status_code=404, detail=f"No logs found for factor: {factor}")
+    for run_id in reversed(df["run_id"].astype(str).tolist()):
+        if rebalance_frequency is not None and run_rebalance_frequency(factor, run_id) != rebalance_frequency:
+            continue
+        series = load_time_series(factor, run_id)
+        if series is not None:
+            return run_id, series
+    return None
 
-    row = df.iloc[-1].replace({np.nan: None})
 
+def performance_from_log_row(row: pd.Series, factor: str) -> FactorPerformance:
+    """Build a FactorPerformance from a factor log row whose NaNs are already replaced with None"""
     return FactorPerformance(
         run_id=str(row.get("run_id", "")),
         factor=str(row.get("factor", factor)),
@@ -294,367 +921,3054 @@ async def get_factor_latest(factor: str):
         years=row.get("years"),
         sharpe_ratio=row.get("sharpe_ratio"),
         sortino_ratio=row.get("sortino_ratio"),
+        calmar_ratio=row.get("calmar_ratio"),
+        alpha=row.get("alpha"),
+        beta=row.get("beta"),
+        tracking_error=row.get("tracking_error"),
+        information_ratio=row.get("information_ratio"),
         long_only_returns=row.get("long_only_returns"),
         short_only_returns=row.get("short_only_returns"),
         start_date=str(row.get("start_date")) if pd.notna(row.get("start_date")) else None,
         end_date=str(row.get("end_date")) if pd.notna(row.get("end_date")) else None,
+        partial=str(row.get("partial")) == "True",
     )
 
 
-@app.get("/factors/compare")
-async def compare_factors():
-    """Compare latest performance across all factors"""
-    comparison = []
+def save_run_config(logger, result: dict):
+    """Save a run's echoed config to its manifest entry, leaving out the callback URL"""
+    logger.save_config({**result["config"], "callback_url": None}, CONFIG_SCHEMA_VERSION)
 
-    for factor in AVAILABLE_FACTORS:
-        try:
-            df = load_factor_logs(factor)
-            if not df.empty:
-                row = df.iloc[-1]
-                comparison.append(
-                    {
-                        "factor": factor,
-                        "annualized_return": row.get("annualized_return"),
-                        "cumulative_returns": row.get("cumulative_returns"),
-                        "sharpe_ratio": row.get("sharpe_ratio"),
-                        "sortino_ratio": row.get("sortino_ratio"),
+
+def load_run_config(factor: str, run_id: str) -> tuple[dict, int]:
+    """
+    A run's saved config and the config schema version it was saved under.
+
+    Runs logged before configs were saved have schema version 0; their config is
+    rebuilt from the FactorConfig fields among the factor's log columns.
+    """
+    manifest_path = FACTOR_LOGS_DIR / f"{factor}_index.json"
+    if manifest_path.exists():
+        with open(manifest_path) as f:
+            run = json.load(f).get("runs", {}).get(run_id, {})
+        if "config" in run:
+            return run["config"], run.get("config_schema_version", CONFIG_SCHEMA_VERSION)
+
+    logs = load_factor_logs(factor)
+    if "run_id" not in logs.columns or run_id not in set(logs["run_id"].astype(str)):
+        raise HTTPException(status_code=404, detail=f"Run '{run_id}' not found for factor '{factor}'")
+
+    row = logs[logs["run_id"].astype(str) == run_id].iloc[-1]
+    config = {"factor": str(factor)}
+    for name in [*FactorConfig.model_fields, "lookback_periods"]:
+        value = row.get(name)
+        if name != "factor" and value is not None and not pd.isna(value):
+            config[name] = value.item() if isinstance(value, np.generic) else value
+    return config, 0
+
+
+def upgrade_run_config(saved: dict, schema_version: int) -> tuple[FactorConfig, dict, List[str]]:
+    """
+    Deserialize a saved run config under the current FactorConfig schema.
+
+    Returns the config, the run's extra compute parameters (lookback_periods,
+    weights) and upgrade warnings: one per result-affecting field the saved config
+    lacks, which takes the current default, and one per saved field that no longer exists.
+    A missing field added in a later schema version than the run's (see
+    CONFIG_FIELDS_ADDED) is reported as such.
+    """
+    saved = dict(saved)
+    params = {name: saved.pop(name) for name in ("lookback_periods", "weights") if name in saved}
+    warnings = [
+        f"'{name}' is no longer a config field and was ignored" for name in saved if name not in FactorConfig.model_fields
+    ]
+    for name, field in FactorConfig.model_fields.items():
+        if name not in saved and name not in RESULT_NEUTRAL_CONFIG_FIELDS:
+            default = field.get_default(call_default_factory=True)
+            if CONFIG_FIELDS_ADDED.get(name, 1) > schema_version:
+                reason = f"was added in config schema v{CONFIG_FIELDS_ADDED[name]}, after this run's v{schema_version}"
+            else:
+                reason = f"wasn't saved (config schema v{schema_version})"
+            warnings.append(f"'{name}' {reason}; using the current default {default!r}, which may change results")
+
+    # Dates are saved resolved, so the lookback_period they came from would conflict with start_date
+    fields = {
+        name: value
+        for name, value in saved.items()
+        if name in FactorConfig.model_fields and name not in {"run_id", "callback_url", "lookback_period"}
+    }
+    try:
+        config = FactorConfig.model_validate(fields)
+    except ValidationError as e:
+        errors = "; ".join(f"{'.'.join(map(str, err['loc']))}: {err['msg']}" for err in e.errors())
+        raise HTTPException(status_code=400, detail=f"Saved config can't be upgraded: {errors}")
+    return config, params, warnings
+
+
+def load_run_created_at(factor: str, run_ids: List[str]) -> Dict[str, datetime]:
+    """
+    Creation times of a factor's runs: from its index manifest, else the timestamp in
+    an auto-generated run id. Runs with neither (old custom ids) are absent.
+    """
+    from utils import parse_run_id_time
+
+    manifest_path = FACTOR_LOGS_DIR / f"{factor}_index.json"
+    indexed = {}
+    if manifest_path.exists():
+        with open(manifest_path) as f:
+            indexed = json.load(f).get("runs", {})
+
+    created_at = {}
+    for run_id in run_ids:
+        if run_id in indexed:
+            created_at[run_id] = datetime.fromisoformat(indexed[run_id]["created_at"])
+        elif (generated_at := parse_run_id_time(run_id)) is not None:
+            created_at[run_id] = generated_at
+    return created_at
+
+
+def ensure_endpoint_factor(config: FactorConfig, expected: Factor):
+    """Reject a compute request whose config names a different factor than its endpoint, or sets options it ignores"""
+    if config.factor.lower() != expected:
+        raise HTTPException(
+            status_code=400,
+            detail=f"Factor name '{config.factor}' does not match endpoint. Expected '{expected}'.",
+        )
+    if config.normalize_by_mc and expected not in NORMALIZE_BY_MC_FACTORS:
+        raise HTTPException(
+            status_code=400,
+            detail=f"normalize_by_mc is only supported for {NORMALIZE_BY_MC_FACTORS}, not '{expected}'",
+        )
+
+
+def signal_long_high(config: FactorConfig, factor: Factor) -> bool:
+    """Whether high signal values go long for a run: the config's signal_direction, else the factor's"""
+    return (config.signal_direction or SIGNAL_DIRECTIONS[factor]) == "high"
+
+
+def ensure_run_id_available(factor: str, run_id: Optional[str]):
+    """Reject a caller-supplied run id that already exists in the factor's logs"""
+    if run_id is None:
+        return
+    try:
+        df = load_factor_logs(factor)
+    except HTTPException:
+        return
+    existing = set(df["run_id"].astype(str)) if "run_id" in df.columns else set()
+    if run_id in existing or time_series_path(factor, run_id) is not None:
+        raise HTTPException(
+            status_code=400,
+            detail=f"Run id '{run_id}' already exists for factor '{factor}'",
+        )
+
+
+def load_run_time_series(factor: str, run_id: Optional[str] = None) -> tuple[str, pd.DataFrame]:
+    """Load a specific run's time series, or the latest run's when run_id is None, raising a 404 if missing"""
+    Factor.parse(factor)
+
+    if run_id is None:
+        latest = load_latest_time_series(factor)
+        if latest is None:
+            raise HTTPException(status_code=404, detail=f"No time series found for factor: {factor}")
+        return latest
+
+    series = load_time_series(factor, run_id)
+    if series is None:
+        raise HTTPException(status_code=404, detail=f"No time series found for run '{run_id}'")
+    return run_id, series
+
+
+def negotiate_format(accept: Optional[str]) -> str:
+    """Pick a tabular response format from an Accept header, falling back to JSON"""
+    accept = (accept or "").lower()
+    if "text/csv" in accept:
+        return "csv"
+    if "application/vnd.apache.parquet" in accept:
+        return "parquet"
+    if "application/octet-stream" in accept:
+        return "octet-stream"
+    return "json"
+
+
+def tabular_response(df: pd.DataFrame, fmt: str, filename: str) -> Response:
+    """Serialize a DataFrame as a CSV or Parquet download"""
+    if fmt == "csv":
+        return Response(
+            content=df.to_csv(index=False),
+            media_type="text/csv",
+            headers={"Content-Disposition": f'attachment; filename="{filename}.csv"'},
+        )
+
+    buffer = io.BytesIO()
+    df.to_parquet(buffer, index=False)
+    media_type = "application/vnd.apache.parquet" if fmt == "parquet" else "application/octet-stream"
+    return Response(
+        content=buffer.getvalue(),
+        media_type=media_type,
+        headers={"Content-Disposition": f'attachment; filename="{filename}.parquet"'},
+    )
+
+
+def stream_csv(frames: Iterator[pd.DataFrame], columns: List[str], filename: str) -> StreamingResponse:
+    """Serialize DataFrame chunks as a CSV download as they're generated, instead of one buffered string"""
+
+    def chunks():
+        header = True
+        for frame in frames:
+            yield frame.to_csv(index=False, header=header, columns=columns)
+            header = False
+        if header:
+            yield pd.DataFrame(columns=columns).to_csv(index=False)
+
+    return StreamingResponse(
+        chunks(),
+        media_type="text/csv",
+        headers={"Content-Disposition": f'attachment; filename="{filename}.csv"'},
+    )
+
+
+def parse_series_points(points: List[SeriesPoint]) -> pd.Series:
+    """Convert uploaded [{date, value}] points to a date-sorted series, raising a 400 on bad or duplicate dates"""
+    try:
+        series = pd.Series(
+            [point.value for point in points],
+            index=pd.to_datetime([point.date for point in points], utc=True).tz_convert(None).normalize(),
+        ).sort_index()
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=f"Invalid date in series: {str(e)}")
+    if series.index.has_duplicates:
+        raise HTTPException(status_code=400, detail="Series contains duplicate dates")
+    return series
+
+
+def to_optional_floats(values) -> List[Optional[float]]:
+    """Convert a series to a JSON-safe list, mapping NaN to None"""
+    return [None if pd.isna(value) else float(value) for value in values]
+
+
+def parse_query_date(value: Optional[str], name: str) -> Optional[pd.Timestamp]:
+    """Parse an optional YYYY-MM-DD query parameter, raising a 400 on bad input"""
+    if value is None:
+        return None
+    try:
+        return pd.Timestamp(value)
+    except ValueError:
+        raise HTTPException(status_code=400, detail=f"Invalid {name} '{value}', expected YYYY-MM-DD")
+
+
+def get_api_data():
+    """Get ApiData instance"""
+    if not API_KEY:
+        raise HTTPException(
+            status_code=500,
+            detail="ARTEMIS_API_KEY environment variable not set",
+        )
+    ApiData, _, _, _ = _load_utils()
+    return ApiData(API_KEY)
+
+
+def format_recent_returns(factor_returns: dict, returns_format: str, periods: int = 10):
+    """
+    The last `periods` portfolio returns keyed by ISO rebalance date, oldest first.
+
+    "ordered" emits [date, return] pairs, since JSON object key order isn't
+    guaranteed to survive every client's parser.
+    """
+    recent = sorted(factor_returns.items())[-periods:]
+    pairs = [(pd.Timestamp(date).strftime("%Y-%m-%d"), float(value)) for date, value in recent]
+    if returns_format == "ordered":
+        return [list(pair) for pair in pairs]
+    return dict(pairs)
+
+
+def form_long_short_portfolios(factor_model, signal_col: str, long_high: bool, count_unrankable: bool = False):
+    """Form the long/short legs, mapping an empty or overlapping leg under an "error" policy to a 400"""
+    try:
+        factor_model.form_long_short_portfolios(signal_col, long_high=long_high, count_unrankable=count_unrankable)
+    except _EmptyLegError as e:
+        raise HTTPException(status_code=400, detail=f"Empty portfolio leg: {str(e)}")
+    except _OverlappingLegsError as e:
+        raise HTTPException(status_code=400, detail=f"Overlapping portfolio legs: {str(e)}")
+
+
+def compute_deadline(config: FactorConfig) -> Optional[float]:
+    """The time.monotonic() deadline for a run starting now, or None without a timeout"""
+    if config.timeout_seconds is None:
+        return None
+    return time.monotonic() + config.timeout_seconds
+
+
+def ensure_factor_returns(factor_model):
+    """Fail a run that formed no periods: 504 if it was stopped before the first one, else 400"""
+    # A streamed run whose client disconnected stops here, before anything is logged
+    progress = _compute_progress.get()
+    if progress is not None:
+        progress.raise_if_cancelled()
+    if factor_model.factor_returns:
+        return
+    if factor_model.partial:
+        raise HTTPException(
+            status_code=504, detail="Compute stopped before any rebalance period completed - raise timeout_seconds"
+        )
+    raise HTTPException(status_code=400, detail="No factor returns computed - check data availability")
+
+
+def record_portfolio_quality(factor_model, data_quality: dict):
+    """
+    Report assets dropped for a NaN signal, rebalances skipped for an empty leg,
+    assets netted across both legs and where a partial run stopped
+    """
+    data_quality["nan_signal_assets"] = sum(factor_model.nan_signal_counts.values())
+    data_quality["empty_leg_rebalances"] = [
+        pd.Timestamp(date).strftime("%Y-%m-%d") for date in factor_model.empty_leg_dates
+    ]
+    if factor_model.covariance_fallback_assets:
+        log.warning(
+            f"{factor_model.factor} run {factor_model.run_id}: {factor_model.covariance_fallback_assets} "
+            "holdings lacked a full covariance window and were equal-weighted"
+        )
+        data_quality["covariance_fallback_assets"] = factor_model.covariance_fallback_assets
+    if factor_model.overlapping_assets:
+        log.warning(
+            f"{factor_model.factor} run {factor_model.run_id}: assets selected for both legs were netted "
+            f"on {len(factor_model.overlapping_assets)} rebalances"
+        )
+        data_quality["overlapping_assets"] = {
+            pd.Timestamp(date).strftime("%Y-%m-%d"): assets for date, assets in factor_model.overlapping_assets.items()
+        }
+    if factor_model.partial:
+        data_quality["stopped_at"] = pd.Timestamp(factor_model.stopped_at).strftime("%Y-%m-%d")
+
+
+def ensure_benchmark_mapped(config: FactorConfig):
+    """Reject a benchmark without Coinbase prices before the run fetches anything"""
+    from utils import resolve_symbol
+
+    if resolve_symbol(config.benchmark) is None:
+        raise HTTPException(status_code=400, detail=f"Benchmark '{config.benchmark}' has no Coinbase price mapping")
+
+
+def benchmark_metrics(factor_model, config: FactorConfig) -> dict:
+    """
+    The run's per-period returns against config.benchmark's over the same dates: alpha and beta
+    from regressing one on the other, and the tracking error and information ratio of the
+    active returns (factor minus benchmark), annualized for the rebalance frequency.
+
+    The benchmark's Coinbase returns are fetched once per run (once per sweep) over the
+    config's date range, at its rebalance frequency. All are None with fewer than
+    BENCHMARK_MIN_PERIODS overlapping periods, or when the prices can't be fetched,
+    which doesn't fail the run.
+    """
+    metrics = {"alpha": None, "beta": None, "tracking_error": None, "information_ratio": None}
+    start, end = pd.Timestamp(config.start_date), pd.Timestamp(config.end_date)
+    try:
+        benchmark = reuse_sweep_fetch(
+            ("benchmark", config.benchmark, config.start_date, config.end_date, config.rebalance_frequency),
+            {},
+            lambda _: fetch_asset_returns(config.benchmark, start, end, config.rebalance_frequency),
+        )
+    except HTTPException as e:
+        log.warning(f"No benchmark metrics for run {factor_model.run_id}: '{config.benchmark}': {e.detail}")
+        return metrics
+
+    returns = pd.Series(factor_model.factor_returns, dtype=float)
+    returns.index = pd.to_datetime(returns.index)
+    aligned = pd.DataFrame({"factor_return": returns, "benchmark_return": benchmark}).dropna()
+    if len(aligned) < BENCHMARK_MIN_PERIODS:
+        return metrics
+
+    regression = ols_regression(aligned["factor_return"], aligned["benchmark_return"])
+    active = aligned["factor_return"] - aligned["benchmark_return"]
+    annualization = periods_per_year(config)
+    return {
+        "alpha": regression["alpha"],
+        "beta": regression["betas"][0],
+        "tracking_error": tracking_error(active, periods_per_year=annualization),
+        "information_ratio": information_ratio(active, periods_per_year=annualization),
+    }
+
+
+def summarize_performance(factor_model, config: FactorConfig) -> dict:
+    """
+    Compute headline performance metrics from a factor model's per-period returns,
+    annualized for the run's rebalance_frequency. Returns are net of transaction
+    costs; gross_cumulative_returns is the cumulative return before them.
+
+    Leg sign convention: long_sharpe, short_sharpe and spread_return are for the
+    positions held, so the short leg earns the negated return of the shorted assets
+    and a positive short_sharpe means shorting them made money. spread_return
+    compounds each period's long leg plus short position (long minus shorted assets),
+    so for a long_short run it matches cumulative_returns. long_only_returns and
+    short_only_returns are the cumulative returns of the assets held in each leg, so
+    short_only_returns is the one shorted-assets figure (as it has always been logged).
+    Leg metrics are None for a leg the run's portfolio_mode doesn't hold.
+    mean_ic and ic_ir summarize the per-period rank ICs of the signal.
+    alpha (per period), beta, tracking_error and information_ratio are against
+    config.benchmark (see benchmark_metrics).
+
+    Runs shorter than `config.min_annualization_days` compound a few periods to a
+    full year, so their annualized return is flagged unreliable or suppressed.
+    """
+    _, _, _, cumulative_returns = _load_utils()
+
+    returns_df = cumulative_returns(factor_model.factor_returns)
+    total_cumulative = returns_df["cumulative_returns"].iloc[-1] if not returns_df.empty else 0
+    gross_returns_df = cumulative_returns(factor_model.gross_factor_returns)
+    gross_cumulative = gross_returns_df["cumulative_returns"].iloc[-1] if not gross_returns_df.empty else 0
+
+    dates_list = list(factor_model.factor_returns.keys())
+    if len(dates_list) >= 2:
+        days = (pd.to_datetime(dates_list[-1]) - pd.to_datetime(dates_list[0])).days
+    else:
+        days = 0
+    years = days / 365 if days > 0 else 0
+    annualized = annualize_return(total_cumulative, days)
+
+    annualization_reliable = days >= config.min_annualization_days
+    if not annualization_reliable and config.short_window_annualization == "suppress":
+        annualized = None
+
+    annualization = periods_per_year(config)
+    returns_series = pd.Series(list(factor_model.factor_returns.values()))
+    sharpe = sharpe_ratio(returns_series, periods_per_year=annualization)
+
+    # Sortino is None with fewer than two losing periods, Calmar when the factor never drew down
+    sortino = sortino_ratio(returns_series, periods_per_year=annualization)
+    drawdown = max_drawdown(returns_df["cumulative_returns"]) if not returns_df.empty else 0.0
+    calmar = calmar_ratio(annualized, drawdown)
+
+    # Calculate long-only and short-only cumulative returns
+    long_returns_df = cumulative_returns(factor_model.long_portfolio_returns)
+    short_returns_df = cumulative_returns(factor_model.short_portfolio_returns)
+
+    # Leg attribution, signed as in the docstring. Long-only factors and portfolio
+    # modes have no short leg, so its metrics are None, and short-only modes have no long leg.
+    if config.portfolio_mode != "short_only":
+        long_only_cumulative = (
+            float(long_returns_df["cumulative_returns"].iloc[-1]) if not long_returns_df.empty else 0.0
+        )
+        long_sharpe = sharpe_ratio(list(factor_model.long_portfolio_returns.values()), periods_per_year=annualization)
+    else:
+        long_only_cumulative = long_sharpe = None
+    if factor_model.short_portfolio_returns:
+        short_only_cumulative = float(short_returns_df["cumulative_returns"].iloc[-1])
+        short_sharpe = sharpe_ratio(
+            [-r for r in factor_model.short_portfolio_returns.values()], periods_per_year=annualization
+        )
+    else:
+        short_only_cumulative = short_sharpe = None
+    spread = [
+        long_return - factor_model.short_portfolio_returns[date]
+        for date, long_return in factor_model.long_portfolio_returns.items()
+        if date in factor_model.short_portfolio_returns
+    ]
+    if long_only_cumulative is not None and short_only_cumulative is not None and spread:
+        spread_return = float(compound_returns(spread).iloc[-1])
+    else:
+        spread_return = None
+
+    ics = pd.Series(list(factor_model.period_ics.values()), dtype=float).dropna()
+    benchmark = benchmark_metrics(factor_model, config)
+
+    return {
+        "cumulative_returns": float(total_cumulative),
+        "gross_cumulative_returns": float(gross_cumulative),
+        "mean_turnover": float(np.mean(list(factor_model.turnover.values()))) if factor_model.turnover else None,
+        "annualized_return": float(annualized) if annualized is not None else None,
+        "annualized_return_reliable": annualization_reliable,
+        "sharpe_ratio": float(sharpe),
+        "sortino_ratio": sortino,
+        "calmar_ratio": calmar,
+        "max_drawdown": drawdown,
+        "years": float(years),
+        "num_periods": len(factor_model.factor_returns),
+        "long_only_returns": long_only_cumulative,
+        "short_only_returns": short_only_cumulative,
+        "spread_return": spread_return,
+        "long_sharpe": long_sharpe,
+        "short_sharpe": short_sharpe,
+        "mean_ic": float(ics.mean()) if not ics.empty else None,
+        "ic_ir": ic_information_ratio(ics, periods_per_year=annualization),
+        "alpha": benchmark["alpha"],
+        "beta": benchmark["beta"],
+        "tracking_error": benchmark["tracking_error"],
+        "information_ratio": benchmark["information_ratio"],
+        "start_date": str(dates_list[0]) if dates_list else None,
+        "end_date": str(dates_list[-1]) if dates_list else None,
+    }
+
+
+def finalize_factor_run(
+    factor: Factor,
+    config: FactorConfig,
+    factor_model,
+    data_quality: dict,
+    config_extra: Optional[dict] = None,
+    log_extra: Optional[dict] = None,
+) -> dict:
+    """
+    Finish a run whose portfolios are formed: summarize its performance, log it and
+    return its result. Shared by every factor, so each run only sets up its signal and legs.
+
+    config_extra is echoed in the result's config alongside the FactorConfig fields
+    (e.g. lookback_periods), and log_extra adds the factor's own columns to its log row.
+    """
+    ensure_factor_returns(factor_model)
+
+    performance = summarize_performance(factor_model, config)
+    record_portfolio_quality(factor_model, data_quality)
+
+    result = {
+        "factor": factor,
+        "run_id": factor_model.run_id,
+        "config": {**config.model_dump(), **(config_extra or {})},
+        "performance": {
+            key: value for key, value in performance.items() if key not in ("start_date", "end_date")
+        },
+        "returns": format_recent_returns(factor_model.factor_returns, config.returns_format),
+        "partial": factor_model.partial,
+        "data_quality": data_quality,
+    }
+
+    # Log results
+    _, _, Logger, _ = _load_utils()
+    logger = Logger(FACTOR_LOGS_DIR, factor_model)
+    factor_model.results_dict = {
+        "cumulative_returns": performance["cumulative_returns"],
+        "annualized_return": performance["annualized_return"],
+        "sharpe_ratio": performance["sharpe_ratio"],
+        "sortino_ratio": performance["sortino_ratio"],
+        "calmar_ratio": performance["calmar_ratio"],
+        "alpha": performance["alpha"],
+        "beta": performance["beta"],
+        "tracking_error": performance["tracking_error"],
+        "information_ratio": performance["information_ratio"],
+        "years": performance["years"],
+        "long_only_returns": performance["long_only_returns"],
+        "short_only_returns": performance["short_only_returns"],
+        **(log_extra or {}),
+        "start_date": performance["start_date"],
+        "end_date": performance["end_date"],
+        "partial": factor_model.partial,
+        "rebalance_frequency": config.rebalance_frequency,
+        "transaction_cost_bps": config.transaction_cost_bps,
+    }
+    logger.log_results(factor_model.results_dict)
+    save_run_config(logger, result)
+    logger.save_time_series(factor_model.factor_returns, factor_model.period_ics, log_format=config.log_format)
+    logger.save_compositions(factor_model.factor_assets)
+    logger.save_asset_returns(factor_model.df)
+
+    return result
+
+
+# Endpoints
+@app.get("/")
+async def root():
+    """API root endpoint"""
+    return {
+        "name": "Factor Models API",
+        "version": "1.0.0",
+        "available_factors": [factor.value for factor in Factor],
+        "endpoints": {
+            "/factors": "List all available factors",
+            "/factors/{factor}/logs": "Get historical performance logs for a factor",
+            "/factors/{factor}/latest": "Get latest performance for a factor",
+            "/factors/compare": "Compare performance across all factors",
+            "/factors/time-series": "Get returns time series of the latest run per factor",
+            "/compute": "Compute a new factor model (POST)",
+            "/compute/{factor}/universe-trace": "Count the assets surviving each universe filter on a date",
+            "/universe": "List the Artemis symbols crypto factors can trade, with their Coinbase products",
+        },
+    }
+
+
+@app.get("/factors")
+async def list_factors():
+    """List all available factors with descriptions"""
+    return {
+        "factors": [
+            {
+                "name": "smb",
+                "description": "Small Minus Big - Size factor based on market capitalization",
+                "signal": "Market cap (long small, short large)",
+            },
+            {
+                "name": "market",
+                "description": "Market factor - Top 10 assets by market cap",
+                "signal": "Market cap weighted top assets",
+            },
+            {
+                "name": "value",
+                "description": "Value factor based on MC-to-fees ratio",
+                "signal": "MC/Fees ratio (long low, short high)",
+            },
+            {
+                "name": "momentum",
+                "description": "Momentum factor - Simple trend following",
+                "signal": "Price momentum over lookback period",
+            },
+            {
+                "name": "momentum_v2",
+                "description": "Momentum V2 - Volatility-adjusted momentum",
+                "signal": "Momentum * (|mean_return| / std)",
+            },
+            {
+                "name": "growth",
+                "description": "Growth factor - Composite of fundamental metrics",
+                "signal": "Fees, DAU, revenue growth rates",
+            },
+            {
+                "name": "composite",
+                "description": "Composite factor - Weighted blend of base factor signals",
+                "signal": "Weighted sum of cross-sectional z-scores (smb, momentum, value, growth)",
+            },
+            {
+                "name": "equity",
+                "description": "Equity momentum factor over a US large-cap ticker universe",
+                "signal": "Trailing price return over lookback_days (Yahoo Finance prices)",
+            },
+        ]
+    }
+
+
+@app.get("/factors/{factor}/metrics")
+async def get_factor_metrics(
+    factor: str,
+    symbol: Optional[str] = Query(None, description="Check each metric's availability for this Artemis symbol"),
+):
+    """List the Artemis metrics and price fields a factor consumes, optionally checking availability"""
+    factor = Factor.parse(factor)
+
+    available = None
+    if symbol:
+        api_data = get_api_data()
+        try:
+            available = set(await asyncio.to_thread(api_data.get_all_metrics_for_symbol, symbol))
+        except Exception as e:
+            raise HTTPException(status_code=502, detail=f"Failed to list Artemis metrics for '{symbol}': {str(e)}")
+
+    return {
+        "factor": factor,
+        "compute_endpoint": f"/compute/{factor}" if factor in FACTOR_REGISTRY else None,
+        "symbol": symbol,
+        "artemis_metrics": [
+            {
+                "metric": metric,
+                "source": "artemis",
+                "available": None if available is None else metric in available,
+            }
+            for metric in FACTOR_METRICS[factor]
+        ],
+        "price_fields": [{"field": field, "source": PRICE_SOURCES[factor]} for field in PRICE_FIELDS],
+    }
+
+
+# Latest tradeable universe and the time.monotonic() it expires at, guarded by the lock
+_universe_cache = {"expires_at": 0.0, "universe": None}
+_universe_lock = threading.Lock()
+
+
+def load_tradeable_universe() -> List[dict]:
+    """
+    Artemis crypto symbols with a Coinbase mapping and their product ids (blocking).
+
+    Cached for UNIVERSE_CACHE_SECONDS; concurrent callers wait for one Artemis fetch.
+    """
+    from utils import ARTEMIS_TO_COINBASE_MAP
+
+    with _universe_lock:
+        if _universe_cache["universe"] is not None and _universe_cache["expires_at"] > time.monotonic():
+            return _universe_cache["universe"]
+        symbols = get_api_data().list_crypto_symbols()
+        universe = [
+            {"symbol": symbol, "coinbase_product_id": ARTEMIS_TO_COINBASE_MAP[symbol]}
+            for symbol in sorted(set(symbols))
+            if symbol in ARTEMIS_TO_COINBASE_MAP
+        ]
+        _universe_cache.update(universe=universe, expires_at=time.monotonic() + UNIVERSE_CACHE_SECONDS)
+        return universe
+
+
+@app.get("/universe")
+async def get_universe():
+    """List the Artemis symbols crypto factors can trade: those mapped to a Coinbase product"""
+    try:
+        universe = await asyncio.to_thread(load_tradeable_universe)
+    except HTTPException:
+        raise
+    except Exception as e:
+        raise HTTPException(status_code=502, detail=f"Failed to list Artemis symbols: {str(e)}")
+    return {"num_symbols": len(universe), "symbols": universe}
+
+
+@app.get("/factors/{factor}/logs", response_model=List[FactorPerformance])
+async def get_factor_logs(
+    factor: str,
+    limit: int = Query(10, ge=1, le=100, description="Number of recent runs to return"),
+    offset: int = Query(0, ge=0, description="Number of most recent runs to skip before taking limit"),
+    include_partial: bool = Query(False, description="Also list runs stopped early by their timeout or a shutdown"),
+    accept: Optional[str] = Header(None),
+):
+    """
+    Get historical performance logs for a factor as JSON, CSV or Parquet (via Accept).
+
+    Pages are counted back from the most recent run: the window skips the latest
+    `offset` runs and takes the `limit` before them, oldest first. An offset past
+    the oldest run gives an empty page. Partial runs are left out, as in every other
+    listing, unless include_partial is set.
+    """
+    factor = Factor.parse(factor)
+
+    df = load_factor_logs(factor)
+    if not include_partial:
+        df = complete_runs(df)
+    end = max(len(df) - offset, 0)
+    df = df.iloc[max(end - limit, 0) : end]
+
+    # Handle NaN values
+    df = df.replace({np.nan: None})
+
+    results = []
+    for _, row in df.iterrows():
+        results.append(performance_from_log_row(row, factor))
+
+    fmt = negotiate_format(accept)
+    if fmt != "json":
+        return tabular_response(
+            pd.DataFrame([result.model_dump() for result in results], columns=list(FactorPerformance.model_fields)),
+            fmt,
+            f"{factor}_logs",
+        )
+    return results
+
+
+@app.get("/factors/{factor}/latest", response_model=FactorPerformance)
+async def get_factor_latest(factor: str):
+    """Get the performance metrics of a factor's latest complete run"""
+    factor = Factor.parse(factor)
+
+    df = complete_runs(load_factor_logs(factor))
+    if df.empty:
+        raise HTTPException(status_code=404, detail=f"No logs found for factor: {factor}")
+
+    row = df.iloc[-1].replace({np.nan: None})
+
+    return performance_from_log_row(row, factor)
+
+
+@app.get("/factors/{factor}/runs", response_model=FactorRunListing)
+async def list_factor_runs(factor: str):
+    """
+    List a factor's logged runs, most recent first, with their dates, key metrics and creation time.
+
+    Log rows whose run id is missing or malformed can't be addressed by the run
+    endpoints, so they're skipped, as are partial runs.
+    """
+    factor = Factor.parse(factor)
+
+    try:
+        df = complete_runs(load_factor_logs(factor))
+    except HTTPException:
+        return FactorRunListing(factor=factor, total=0, runs=[])
+    if df.empty or "run_id" not in df.columns:
+        return FactorRunListing(factor=factor, total=0, runs=[])
+
+    rows = df.replace({np.nan: None})
+    well_formed = rows["run_id"].map(
+        lambda run_id: run_id is not None and re.match(RUN_ID_PATTERN, str(run_id)) is not None
+    )
+    rows = rows[well_formed]
+    created_at = load_run_created_at(factor, rows["run_id"].astype(str).tolist())
+
+    runs = []
+    for _, row in rows.iloc[::-1].iterrows():
+        run = performance_from_log_row(row, factor)
+        run_created_at = created_at.get(run.run_id)
+        runs.append(
+            RunSummary(
+                **run.model_dump(),
+                created_at=run_created_at.isoformat(timespec="seconds") if run_created_at else None,
+            )
+        )
+
+    return FactorRunListing(factor=factor, total=len(runs), runs=runs)
+
+
+@app.get("/runs", response_model=RunListing)
+async def list_runs(
+    factor: Optional[str] = Query(None, description="Comma-separated factor names (default: all)"),
+    start_after: Optional[str] = Query(None, description="Only runs created after this date (YYYY-MM-DD)"),
+    min_sharpe: Optional[float] = Query(None, description="Only runs with at least this Sharpe ratio"),
+    sort_by: Literal[
+        "created_at", "sharpe_ratio", "sortino_ratio", "calmar_ratio", "annualized_return", "cumulative_returns"
+    ] = Query("created_at", description="Field to sort runs by"),
+    order: Literal["asc", "desc"] = Query("desc", description="Sort direction"),
+    offset: int = Query(0, ge=0, description="Number of matching runs to skip"),
+    limit: int = Query(50, ge=1, le=500, description="Maximum number of runs to return"),
+):
+    """
+    List logged runs across factors, filtered and sorted, one page at a time.
+
+    Filters combine: a run must match every one given. Runs with an unknown
+    creation time are excluded by start_after, runs without a Sharpe ratio by
+    min_sharpe, and either sorts last regardless of order. Partial runs are left out.
+    """
+    requested = [Factor.parse(name) for name in factor.split(",")] if factor else list(Factor)
+    created_after = parse_query_date(start_after, "start_after")
+
+    runs = []
+    for name in requested:
+        try:
+            df = complete_runs(load_factor_logs(name))
+        except HTTPException:
+            continue
+        if df.empty:
+            continue
+        created_at = load_run_created_at(name, df["run_id"].astype(str).tolist() if "run_id" in df.columns else [])
+        for _, row in df.replace({np.nan: None}).iterrows():
+            run = performance_from_log_row(row, name)
+            run_created_at = created_at.get(run.run_id)
+            if created_after is not None and (run_created_at is None or run_created_at <= created_after):
+                continue
+            if min_sharpe is not None and (run.sharpe_ratio is None or run.sharpe_ratio < min_sharpe):
+                continue
+            runs.append(
+                RunSummary(
+                    **run.model_dump(),
+                    created_at=run_created_at.isoformat(timespec="seconds") if run_created_at else None,
+                )
+            )
+
+    # Sort the known values, then append runs missing the field so they're last either way
+    known = [run for run in runs if getattr(run, sort_by) is not None]
+    missing = [run for run in runs if getattr(run, sort_by) is None]
+    known.sort(key=lambda run: getattr(run, sort_by), reverse=order == "desc")
+    ordered = known + missing
+
+    return RunListing(total=len(ordered), offset=offset, limit=limit, runs=ordered[offset : offset + limit])
+
+
+# Metrics of the latest run per factor that /factors/compare returns and can sort by
+CompareMetric = Literal[
+    "annualized_return", "cumulative_returns", "sharpe_ratio", "sortino_ratio", "calmar_ratio", "years"
+]
+COMPARE_METRICS = get_args(CompareMetric)
+
+
+@app.get("/factors/compare")
+async def compare_factors(
+    sort_by: CompareMetric = Query("annualized_return", description="Metric to rank factors by"),
+    order: Literal["asc", "desc"] = Query("desc", description="Sort direction"),
+    fmt: Optional[Literal["json", "csv"]] = Query(
+        None, alias="format", description="Response format (default: from the Accept header, else JSON)"
+    ),
+    accept: Optional[str] = Header(None),
+):
+    """
+    Compare the latest complete run of every factor, ranked by a metric. Factors
+    missing the metric rank last in either order, as in /runs.
+
+    As CSV (`format=csv` or Accept: text/csv) it's the same rows in the same order,
+    one column per metric, with missing metrics as empty cells.
+    """
+    comparison = []
+
+    for factor in Factor:
+        try:
+            df = complete_runs(load_factor_logs(factor))
+            if not df.empty:
+                # Runs logged before a metric existed have no value for it
+                row = df.iloc[-1].replace({np.nan: None})
+                comparison.append(
+                    {
+                        "factor": factor.value,
+                        "annualized_return": row.get("annualized_return"),
+                        "cumulative_returns": row.get("cumulative_returns"),
+                        "sharpe_ratio": row.get("sharpe_ratio"),
+                        "sortino_ratio": row.get("sortino_ratio"),
+                        "calmar_ratio": row.get("calmar_ratio"),
                         "years": row.get("years"),
                     }
                 )
-        except Exception:
-            # Skip factors that fail to load (missing files, parse errors, etc.)
-            continue
+        except Exception:
+            # Skip factors that fail to load (missing files, parse errors, etc.)
+            continue
+
+    # Sort the known values, then append factors missing the metric so they're last either way
+    known = [entry for entry in comparison if entry.get(sort_by) is not None]
+    missing = [entry for entry in comparison if entry.get(sort_by) is None]
+    known.sort(key=lambda entry: entry[sort_by], reverse=order == "desc")
+    comparison = known + missing
+
+    if (fmt or negotiate_format(accept)) == "csv":
+        df = pd.DataFrame(comparison, columns=["factor", *COMPARE_METRICS])
+        return tabular_response(df, "csv", "factor_comparison")
+    return {"comparison": comparison}
+
+
+@app.get("/factors/time-series")
+async def get_factors_time_series(
+    factors: Optional[str] = Query(None, description="Comma-separated factor names (default: all)"),
+    start_date: Optional[str] = Query(None, description="Only include dates on or after (YYYY-MM-DD)"),
+    end_date: Optional[str] = Query(None, description="Only include dates on or before (YYYY-MM-DD)"),
+    offset: int = Query(0, ge=0, description="Number of periods to skip after date filtering"),
+    limit: Optional[int] = Query(None, ge=1, le=5000, description="Maximum number of periods to return"),
+    rolling_sharpe_window: Optional[int] = Query(
+        None, description="Also return a trailing Sharpe ratio over this many periods (at least 2)"
+    ),
+    accept: Optional[str] = Header(None),
+):
+    """
+    Get the returns time series of the latest run for each factor, optionally paginated.
+
+    Returns JSON by default; CSV or Parquet (long format, one row per factor and
+    date) when requested via the Accept header.
+
+    With rolling_sharpe_window, each series also carries its trailing Sharpe ratio,
+    annualized at the run's own rebalance frequency. It's computed over the full run
+    before date filtering so the first returned periods still have a value when
+    enough history precedes them.
+    """
+    if rolling_sharpe_window is not None and rolling_sharpe_window < 2:
+        raise HTTPException(status_code=400, detail="rolling_sharpe_window must be at least 2")
+
+    requested = [Factor.parse(factor) for factor in factors.split(",")] if factors else list(Factor)
+
+    start = parse_query_date(start_date, "start_date")
+    end = parse_query_date(end_date, "end_date")
+
+    # (factor, run id, rows before pagination, page of date/return/cumulative_return[/rolling_sharpe] rows)
+    pages = []
+    for factor in requested:
+        latest = load_latest_time_series(factor)
+        if latest is None:
+            continue
+        run_id, series = latest
+        if rolling_sharpe_window is not None:
+            annualization = run_periods_per_year(factor, run_id)
+            series = series.assign(
+                rolling_sharpe=rolling_sharpe(series["return"], rolling_sharpe_window, periods_per_year=annualization)
+            )
+
+        if start is not None:
+            series = series[series["date"] >= start]
+        if end is not None:
+            series = series[series["date"] <= end]
+
+        # Pagination applies after date filtering so the two compose
+        page = series.iloc[offset : offset + limit] if limit else series.iloc[offset:]
+        pages.append((factor, run_id, len(series), page))
+
+    def factor_returns(factor: str, run_id: str, total: int, page: pd.DataFrame) -> FactorReturns:
+        return FactorReturns(
+            factor=factor,
+            run_id=run_id,
+            total=total,
+            dates=page["date"].dt.strftime("%Y-%m-%d").tolist(),
+            returns=page["return"].astype(float).tolist(),
+            cumulative_returns=page["cumulative_return"].astype(float).tolist(),
+            rolling_sharpe=page["rolling_sharpe"].tolist() if rolling_sharpe_window is not None else None,
+        )
+
+    def long_rows(factor: str, run_id: str, page: pd.DataFrame) -> pd.DataFrame:
+        return page.assign(factor=factor, run_id=run_id, date=page["date"].dt.strftime("%Y-%m-%d"))
+
+    stream = sum(len(page) for *_, page in pages) >= STREAM_MIN_ROWS
+
+    fmt = negotiate_format(accept)
+    if fmt != "json":
+        columns = ["factor", "run_id", "date", "return", "cumulative_return"]
+        if rolling_sharpe_window is not None:
+            columns.append("rolling_sharpe")
+        if fmt == "csv" and stream:
+            # Rows are formatted a chunk at a time as the response is sent, so the body is never built whole
+            def csv_frames():
+                for factor, run_id, _, page in pages:
+                    for chunk_start in range(0, len(page), STREAM_CHUNK_ROWS):
+                        yield long_rows(factor, run_id, page.iloc[chunk_start : chunk_start + STREAM_CHUNK_ROWS])
+
+            return stream_csv(csv_frames(), columns, "time_series")
+        rows = [long_rows(factor, run_id, page) for factor, run_id, _, page in pages]
+        df = pd.concat(rows, ignore_index=True)[columns] if rows else pd.DataFrame(columns=columns)
+        return tabular_response(df, fmt, "time_series")
+
+    if stream:
+        # Build and serialize one factor at a time so the full JSON document is never held in memory
+        def json_chunks():
+            yield '{"time_series":['
+            for i, (factor, run_id, total, page) in enumerate(pages):
+                yield ("," if i else "") + factor_returns(factor, run_id, total, page).model_dump_json(exclude_none=True)
+            yield "]}"
+
+        return StreamingResponse(json_chunks(), media_type="application/json")
+
+    # exclude_none leaves rolling_sharpe out unless it was requested
+    return {"time_series": [factor_returns(*page).model_dump(exclude_none=True) for page in pages]}
+
+
+@dataclass
+class Computation:
+    """A computation in flight: its task, and the progress its streams subscribe to"""
+
+    task: asyncio.Task
+    progress: "ComputeProgress"
+
+
+# In-flight computations keyed by (factor, config hash) so identical concurrent
+# requests, streamed or not, share one computation and receive the same result and run id
+_inflight_computations: Dict[str, Computation] = {}
+
+
+def start_single_flight(factor: str, params: dict, compute_fn, *args, stream: bool = False) -> Computation:
+    """
+    Start a blocking compute function off the event loop, or join the identical computation already running.
+
+    A caller that isn't a stream keeps the run alive (see ComputeProgress), so it's
+    never cancelled by streams sharing it disconnecting. A run already cancelled
+    that way isn't joined: the caller starts a new one.
+    """
+    params_hash = hashlib.sha256(json.dumps(params, sort_keys=True, default=str).encode()).hexdigest()
+    key = f"{factor}:{params_hash}"
+
+    computation = _inflight_computations.get(key)
+    if computation is None or computation.progress.stop_event.disconnected():
+        progress = ComputeProgress(asyncio.get_running_loop())
+
+        def run():
+            # to_thread runs this in a copy of the context, so the run reports to its own progress
+            _compute_progress.set(progress)
+            return compute_fn(*args)
+
+        computation = Computation(asyncio.create_task(asyncio.to_thread(run)), progress)
+        _inflight_computations[key] = computation
+
+        def finished(task: asyncio.Task, computation: Computation = computation):
+            # Retrieve the exception even when nobody awaits it, e.g. the ComputeCancelled streams stopped it with
+            task.cancelled() or task.exception()
+            if _inflight_computations.get(key) is computation:
+                del _inflight_computations[key]
+
+        computation.task.add_done_callback(finished)
+    if not stream:
+        computation.progress.keep_alive = True
+    return computation
+
+
+async def run_single_flight(factor: str, params: dict, compute_fn, *args) -> dict:
+    """Run a blocking compute function off the event loop, deduplicating identical concurrent requests"""
+    # Shield so one client disconnecting doesn't cancel the computation for the others
+    return await asyncio.shield(start_single_flight(factor, params, compute_fn, *args).task)
+
+
+def callback_address_allowed(address: str) -> bool:
+    """Whether callbacks may be sent to an IP address: not private, loopback, link-local or otherwise internal"""
+    ip = ipaddress.ip_address(address.split("%")[0])
+    if getattr(ip, "ipv4_mapped", None):
+        ip = ip.ipv4_mapped
+    return not (
+        ip.is_private or ip.is_loopback or ip.is_link_local or ip.is_multicast or ip.is_reserved or ip.is_unspecified
+    )
+
+
+def resolve_callback_address(url: str) -> tuple[Optional[str], Optional[str]]:
+    """
+    Resolve a callback URL's host to the address to connect to, as (address, refusal).
+
+    refusal says why the URL mustn't be called (its host doesn't resolve, or any of its
+    addresses is internal), and address is then None.
+    """
+    parts = urlsplit(url)
+    port = parts.port or (443 if parts.scheme == "https" else 80)
+    try:
+        addresses = sorted({info[4][0] for info in socket.getaddrinfo(parts.hostname, port, proto=socket.IPPROTO_TCP)})
+    except (socket.gaierror, UnicodeError) as e:
+        return None, f"{parts.hostname} doesn't resolve ({e})"
+    internal = [address for address in addresses if not callback_address_allowed(address)]
+    if internal:
+        return None, f"{parts.hostname} resolves to the internal address {internal[0]}"
+    return addresses[0], None
+
+
+class PinnedAddressAdapter(requests.adapters.HTTPAdapter):
+    """
+    Transport adapter connecting to one already-validated IP address, whatever the URL's host.
+
+    The request still carries the URL's host in its Host header, and HTTPS still sends it
+    as SNI and checks the certificate against it. Connecting without a second lookup means
+    a DNS rebinding answer (public when checked, internal when connected) can't redirect it.
+    """
+
+    def __init__(self, url: str, address: str, **kwargs):
+        self.parts = urlsplit(url)
+        self.address = address
+        super().__init__(**kwargs)
+
+    def init_poolmanager(self, *args, **pool_kwargs):
+        if self.parts.scheme == "https":
+            pool_kwargs.update(server_hostname=self.parts.hostname, assert_hostname=self.parts.hostname)
+        super().init_poolmanager(*args, **pool_kwargs)
+
+    def send(self, request: requests.PreparedRequest, **kwargs) -> requests.Response:
+        host = f"[{self.address}]" if ":" in self.address else self.address
+        port = f":{self.parts.port}" if self.parts.port else ""
+        request.headers["Host"] = f"{self.parts.hostname}{port}"
+        request.url = urlsplit(request.url)._replace(netloc=f"{host}{port}").geturl()
+        return super().send(request, **kwargs)
+
+
+def deliver_callback(url: str, payload: dict):
+    """POST a compute result to a webhook, retrying failures with exponential backoff"""
+    # Resolved here rather than at validation so a hostname pointing at an internal address is caught
+    address, refusal = resolve_callback_address(url)
+    if refusal:
+        log.error(f"Not calling back {url} for run {payload.get('run_id')}: {refusal}")
+        return
+
+    body = json.dumps(payload, default=str)
+    with requests.Session() as session:
+        # Connect to the address just checked, rather than letting requests resolve the host again
+        session.mount(f"{urlsplit(url).scheme}://", PinnedAddressAdapter(url, address))
+        for attempt in range(1, CALLBACK_MAX_ATTEMPTS + 1):
+            try:
+                response = session.post(
+                    url,
+                    data=body,
+                    headers={"Content-Type": "application/json"},
+                    timeout=CALLBACK_TIMEOUT_SECONDS,
+                    # A redirect could point anywhere, including the internal hosts refused above
+                    allow_redirects=False,
+                )
+                if response.ok:
+                    return
+                error = f"HTTP {response.status_code}"
+            except requests.RequestException as e:
+                error = str(e)
+
+            if attempt < CALLBACK_MAX_ATTEMPTS:
+                delay = CALLBACK_BACKOFF_SECONDS * 2 ** (attempt - 1)
+                log.warning(f"Callback to {url} failed ({error}), retrying in {delay:.0f}s")
+                time.sleep(delay)
+
+    log.error(f"Giving up on callback to {url} for run {payload.get('run_id')} after {CALLBACK_MAX_ATTEMPTS} attempts")
+
+
+def caller_result(result: dict, config: FactorConfig) -> dict:
+    """A result shared through single-flight as one caller sees it: carrying that caller's own callback_url"""
+    if "config" not in result:
+        return result
+    return {**result, "config": {**result["config"], "callback_url": config.callback_url}}
+
+
+def failure_payload(factor: str, config: FactorConfig, error: BaseException) -> dict:
+    """The callback body for a run that failed: its status code and detail, as the HTTP response would have"""
+    if isinstance(error, HTTPException):
+        status_code, detail = error.status_code, error.detail
+    else:
+        status_code, detail = 500, f"Compute failed: {str(error) or type(error).__name__}"
+    return {"factor": factor, "run_id": config.run_id, "status": "failed", "status_code": status_code, "detail": detail}
+
+
+def schedule_callback(task: asyncio.Future, config: FactorConfig, factor: str):
+    """
+    Deliver a run's outcome to the request's callback_url, if any, once the run finishes:
+    its result, or a "failed" payload when it raised.
+
+    Attached to the run rather than the response, so every caller sharing the run is
+    called back at its own URL, including callers that disconnected before it finished.
+    """
+    if not config.callback_url:
+        return
+    loop = asyncio.get_running_loop()
+
+    def deliver(task: asyncio.Future):
+        if task.cancelled():
+            payload = failure_payload(factor, config, asyncio.CancelledError("cancelled"))
+        elif task.exception() is not None:
+            payload = failure_payload(factor, config, task.exception())
+        else:
+            payload = caller_result(task.result(), config)
+        loop.run_in_executor(None, deliver_callback, config.callback_url, payload)
+
+    task.add_done_callback(deliver)
+
+
+class ComputeCancelled(Exception):
+    """Raised inside a streamed compute once every client sharing it has disconnected, abandoning the run"""
+
+
+class StreamStopEvent(threading.Event):
+    """Set when a run's last stream disconnects; also reads as set once COMPUTE_STOP is, so shutdowns stop it too"""
+
+    def is_set(self) -> bool:
+        return super().is_set() or COMPUTE_STOP.is_set()
+
+    def disconnected(self) -> bool:
+        return super().is_set()
+
+
+class ComputeProgress:
+    """
+    Progress events of one compute, published from its worker thread to every stream subscribed to it.
+
+    The run is cancelled once its last stream unsubscribes, unless a caller that
+    isn't a stream shares it (keep_alive), since that caller still needs the result.
+    """
+
+    def __init__(self, loop: asyncio.AbstractEventLoop):
+        self.loop = loop
+        self.subscribers: set[asyncio.Queue] = set()
+        self.keep_alive = False
+        self.stop_event = StreamStopEvent()
+
+    def subscribe(self) -> asyncio.Queue:
+        """A queue receiving the run's events from now on (call on the event loop)"""
+        queue = asyncio.Queue()
+        self.subscribers.add(queue)
+        return queue
+
+    def unsubscribe(self, queue: asyncio.Queue):
+        """Stop sending events to a stream, cancelling the run if nothing else needs it (call on the event loop)"""
+        self.subscribers.discard(queue)
+        if not self.subscribers and not self.keep_alive:
+            self.stop_event.set()
+
+    def report(self, phase: str, percent: Optional[float] = None):
+        """Publish a phase event (from any thread), raising ComputeCancelled once the run is cancelled"""
+        self.raise_if_cancelled()
+        event = {"phase": phase, "percent": None if percent is None else round(percent, 1)}
+        self.loop.call_soon_threadsafe(self._publish, event)
+
+    def _publish(self, event: dict):
+        for queue in self.subscribers:
+            queue.put_nowait(event)
+
+    def raise_if_cancelled(self):
+        if self.stop_event.disconnected():
+            raise ComputeCancelled()
+
+
+# The progress the current run reports to (None outside a single-flight computation)
+_compute_progress: contextvars.ContextVar[Optional[ComputeProgress]] = contextvars.ContextVar(
+    "compute_progress", default=None
+)
+
+
+def report_compute_progress(phase: str, percent: Optional[float] = None):
+    """Report a compute phase to the streams following the run, if any"""
+    progress = _compute_progress.get()
+    if progress is not None:
+        progress.report(phase, percent)
+
+
+def fetch_checkpoint(deadline: Optional[float]) -> Callable[..., None]:
+    """
+    A fetch progress callback that reports to the run's streams and stops the fetch
+    with a 504 once the run's deadline has passed, so timeout_seconds covers the
+    data fetch as well as portfolio formation.
+    """
+
+    def checkpoint(phase: str, percent: Optional[float] = None):
+        report_compute_progress(phase, percent)
+        if deadline is not None and time.monotonic() >= deadline:
+            raise HTTPException(
+                status_code=504,
+                detail=f"Compute timed out while {phase.replace('_', ' ')}, before any rebalance period - "
+                "raise timeout_seconds",
+            )
+
+    return checkpoint
+
+
+def compute_stop_event() -> threading.Event:
+    """The event that stops the current run's portfolio formation: its streams', or COMPUTE_STOP"""
+    progress = _compute_progress.get()
+    return progress.stop_event if progress is not None else COMPUTE_STOP
+
+
+# Fetched frames shared by the runs of one sweep, keyed by the fetch arguments (None outside a sweep)
+_sweep_fetches: contextvars.ContextVar[Optional[dict]] = contextvars.ContextVar("sweep_fetches", default=None)
+
+
+def reuse_sweep_fetch(key: tuple, data_quality: dict, fetch: Callable[[dict], pd.DataFrame]) -> pd.DataFrame:
+    """
+    Run fetch(data_quality), or inside a sweep reuse the frame an earlier run fetched with the same key.
+
+    The data_quality entries the fetch recorded are replayed onto later runs' dicts,
+    and each run gets its own copy of the frame since runs filter it in place.
+    """
+    fetches = _sweep_fetches.get()
+    if fetches is None:
+        return fetch(data_quality)
+    if key not in fetches:
+        fetch_quality = {}
+        fetches[key] = (fetch(fetch_quality), fetch_quality)
+    df, fetch_quality = fetches[key]
+    data_quality.update(copy.deepcopy(fetch_quality))
+    return df.copy()
+
+
+def fetch_factor_data(
+    config: FactorConfig,
+    artemis_metrics: list[str],
+    data_quality: dict,
+    required_metrics: Optional[list[str]] = None,
+    deadline: Optional[float] = None,
+) -> pd.DataFrame:
+    """Fetch merged Artemis + Coinbase data for a compute request, mapping failures to a 502 and timeouts to a 504"""
+    _load_utils()
+    checkpoint = fetch_checkpoint(deadline)
+    key = (
+        "crypto",
+        tuple(artemis_metrics),
+        tuple(required_metrics or ()),
+        config.start_date,
+        config.end_date,
+        config.price_join,
+        tuple(config.universe) if config.universe else None,
+    )
+    try:
+        df = reuse_sweep_fetch(
+            key,
+            data_quality,
+            lambda quality: _fetch_merged_crypto_data(
+                start_date=config.start_date,
+                end_date=config.end_date,
+                artemis_metrics=artemis_metrics,
+                api_key=API_KEY,
+                data_quality=quality,
+                required_metrics=required_metrics,
+                join=config.price_join,
+                universe=config.universe,
+                progress=checkpoint,
+            ),
+        )
+    except (ComputeCancelled, HTTPException):
+        raise
+    except _EmptyUniverseError as e:
+        raise HTTPException(status_code=400, detail=f"Empty asset universe: {str(e)}")
+    except Exception as e:
+        raise HTTPException(
+            status_code=502,
+            detail=f"Failed to fetch merged crypto data: {str(e)}",
+        )
+    checkpoint("computing")
+    return df
+
+
+def prepare_period_data(factor_model, config: FactorConfig, extra_aggs: Optional[dict] = None):
+    """
+    Resample to the rebalance frequency and add the return and t-1 columns every factor ranks on.
+
+    Rows are labelled by the period end: each day, the Sunday ending each week, or
+    the calendar month-end, per `config.rebalance_frequency` (weekly by default).
+    `extra_aggs` keeps additional metric columns through the resample, e.g. {"fees": "mean"}.
+    Market cap columns are only built when the data has them.
+    Inverse-variance runs also get each asset's return variance for leg weighting,
+    over the trailing config.covariance_lookback_days (full history when None).
+    """
+    rule, _ = REBALANCE_FREQUENCIES[config.rebalance_frequency]
+    base_aggs = {"price": "last", "mc": "last", "24h_volume": "sum"}
+    # Equity data has no market cap column
+    base_aggs = {col: agg for col, agg in base_aggs.items() if col in factor_model.df.columns}
+    factor_model.resample(rule, {**base_aggs, **(extra_aggs or {})})
+    if config.rebalance_frequency == "daily":
+        # Days the market was closed (weekends for equities) resample to empty rows; keep trading days only
+        factor_model.df = factor_model.df.dropna(subset=["price"])
+    factor_model.period_days = DAYS_PER_PERIOD[config.rebalance_frequency]
+    factor_model.calculate_price_pct_change(periods=1)
+    factor_model.get_t_minus_1_metrics([col for col in ("mc", "24h_volume") if col in base_aggs])
+    if factor_model.weighting_method == "inverse_variance":
+        lookback_periods = (
+            lookback_days_to_periods(config.covariance_lookback_days, config.rebalance_frequency)
+            if config.covariance_lookback_days
+            else None
+        )
+        factor_model.calculate_variance(lookback_periods=lookback_periods)
+
+
+def lookback_days_to_periods(lookback_days: int, rebalance_frequency: str = "weekly") -> int:
+    """Convert a lookback in days to whole rebalance periods of the given frequency (at least one)"""
+    return max(1, round(lookback_days / DAYS_PER_PERIOD[rebalance_frequency]))
+
+
+def frequency_periods_per_year(factor: str, rebalance_frequency: str) -> int:
+    """Periods per year of a factor's runs at a rebalance frequency: trading days for daily equity runs"""
+    if factor == Factor.EQUITY and rebalance_frequency == "daily":
+        return EQUITY_TRADING_DAYS_PER_YEAR
+    return REBALANCE_FREQUENCIES[rebalance_frequency][1]
+
+
+def periods_per_year(config: FactorConfig) -> int:
+    """Rebalance periods per year for annualizing a run's per-period returns"""
+    return frequency_periods_per_year(config.factor.lower(), config.rebalance_frequency)
+
+
+def run_rebalance_frequency(factor: str, run_id: str) -> str:
+    """The rebalance frequency a logged run was computed at, from its saved config (weekly for older runs)"""
+    try:
+        config, _ = load_run_config(factor, run_id)
+    except HTTPException:
+        return "weekly"
+    frequency = config.get("rebalance_frequency")
+    return frequency if frequency in REBALANCE_FREQUENCIES else "weekly"
+
+
+def run_periods_per_year(factor: str, run_id: str) -> int:
+    """Periods per year for annualizing a logged run's returns, matching periods_per_year at compute time"""
+    return frequency_periods_per_year(factor, run_rebalance_frequency(factor, run_id))
+
+
+def add_trailing_return_signal(factor_model, lookback_periods: int) -> str:
+    """
+    Add the plain momentum signal: each asset's trailing return over lookback_periods.
+
+    Assets without lookback_periods of price history get a NaN signal and are
+    left out of the ranking. Returns the t-1 signal column to rank on.
+    """
+    factor_model.calculate_price_pct_change(periods=lookback_periods)
+    factor_model.df["trailing_return"] = factor_model.df[f"price_pct_change_p{lookback_periods}"]
+    factor_model.get_t_minus_1_metrics(["trailing_return"])
+    return "trailing_return_t_minus_1"
+
+
+def add_momentum_signal(factor_model, lookback_periods: int) -> str:
+    """
+    Add the vol-adjusted momentum signal: raw_momentum * (|mean_return| / std).
+
+    Returns the t-1 signal column to rank on.
+    """
+    factor_model.calculate_price_pct_change(periods=lookback_periods)
+
+    # Calculate rolling volatility metrics
+    factor_model.df["rolling_mean"] = factor_model.df.groupby("asset")[
+        "price_pct_change_p1"
+    ].transform(lambda x: x.rolling(lookback_periods, min_periods=1).mean())
+
+    factor_model.df["rolling_std"] = factor_model.df.groupby("asset")[
+        "price_pct_change_p1"
+    ].transform(lambda x: x.rolling(lookback_periods, min_periods=1).std())
+
+    # Vol ratio (essentially rolling Sharpe). With zero or undefined volatility the
+    # ratio would be inf/NaN, so those signals are zero instead
+    rolling_std = factor_model.df["rolling_std"]
+    factor_model.df["vol_ratio"] = (
+        factor_model.df["rolling_mean"].abs() / rolling_std.where(rolling_std > 0)
+    ).fillna(0)
+
+    # Filtered momentum
+    factor_model.df["filtered_momentum"] = (
+        factor_model.df[f"price_pct_change_p{lookback_periods}"] * factor_model.df["vol_ratio"]
+    )
+
+    factor_model.get_t_minus_1_metrics(["filtered_momentum"])
+    return "filtered_momentum_t_minus_1"
+
+
+def add_value_signal(factor_model, config: FactorConfig, fees_col: str = "fees") -> str:
+    """
+    Add the value signal: market cap over annualized fees, from each period's summed `fees_col`.
+
+    The ratio is undefined without positive fees. Returns the t-1 signal column to rank on.
+    """
+    fees = factor_model.df[fees_col]
+    annualized_fees = fees.where(fees > 0) * periods_per_year(config)
+    factor_model.df["mc_fees_ratio"] = factor_model.df["mc"] / annualized_fees
+    factor_model.get_t_minus_1_metrics(["mc_fees_ratio"])
+    return "mc_fees_ratio_t_minus_1"
+
+
+def ensure_growth_weights(config: FactorConfig):
+    """Reject growth_weights without a non-zero weight, or that weight market cap"""
+    if not config.growth_weights or sum(abs(weight) for weight in config.growth_weights.values()) == 0:
+        raise HTTPException(status_code=400, detail="growth_weights must include at least one non-zero weight")
+    if "mc" in config.growth_weights:
+        raise HTTPException(status_code=400, detail="growth_weights cannot include 'mc'")
+
+
+def add_growth_rates(factor_model, config: FactorConfig, lookback_periods: int):
+    """
+    Add each growth_weights metric's growth over lookback_periods, lagged so each
+    rebalance only uses prior data, dividing the metrics by market cap first with normalize_by_mc.
+
+    Growth from a zero base is undefined. Call combine_growth_signal after filtering.
+    """
+    from utils import calculate_growth_metrics
+
+    metrics = list(config.growth_weights)
+    if config.normalize_by_mc:
+        factor_model.normalize_by_market_cap(metrics)
+    factor_model.df = calculate_growth_metrics(factor_model.df, lookback_periods, metrics)
+    growth_cols = [f"{metric}_pct_change" for metric in metrics]
+    factor_model.df[growth_cols] = factor_model.df[growth_cols].replace([np.inf, -np.inf], np.nan)
+    factor_model.get_t_minus_1_metrics(growth_cols)
+
+
+def combine_growth_signal(factor_model, config: FactorConfig, partial_metric_policy: str) -> str:
+    """
+    Blend the growth rates from add_growth_rates into the growth signal: each is winsorized
+    (with winsorize_pct) and z-scored per date, then weighted by growth_weights.
+
+    Call after filtering so each date is standardized over the tradeable universe.
+    Returns the signal column to rank on.
+    """
+    zscore_cols = {}
+    for metric in config.growth_weights:
+        growth_col = f"{metric}_pct_change_t_minus_1"
+        if config.winsorize_pct is not None:
+            factor_model.df[growth_col] = cross_sectional_winsorize(factor_model.df, growth_col, config.winsorize_pct)
+        zscore_cols[metric] = f"{metric}_growth_zscore"
+        factor_model.df[zscore_cols[metric]] = cross_sectional_zscore(factor_model.df, growth_col)
+
+    factor_model.df["growth_signal"] = combine_zscores(
+        factor_model.df[list(zscore_cols.values())],
+        {zscore_cols[metric]: weight for metric, weight in config.growth_weights.items()},
+        partial_metric_policy,
+    )
+    return "growth_signal"
+
+
+def combine_composite_signal(
+    factor_model, signal_cols: Dict[str, str], weights: Dict[str, float], partial_metric_policy: str
+) -> str:
+    """
+    Z-score each weighted base factor's signal column per date and blend them by weight
+    (see combine_zscores). Returns the signal column to rank on.
+    """
+    zscore_cols = {}
+    for factor in weights:
+        zscore_cols[factor] = f"{factor}_zscore"
+        factor_model.df[zscore_cols[factor]] = cross_sectional_zscore(factor_model.df, signal_cols[factor])
+
+    factor_model.df["composite_signal"] = combine_zscores(
+        factor_model.df[list(zscore_cols.values())],
+        {zscore_cols[factor]: weight for factor, weight in weights.items()},
+        partial_metric_policy,
+    )
+    return "composite_signal"
+
+
+def apply_universe_filters(
+    factor_model, config: FactorConfig, on_stage: Optional[Callable[[str], None]] = None
+):
+    """
+    Apply the market cap, liquidity and lifetime filters configured on the request.
+
+    `on_stage` is called with each stage name after it runs, so callers can
+    inspect the surviving universe (used by the universe trace).
+    """
+    if config.market_cap_threshold:
+        factor_model.market_cap_threshold(config.market_cap_threshold)
+    if on_stage:
+        on_stage("market_cap")
+    if config.liquidity_threshold:
+        factor_model.liquidity_threshold(config.liquidity_threshold)
+    if on_stage:
+        on_stage("liquidity")
+    if config.min_lifetime_days:
+        factor_model.minimum_lifetime(config.min_lifetime_days)
+    if on_stage:
+        on_stage("lifetime")
+
+
+@app.get("/factors/{factor}/rolling-ic")
+async def get_rolling_ic(
+    factor: str,
+    window: int = Query(12, ge=1, le=520, description="Number of rebalances in each trailing window"),
+    run_id: Optional[str] = Query(None, description="Run to read (default: latest run with a time series)"),
+):
+    """
+    Get the trailing-window average of a run's per-period information coefficients,
+    with the run's mean IC and IC information ratio, annualized at its rebalance_frequency
+    """
+    run_id, series = load_run_time_series(factor, run_id)
+    if "ic" not in series.columns:
+        raise HTTPException(status_code=404, detail=f"Run '{run_id}' has no per-period ICs saved")
+
+    # Windows are counted in rebalances, so leading incomplete windows are null
+    rolling_ic = rolling_mean(series["ic"], window)
+
+    return {
+        "factor": factor,
+        "run_id": run_id,
+        "window": window,
+        "mean_ic": float(series["ic"].mean()) if series["ic"].notna().any() else None,
+        "ic_ir": ic_information_ratio(series["ic"], periods_per_year=run_periods_per_year(factor, run_id)),
+        "dates": series["date"].dt.strftime("%Y-%m-%d").tolist(),
+        "ic": to_optional_floats(series["ic"]),
+        "rolling_ic": rolling_ic,
+    }
+
+
+@app.get("/factors/{factor}/chart")
+async def get_factor_chart(
+    factor: str,
+    window: int = Query(90, ge=14, le=3650, description="Trailing window in days for the rolling series"),
+    run_id: Optional[str] = Query(None, description="Run to read (default: latest run with a time series)"),
+):
+    """
+    Get a run's cumulative return, rolling Sharpe, rolling volatility and drawdown on one date axis.
+
+    The window is rounded to whole rebalances at the run's rebalance_frequency, and
+    the rolling series are annualized at it. Every series has one entry per date;
+    the rolling series are null until a full window is available.
+    """
+    run_id, series = load_run_time_series(factor, run_id)
+    periods = lookback_days_to_periods(window, run_rebalance_frequency(factor, run_id))
+    annualization = run_periods_per_year(factor, run_id)
+
+    return {
+        "factor": factor,
+        "run_id": run_id,
+        "window": window,
+        "window_periods": periods,
+        "dates": series["date"].dt.strftime("%Y-%m-%d").tolist(),
+        "cumulative_returns": to_optional_floats(series["cumulative_return"]),
+        "rolling_sharpe": rolling_sharpe(series["return"], periods, periods_per_year=annualization),
+        "rolling_volatility": rolling_volatility(series["return"], periods, periods_per_year=annualization),
+        "drawdown": drawdown_series(series["cumulative_return"]),
+    }
+
+
+@app.get("/factors/{factor}/hedged")
+async def get_hedged_returns(
+    factor: str,
+    window: int = Query(90, ge=14, le=3650, description="Trailing window in days for the rolling market beta"),
+    run_id: Optional[str] = Query(None, description="Run to read (default: latest run with a time series)"),
+    market_run_id: Optional[str] = Query(
+        None,
+        description="Market factor run to hedge with (default: latest market run with a time series "
+        "at the run's rebalance frequency)",
+    ),
+):
+    """
+    Get a run's market-hedged returns: each period's return minus beta times the market factor's return.
+
+    Beta is the rolling regression beta of the factor on the market factor over
+    the window before each period, so hedging only uses returns known at the time.
+    Periods before a full window are null. The market run must have the run's
+    rebalance_frequency, which sets the window's periods and the Sharpe annualization.
+    """
+    run_id, series = load_run_time_series(factor, run_id)
+    if Factor.parse(factor) == Factor.MARKET:
+        raise HTTPException(status_code=400, detail="The market factor can't be hedged against itself")
+    frequency = run_rebalance_frequency(factor, run_id)
+    if market_run_id is None:
+        latest = load_latest_time_series(Factor.MARKET, rebalance_frequency=frequency)
+        if latest is None:
+            raise HTTPException(status_code=404, detail=f"No {frequency} market run with a time series to hedge with")
+        market_run_id, market_series = latest
+    else:
+        market_run_id, market_series = load_run_time_series(Factor.MARKET, market_run_id)
+        market_frequency = run_rebalance_frequency(Factor.MARKET, market_run_id)
+        if market_frequency != frequency:
+            raise HTTPException(
+                status_code=400,
+                detail=f"Market run '{market_run_id}' is {market_frequency} but run '{run_id}' is {frequency}; "
+                "hedge with a market run at the same rebalance frequency",
+            )
+    periods = lookback_days_to_periods(window, frequency)
+    annualization = run_periods_per_year(factor, run_id)
+
+    # Only dates both runs have; a missing market week would leave the hedge undefined
+    aligned = pd.DataFrame(
+        {
+            "return": series.set_index("date")["return"],
+            "market_return": market_series.set_index("date")["return"],
+        }
+    ).dropna().sort_index()
+    if len(aligned) <= periods:
+        raise HTTPException(
+            status_code=400,
+            detail=f"Only {len(aligned)} dates align with market run '{market_run_id}', "
+            f"need more than the {periods}-period window",
+        )
+
+    beta = rolling_beta(aligned["return"], aligned["market_return"], periods).shift(1)
+    hedged = aligned["return"] - beta * aligned["market_return"]
+    hedged_periods = hedged.notna()
+
+    return {
+        "factor": factor,
+        "run_id": run_id,
+        "market_run_id": market_run_id,
+        "window": window,
+        "window_periods": periods,
+        "dates": aligned.index.strftime("%Y-%m-%d").tolist(),
+        "returns": to_optional_floats(aligned["return"]),
+        "market_returns": to_optional_floats(aligned["market_return"]),
+        "rolling_beta": to_optional_floats(beta),
+        "hedged_returns": to_optional_floats(hedged),
+        "num_hedged_periods": int(hedged_periods.sum()),
+        "sharpe_ratio": sharpe_ratio(aligned["return"][hedged_periods], periods_per_year=annualization),
+        "hedged_sharpe_ratio": sharpe_ratio(hedged[hedged_periods], periods_per_year=annualization),
+    }
+
+
+@app.get("/factors/{factor}/seasonality")
+async def get_factor_seasonality(
+    factor: str,
+    by: Literal["month", "day_of_week"] = Query("month", description="Calendar bucket to group returns by"),
+    min_observations: int = Query(3, ge=1, description="Buckets with fewer returns are flagged as unreliable"),
+    run_id: Optional[str] = Query(None, description="Run to read (default: latest run with a time series)"),
+):
+    """Average a run's returns by month of year or day of week to surface calendar effects"""
+    run_id, series = load_run_time_series(factor, run_id)
+
+    if by == "month":
+        keys, labels = series["date"].dt.month, series["date"].dt.month_name()
+    else:
+        keys, labels = series["date"].dt.dayofweek, series["date"].dt.day_name()
+
+    grouped = series.groupby(keys)["return"]
+    stats = pd.DataFrame({"mean": grouped.mean(), "std": grouped.std(), "count": grouped.count()})
+    names = labels.groupby(keys).first()
+
+    buckets = [
+        {
+            "bucket": names[key],
+            "mean_return": float(row["mean"]),
+            "std_return": None if pd.isna(row["std"]) else float(row["std"]),
+            "num_observations": int(row["count"]),
+            "sufficient_observations": bool(row["count"] >= min_observations),
+        }
+        for key, row in stats.sort_index().iterrows()
+    ]
+
+    return {
+        "factor": factor,
+        "run_id": run_id,
+        "by": by,
+        "min_observations": min_observations,
+        "buckets": buckets,
+    }
+
+
+@app.get("/factors/{factor}/tail-risk")
+async def get_factor_tail_risk(
+    factor: str,
+    confidence: float = Query(0.95, gt=0.5, lt=1, description="VaR confidence level"),
+    worst_n: int = Query(5, ge=1, le=100, description="Number of worst single-period returns to list"),
+    run_id: Optional[str] = Query(None, description="Run to read (default: latest run with a time series)"),
+):
+    """Report a run's worst returns, its deepest drawdown episode, and Gaussian vs Cornish-Fisher VaR"""
+    run_id, series = load_run_time_series(factor, run_id)
+    returns = series.set_index("date")["return"].sort_index().dropna()
+    if len(returns) < 4:
+        raise HTTPException(
+            status_code=400, detail=f"Run '{run_id}' has {len(returns)} returns; tail risk needs at least 4"
+        )
+
+    worst = returns.nsmallest(worst_n)
+    episode = worst_drawdown(compound_returns(returns))
+    if episode is not None:
+        # Periods from the peak (or the start) until recovery, or to date if still under water
+        in_episode = returns.index <= (episode["recovery"] or returns.index[-1])
+        if episode["peak"] is not None:
+            in_episode &= returns.index > episode["peak"]
+        episode = {
+            key: value.strftime("%Y-%m-%d") if isinstance(value, pd.Timestamp) else value
+            for key, value in episode.items()
+        }
+        episode["num_periods"] = int(in_episode.sum())
+
+    return {
+        "factor": factor,
+        "run_id": run_id,
+        "num_periods": len(returns),
+        "worst_returns": [
+            {"date": date.strftime("%Y-%m-%d"), "return": float(value)} for date, value in worst.items()
+        ],
+        "worst_drawdown": episode,
+        "skewness": skewness(returns),
+        "excess_kurtosis": excess_kurtosis(returns),
+        "confidence": confidence,
+        "var_gaussian": value_at_risk(returns, confidence, "gaussian"),
+        "var_cornish_fisher": value_at_risk(returns, confidence, "cornish_fisher"),
+    }
+
+
+@app.get("/factors/{factor}/capacity")
+async def get_factor_capacity(
+    factor: str,
+    run_id: Optional[str] = Query(None, description="Run to read (default: latest run with a time series)"),
+    participation_rate: float = Query(
+        0.1, gt=0, le=1, description="Largest trade allowed, as a fraction of an asset's daily dollar volume"
+    ),
+    top_n: int = Query(10, ge=1, le=100, description="Number of most constraining assets to list"),
+):
+    """
+    Estimate the AUM a run's portfolio can absorb before its trades exceed a share of daily volume.
+
+    Each leg is sized at the AUM. An asset's largest single-rebalance weight change
+    (entering a position counts as its full weight) times the AUM must stay below
+    participation_rate x its average daily dollar volume; capacity is the smallest
+    AUM that violates this for any asset.
+    """
+    factor = Factor.parse(factor)
+    if run_id is None:
+        run_id, _ = load_run_time_series(factor)
+
+    compositions = load_compositions(factor, run_id)
+    if compositions is None:
+        raise HTTPException(status_code=404, detail=f"No compositions found for run '{run_id}'")
+    compositions = sorted(compositions, key=lambda composition: composition["date"])
+
+    dollar_volumes: Dict[str, List[float]] = {}
+    max_trades: Dict[str, float] = {}
+    for leg in ("long_portfolio", "short_portfolio"):
+        previous: Dict[str, float] = {}
+        for composition in compositions:
+            current = {asset: holding["weighting"] for asset, holding in composition[leg].items()}
+            for asset in set(previous) | set(current):
+                trade = abs(current.get(asset, 0.0) - previous.get(asset, 0.0))
+                max_trades[asset] = max(max_trades.get(asset, 0.0), trade)
+            for asset, holding in composition[leg].items():
+                if "daily_dollar_volume" in holding:
+                    dollar_volumes.setdefault(asset, []).append(holding["daily_dollar_volume"])
+            previous = current
+
+    if not dollar_volumes:
+        raise HTTPException(
+            status_code=404,
+            detail=f"Run '{run_id}' has no dollar volumes saved; recompute it to estimate capacity",
+        )
+
+    constraints = []
+    for asset, volumes in dollar_volumes.items():
+        average_volume = float(np.mean(volumes))
+        if max_trades.get(asset, 0.0) <= 0 or average_volume <= 0:
+            continue
+        constraints.append(
+            {
+                "asset": asset,
+                "avg_daily_dollar_volume": average_volume,
+                "max_trade_weight": max_trades[asset],
+                "capacity": participation_rate * average_volume / max_trades[asset],
+            }
+        )
+    constraints.sort(key=lambda constraint: constraint["capacity"])
+
+    return {
+        "factor": factor,
+        "run_id": run_id,
+        "participation_rate": participation_rate,
+        "capacity": constraints[0]["capacity"] if constraints else None,
+        "binding_asset": constraints[0]["asset"] if constraints else None,
+        "constraints": constraints[:top_n],
+    }
+
+
+def fetch_asset_returns(
+    asset: str, start_date: pd.Timestamp, end_date: pd.Timestamp, rebalance_frequency: str = "weekly"
+) -> pd.Series:
+    """Close-to-close returns for an Artemis asset per rebalance period, labelled like the factor rebalance dates"""
+    from utils import CoinbaseData, InvalidDateRangeError, resolve_symbol
+
+    # Forgiving of tickers and product ids, e.g. "BTC" or "BTC-USD" for bitcoin
+    product_id = resolve_symbol(asset)
+    if product_id is None:
+        raise HTTPException(status_code=400, detail=f"Asset '{asset}' has no Coinbase price mapping")
+
+    # Start a period early so the first rebalance date has a prior close
+    rule, _ = REBALANCE_FREQUENCIES[rebalance_frequency]
+    period_days = math.ceil(DAYS_PER_PERIOD[rebalance_frequency])
+    try:
+        candles = CoinbaseData().get_candles(
+            product_id,
+            (start_date - pd.Timedelta(days=period_days)).strftime("%Y-%m-%d"),
+            (end_date + pd.Timedelta(days=1)).strftime("%Y-%m-%d"),
+        )
+    except InvalidDateRangeError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    except Exception as e:
+        raise HTTPException(status_code=502, detail=f"Error fetching prices for '{asset}': {str(e)}")
+    if candles.empty:
+        raise HTTPException(status_code=404, detail=f"No prices found for '{asset}' over the run period")
+
+    period_close = candles.set_index("date")["close"].resample(rule).last()
+    return simple_returns(period_close)
+
+
+def load_benchmark_returns(
+    name: str, start_date: pd.Timestamp, end_date: pd.Timestamp, rebalance_frequency: str = "weekly"
+) -> pd.Series:
+    """
+    Per-period returns of a benchmark at a rebalance frequency, indexed by date: a
+    factor's latest run at that frequency, or an asset's prices over its periods.
+
+    Raises a 404 when the name is neither a factor with a saved series at the frequency nor a mapped asset.
+    """
+    if name in {factor.value for factor in Factor}:
+        latest = load_latest_time_series(name, rebalance_frequency=rebalance_frequency)
+        if latest is None:
+            raise HTTPException(
+                status_code=404, detail=f"No {rebalance_frequency} run with a time series for benchmark factor '{name}'"
+            )
+        return latest[1].set_index("date")["return"]
+
+    try:
+        return fetch_asset_returns(name, start_date, end_date, rebalance_frequency)
+    except HTTPException as e:
+        if e.status_code == 400:
+            raise HTTPException(status_code=404, detail=f"Benchmark '{name}' not found")
+        raise
+
+
+@app.get("/factors/{factor}/rolling-correlation")
+async def get_rolling_correlation(
+    factor: str,
+    to: str = Query("market", description="Benchmark: a factor name or an Artemis asset (e.g. bitcoin)"),
+    window: int = Query(12, ge=2, le=520, description="Number of rebalances in each trailing window"),
+    run_id: Optional[str] = Query(None, description="Run to read (default: latest run with a time series)"),
+):
+    """
+    Trailing correlation of a run's returns with a benchmark's over each window,
+    the benchmark taken at the run's rebalance frequency so their periods line up
+    """
+    run_id, series = load_run_time_series(factor, run_id)
+    returns = series.set_index("date")["return"]
+
+    benchmark = await asyncio.to_thread(
+        load_benchmark_returns, to, returns.index.min(), returns.index.max(), run_rebalance_frequency(factor, run_id)
+    )
+    # Align on the factor's dates; periods the benchmark lacks stay null
+    benchmark = benchmark.reindex(returns.index)
+    correlation = rolling_correlation(returns, benchmark, window)
+
+    return {
+        "factor": factor,
+        "run_id": run_id,
+        "to": to,
+        "window": window,
+        "dates": returns.index.strftime("%Y-%m-%d").tolist(),
+        "rolling_correlation": to_optional_floats(correlation),
+    }
+
+
+@app.post("/factors/{factor}/correlate")
+async def correlate_with_series(factor: str, request: CorrelateRequest):
+    """Correlate a run's returns with an uploaded external series and regress the returns on it"""
+    run_id, series = load_run_time_series(factor, request.run_id)
+    returns = series.set_index("date")["return"].sort_index()
+
+    external = parse_series_points(request.series)
+
+    if request.alignment == "exact":
+        aligned_values = external.reindex(returns.index)
+    else:
+        # Each factor date takes the latest observation on or before it, up to a week old
+        aligned_values = pd.merge_asof(
+            returns.rename("return").reset_index(),
+            external.rename("value").rename_axis("date").reset_index(),
+            on="date",
+            direction="backward",
+            tolerance=pd.Timedelta(days=7),
+        ).set_index("date")["value"]
+
+    aligned = pd.DataFrame({"return": returns, "value": aligned_values}).dropna()
+    if len(aligned) < request.min_observations:
+        raise HTTPException(
+            status_code=400,
+            detail=f"Only {len(aligned)} factor dates align with the series, need at least {request.min_observations}",
+        )
+
+    regression = ols_regression(aligned["return"], aligned["value"])
+    correlation = aligned["return"].corr(aligned["value"])
+
+    return {
+        "factor": factor,
+        "run_id": run_id,
+        "alignment": request.alignment,
+        "correlation": None if pd.isna(correlation) else float(correlation),
+        "alpha": regression["alpha"],
+        "beta": regression["betas"][0],
+        "r_squared": regression["r_squared"],
+        "num_observations": regression["num_observations"],
+        "start_date": aligned.index.min().strftime("%Y-%m-%d"),
+        "end_date": aligned.index.max().strftime("%Y-%m-%d"),
+    }
+
+
+@app.post("/factors/exposures")
+async def get_factor_exposures(request: ExposuresRequest):
+    """
+    Regress a target return series on several factors at once: loadings, t-stats (Newey-West) and r².
+
+    Every factor's latest run at the target's rebalance frequency is used, so the
+    regression is over periods that line up rather than whatever dates overlap.
+    """
+    if request.target_factor is not None:
+        target_factor = Factor.parse(request.target_factor)
+        if request.rebalance_frequency is None:
+            target_run_id, target_series = load_run_time_series(target_factor, None)
+        else:
+            latest = load_latest_time_series(target_factor, rebalance_frequency=request.rebalance_frequency)
+            if latest is None:
+                raise HTTPException(
+                    status_code=404,
+                    detail=f"No {request.rebalance_frequency} run with a time series for factor: {target_factor}",
+                )
+            target_run_id, target_series = latest
+        frequency = run_rebalance_frequency(target_factor, target_run_id)
+        target = target_series.set_index("date")["return"].sort_index()
+    else:
+        target_factor = target_run_id = None
+        frequency = request.rebalance_frequency or "weekly"
+        target = parse_series_points(request.series)
+
+    if request.factors is not None:
+        factors = [Factor.parse(name) for name in dict.fromkeys(request.factors)]
+        if target_factor in factors:
+            raise HTTPException(status_code=400, detail=f"Cannot regress '{target_factor}' on itself")
+    else:
+        factors = [factor for factor in Factor if factor != target_factor]
+
+    # Latest run of each factor at the target's frequency; unrequested factors without one are skipped
+    factor_returns, run_ids, skipped = {}, {}, []
+    for factor in factors:
+        latest = load_latest_time_series(factor, rebalance_frequency=frequency)
+        if latest is None:
+            if request.factors is None:
+                skipped.append(str(factor))
+                continue
+            if load_latest_time_series(factor) is None:
+                raise HTTPException(status_code=404, detail=f"No time series found for factor: {factor}")
+            raise HTTPException(
+                status_code=400,
+                detail=f"Factor '{factor}' has no {frequency} run with a time series to regress a {frequency} "
+                "target on",
+            )
+        run_ids[str(factor)], series = latest
+        factor_returns[str(factor)] = series.set_index("date")["return"].sort_index()
+    if not factor_returns:
+        raise HTTPException(status_code=404, detail="No factor time series available to regress on")
+
+    aligned = pd.DataFrame({"target": target, **factor_returns}).dropna()
+    if len(aligned) < request.min_observations:
+        raise HTTPException(
+            status_code=400,
+            detail=f"Only {len(aligned)} dates are shared by the target and every factor, "
+            f"need at least {request.min_observations}",
+        )
+
+    names = list(factor_returns)
+    try:
+        regression = newey_west_regression(
+            aligned["target"], aligned[names], lags=request.newey_west_lags, names=names
+        )
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+    return {
+        "target": {"factor": target_factor, "run_id": target_run_id},
+        "rebalance_frequency": frequency,
+        "factor_run_ids": run_ids,
+        "skipped_factors": skipped,
+        **regression,
+        "start_date": aligned.index.min().strftime("%Y-%m-%d"),
+        "end_date": aligned.index.max().strftime("%Y-%m-%d"),
+    }
+
+
+@app.get("/factors/{factor}/beta")
+async def get_factor_beta(
+    factor: str,
+    to: str = Query(..., description="Artemis asset to regress the factor's returns on (e.g. bitcoin)"),
+    run_id: Optional[str] = Query(None, description="Run to read (default: latest run with a time series)"),
+    min_observations: int = Query(10, ge=3, description="Minimum overlapping periods required"),
+):
+    """
+    Regress a run's factor returns on a single asset's returns over the same
+    rebalance periods (at the run's rebalance frequency): alpha, beta, and r²
+    """
+    run_id, series = load_run_time_series(factor, run_id)
 
-    # Sort by annualized return
-    comparison = sorted(
-        comparison,
-        key=lambda x: x.get("annualized_return") or 0,
-        reverse=True,
+    asset_returns = await asyncio.to_thread(
+        fetch_asset_returns, to, series["date"].min(), series["date"].max(), run_rebalance_frequency(factor, run_id)
     )
+    aligned = pd.DataFrame(
+        {"factor_return": series.set_index("date")["return"], "asset_return": asset_returns}
+    ).dropna()
+
+    if len(aligned) < min_observations:
+        raise HTTPException(
+            status_code=400,
+            detail=f"Only {len(aligned)} overlapping periods with '{to}', need at least {min_observations}",
+        )
+
+    regression = ols_regression(aligned["factor_return"], aligned["asset_return"])
+
+    from utils import resolve_artemis_symbol
+
+    return {
+        "factor": factor,
+        "run_id": run_id,
+        "asset": resolve_artemis_symbol(to),
+        "alpha": regression["alpha"],
+        "beta": regression["betas"][0],
+        "r_squared": regression["r_squared"],
+        "num_observations": regression["num_observations"],
+        "start_date": aligned.index.min().strftime("%Y-%m-%d"),
+        "end_date": aligned.index.max().strftime("%Y-%m-%d"),
+    }
+
+
+@app.get("/factors/{factor}/runs/{run_id}/compositions")
+async def get_run_compositions(factor: str, run_id: str):
+    """Get the long/short holdings a run saved at each rebalance date, oldest first"""
+    factor = Factor.parse(factor)
+
+    compositions = load_compositions(factor, run_id)
+    if compositions is None:
+        raise HTTPException(status_code=404, detail=f"No compositions found for run '{run_id}'")
+    compositions = sorted(compositions, key=lambda composition: composition["date"])
+
+    return {
+        "factor": factor,
+        "run_id": run_id,
+        "num_rebalances": len(compositions),
+        "compositions": compositions,
+    }
+
+
+@app.get("/factors/{factor}/runs/{run_id}/composition/diff")
+async def diff_run_compositions(
+    factor: str,
+    run_id: str,
+    from_date: str = Query(..., alias="from", description="Earlier rebalance date (YYYY-MM-DD)"),
+    to_date: str = Query(..., alias="to", description="Later rebalance date (YYYY-MM-DD)"),
+):
+    """Diff a run's holdings between two rebalance dates: assets added, removed, and re-weighted"""
+    factor = Factor.parse(factor)
+
+    compositions = load_compositions(factor, run_id)
+    if compositions is None:
+        raise HTTPException(status_code=404, detail=f"No compositions found for run '{run_id}'")
+    by_date = {composition["date"]: composition for composition in compositions}
+
+    snapshots = []
+    for name, value in (("from", from_date), ("to", to_date)):
+        date = parse_query_date(value, name).strftime("%Y-%m-%d")
+        if date not in by_date:
+            raise HTTPException(
+                status_code=404,
+                detail=f"No rebalance on {date} for run '{run_id}'",
+            )
+        snapshots.append(by_date[date])
+    before, after = snapshots
+
+    diff = {}
+    for leg in ("long_portfolio", "short_portfolio"):
+        old_weights = {asset: holding["weighting"] for asset, holding in before[leg].items()}
+        new_weights = {asset: holding["weighting"] for asset, holding in after[leg].items()}
+        diff[leg] = {
+            "added": sorted(set(new_weights) - set(old_weights)),
+            "removed": sorted(set(old_weights) - set(new_weights)),
+            "weight_changes": {
+                asset: {
+                    "from": old_weights[asset],
+                    "to": new_weights[asset],
+                    "delta": new_weights[asset] - old_weights[asset],
+                }
+                for asset in sorted(set(old_weights) & set(new_weights))
+                if abs(new_weights[asset] - old_weights[asset]) > 1e-12
+            },
+        }
+
+    return {
+        "factor": factor,
+        "run_id": run_id,
+        "from": before["date"],
+        "to": after["date"],
+        **diff,
+    }
+
+
+@app.get("/factors/{factor}/runs/{run_id}/series", response_model=RunSeries, response_model_exclude_none=True)
+async def get_run_series(factor: str, run_id: str):
+    """Get the full returns time series saved for any run of a factor, as stored"""
+    factor = Factor.parse(factor)
+    run_id, series = load_run_time_series(factor, run_id)
+    return RunSeries(
+        factor=factor,
+        run_id=run_id,
+        total=len(series),
+        dates=series["date"].dt.strftime("%Y-%m-%d").tolist(),
+        returns=series["return"].astype(float).tolist(),
+        cumulative_returns=series["cumulative_return"].astype(float).tolist(),
+        ics=[None if pd.isna(ic) else float(ic) for ic in series["ic"]] if "ic" in series.columns else None,
+    )
+
+
+@app.post("/factors/{factor}/runs/{run_id}/time-series", status_code=201)
+async def backfill_time_series(factor: str, run_id: str, upload: TimeSeriesUpload):
+    """Save a returns series for a logged run that has none, rebuilding its cumulative returns"""
+    factor = Factor.parse(factor)
+    logs = load_factor_logs(factor)
+    if "run_id" not in logs.columns or run_id not in set(logs["run_id"].astype(str)):
+        raise HTTPException(status_code=404, detail=f"Run '{run_id}' not found for factor '{factor}'")
+
+    returns = parse_series_points(upload.returns)
+    if not np.isfinite(returns).all():
+        raise HTTPException(status_code=400, detail="Returns must be finite numbers")
+
+    _, FactorModel, Logger, _ = _load_utils()
+    factor_model = FactorModel(df=pd.DataFrame(), factor=factor, run_id=run_id)
+    try:
+        await asyncio.to_thread(Logger(FACTOR_LOGS_DIR, factor_model).save_time_series, returns.to_dict())
+    except FileExistsError:
+        raise HTTPException(status_code=409, detail=f"Run '{run_id}' already has a time series")
+
+    series = load_time_series(factor, run_id)
+    return {
+        "factor": factor,
+        "run_id": run_id,
+        "num_periods": len(series),
+        "start_date": series["date"].iloc[0].strftime("%Y-%m-%d"),
+        "end_date": series["date"].iloc[-1].strftime("%Y-%m-%d"),
+        "cumulative_return": float(series["cumulative_return"].iloc[-1]),
+    }
+
+
+@app.get("/factors/{factor}/runs/{run_id}/holding-periods")
+async def get_holding_periods(
+    factor: str,
+    run_id: str,
+    include_open: bool = Query(False, description="Count positions still held at the final rebalance"),
+):
+    """Histogram of how many consecutive rebalances each position is held before being dropped"""
+    factor = Factor.parse(factor)
+
+    compositions = load_compositions(factor, run_id)
+    if compositions is None:
+        raise HTTPException(status_code=404, detail=f"No compositions found for run '{run_id}'")
+    compositions = sorted(compositions, key=lambda composition: composition["date"])
+
+    legs = {}
+    for leg in ("long_portfolio", "short_portfolio"):
+        streaks: Dict[str, int] = {}
+        durations = []
+        for composition in compositions:
+            held = set(composition[leg])
+            # Positions missing from this rebalance were dropped after their streak
+            for asset in set(streaks) - held:
+                durations.append(streaks.pop(asset))
+            for asset in held:
+                streaks[asset] = streaks.get(asset, 0) + 1
+        if include_open:
+            durations.extend(streaks.values())
+
+        histogram = pd.Series(durations, dtype=int).value_counts().sort_index()
+        legs[leg] = {
+            "histogram": {str(duration): int(count) for duration, count in histogram.items()},
+            "num_positions": len(durations),
+            "open_positions": len(streaks),
+            "mean_holding_periods": float(np.mean(durations)) if durations else None,
+            "median_holding_periods": float(np.median(durations)) if durations else None,
+        }
+
+    return {
+        "factor": factor,
+        "run_id": run_id,
+        "num_rebalances": len(compositions),
+        "include_open": include_open,
+        **legs,
+    }
+
+
+@app.get("/factors/{factor}/runs/{run_id}/outliers")
+async def get_return_outliers(
+    factor: str,
+    run_id: str,
+    threshold: float = Query(4.0, gt=0, description="Flag returns whose |z-score| exceeds this"),
+    min_observations: int = Query(10, ge=3, description="Skip assets with fewer returns than this"),
+    limit: int = Query(100, ge=1, le=1000, description="Maximum number of outliers to return"),
+):
+    """
+    Flag per-asset returns that are extreme relative to that asset's own return history.
+
+    Each return is z-scored against the mean and standard deviation of the asset's
+    other returns over the run, so a 40% week counts as an outlier for a stablecoin
+    but may not for a small cap, and can't hide by inflating the spread itself.
+    Outliers are listed by |z-score|, largest first.
+    """
+    factor = Factor.parse(factor)
+
+    asset_returns = load_asset_returns(factor, run_id)
+    if asset_returns is None:
+        raise HTTPException(status_code=404, detail=f"No per-asset returns found for run '{run_id}'")
+
+    observations = asset_returns.groupby("asset")["return"].transform("count")
+    asset_returns = asset_returns[observations >= min_observations].copy()
+    asset_returns["zscore"] = asset_returns.groupby("asset")["return"].transform(leave_one_out_zscore)
+
+    flagged = asset_returns[asset_returns["zscore"].abs() > threshold]
+    top = flagged.loc[flagged["zscore"].abs().sort_values(ascending=False).index].head(limit)
+
+    return {
+        "factor": factor,
+        "run_id": run_id,
+        "threshold": threshold,
+        "num_assets": int(asset_returns["asset"].nunique()),
+        "num_returns": len(asset_returns),
+        "num_outliers": len(flagged),
+        "outliers": [
+            {"asset": asset, "date": date.strftime("%Y-%m-%d"), "return": float(value), "zscore": float(zscore)}
+            for asset, date, value, zscore in zip(top["asset"], top["date"], top["return"], top["zscore"])
+        ],
+    }
+
+
+@app.get("/compute/{factor}/universe-trace")
+async def trace_universe(
+    factor: str,
+    date: str = Query(..., description="Rebalance date to trace (YYYY-MM-DD, snapped to its week end)"),
+    start_date: Optional[str] = Query(None, description="Data start used for lifetime (default: one year before date)"),
+    market_cap_threshold: Optional[int] = Query(100_000_000, description="Minimum market cap filter"),
+    liquidity_threshold: Optional[int] = Query(35_000_000, description="Minimum 24h volume filter"),
+    min_lifetime_days: Optional[int] = Query(30, description="Minimum asset lifetime in days"),
+    lookback_days: Optional[int] = Query(
+        None, ge=7, le=730, description="Momentum trailing return window in days (default 90)"
+    ),
+    lookback_periods: Optional[int] = Query(
+        None,
+        ge=1,
+        le=12,
+        description="Lookback periods for the momentum_v2 and composite signals (default 3); "
+        "for momentum, the deprecated alias of lookback_days",
+    ),
+):
+    """
+    Count how many assets survive each universe filter stage on a single rebalance date.
+
+    Runs the same filters as the compute endpoints, in the same order, without
+    forming portfolios, to show where the universe shrinks.
+    """
+    factor = Factor.parse(factor)
+    if factor not in (Factor.SMB, Factor.MOMENTUM, Factor.MOMENTUM_V2, Factor.COMPOSITE):
+        raise HTTPException(status_code=400, detail=f"Universe trace is not supported for factor '{factor}'")
+
+    # Weekly rows are labelled by the Sunday ending each week
+    trace_date = parse_query_date(date, "date").to_period("W").end_time.normalize()
+    start = parse_query_date(start_date, "start_date") if start_date else trace_date - pd.DateOffset(years=1)
+    if start >= trace_date:
+        raise HTTPException(status_code=400, detail="start_date must be before date")
+
+    # The trace date's week can still be running, so fetch no further than today
+    today = pd.Timestamp.now(tz="UTC").tz_localize(None).normalize()
+    try:
+        config = FactorConfig(
+            factor=factor,
+            start_date=start.strftime("%Y-%m-%d"),
+            end_date=min(trace_date, today).strftime("%Y-%m-%d"),
+            market_cap_threshold=market_cap_threshold,
+            liquidity_threshold=liquidity_threshold,
+            min_lifetime_days=min_lifetime_days,
+            # Left unset when not given, so the deprecated lookback_periods alias can apply
+            **({"lookback_days": lookback_days} if lookback_days is not None else {}),
+        )
+    except ValidationError as e:
+        errors = "; ".join(err["msg"] for err in e.errors())
+        raise HTTPException(status_code=400, detail=f"Invalid trace range: {errors}")
+    if factor == Factor.MOMENTUM:
+        config = momentum_lookback_alias(config, lookback_periods)
+    stages = await asyncio.to_thread(run_universe_trace, config, trace_date, lookback_periods or 3)
+
+    return {
+        "factor": factor,
+        "date": trace_date.strftime("%Y-%m-%d"),
+        "start_date": config.start_date,
+        "stages": stages,
+    }
 
-    return {"comparison": comparison}
+
+def run_universe_trace(config: FactorConfig, trace_date: pd.Timestamp, lookback_periods: int) -> List[dict]:
+    """Run the compute pipeline's universe filters, recording the assets left on trace_date after each stage"""
+    _, FactorModel, _, _ = _load_utils()
+    factor = Factor(config.factor)
+
+    df = fetch_factor_data(config, FACTOR_METRICS[factor], {})
+    factor_model = FactorModel(df=df.reset_index(), factor=factor)
+    prepare_period_data(factor_model, config)
+
+    signal_cols = []
+    if factor in (Factor.SMB, Factor.COMPOSITE):
+        signal_cols.append("mc_t_minus_1")
+    if factor is Factor.MOMENTUM:
+        signal_cols.append(add_trailing_return_signal(factor_model, lookback_days_to_periods(config.lookback_days)))
+    if factor in (Factor.MOMENTUM_V2, Factor.COMPOSITE):
+        signal_cols.append(add_momentum_signal(factor_model, lookback_periods))
+
+    stages = []
+
+    def record(stage: str):
+        on_date = factor_model.df[factor_model.df["date"] == trace_date]
+        stages.append({"stage": stage, "num_assets": int(on_date["asset"].nunique())})
+
+    record("initial")
+    apply_universe_filters(factor_model, config, on_stage=record)
+
+    # Same availability rule as portfolio formation: a finite signal and a return on the date
+    valid_signals = np.isfinite(factor_model.df[signal_cols].astype(float)).all(axis=1)
+    factor_model.df = factor_model.df[valid_signals].dropna(subset=["price_pct_change_p1"])
+    record("data_availability")
+
+    return stages
 
 
 @app.post("/compute/smb")
 async def compute_smb_factor(config: FactorConfig):
     """
-    Compute SMB (Small Minus Big) factor model.
+    Compute SMB (Small Minus Big) factor model.
+
+    This endpoint fetches data from Artemis API and computes the size factor.
+    """
+    return await dispatch_compute(Factor.SMB, config)
+
+
+def run_smb_factor(config: FactorConfig) -> dict:
+    """Compute and log an SMB run (blocking)"""
+    # Validate factor name matches endpoint
+    ensure_endpoint_factor(config, Factor.SMB)
+
+    ensure_run_id_available(Factor.SMB, config.run_id)
+    deadline = compute_deadline(config)
+
+    ApiData, FactorModel, Logger, cumulative_returns = _load_utils()
+
+    data_quality = {}
+    df = fetch_factor_data(config, FACTOR_METRICS[Factor.SMB], data_quality, deadline=deadline)
+
+    # Initialize factor model
+    factor_model = FactorModel(
+        df=df.reset_index(),
+        factor=Factor.SMB,
+        breakpoint=config.breakpoint,
+        min_assets=config.min_assets,
+        weighting_method=config.weighting_method,
+        run_id=config.run_id,
+        empty_leg_policy=config.empty_leg_policy,
+        leg_overlap_policy=config.leg_overlap_policy,
+        portfolio_mode=config.portfolio_mode,
+        deadline=deadline,
+        stop_event=compute_stop_event(),
+        transaction_cost_bps=config.transaction_cost_bps,
+    )
+
+    prepare_period_data(factor_model, config)
+    apply_universe_filters(factor_model, config)
+    if config.min_valid_periods:
+        factor_model.minimum_valid_periods("mc_t_minus_1", config.min_valid_periods)
+
+    # Long small, short big by default; min_assets counts assets without a prior market cap, as it always has
+    form_long_short_portfolios(
+        factor_model, "mc_t_minus_1", long_high=signal_long_high(config, Factor.SMB), count_unrankable=True
+    )
+
+    return finalize_factor_run(Factor.SMB, config, factor_model, data_quality)
+
+
+@app.post("/compute/market")
+async def compute_market_factor(config: FactorConfig):
+    """
+    Compute Market factor model.
+
+    Holds the `top_n` largest assets by market cap each period in a long-only,
+    market-cap-weighted portfolio.
+    """
+    return await dispatch_compute(Factor.MARKET, config)
+
+
+def run_market_factor(config: FactorConfig) -> dict:
+    """Compute and log a market run (blocking)"""
+    # Validate factor name matches endpoint
+    ensure_endpoint_factor(config, Factor.MARKET)
+    if config.portfolio_mode == "short_only":
+        raise HTTPException(status_code=400, detail="The market factor is long-only and can't run in short_only mode")
+    if config.weighting_method != "market_cap":
+        raise HTTPException(
+            status_code=400,
+            detail=f"The market factor is always market-cap weighted; weighting_method '{config.weighting_method}' "
+            "isn't supported",
+        )
+
+    ensure_run_id_available(Factor.MARKET, config.run_id)
+    deadline = compute_deadline(config)
+
+    ApiData, FactorModel, Logger, cumulative_returns = _load_utils()
+
+    data_quality = {}
+    df = fetch_factor_data(config, FACTOR_METRICS[Factor.MARKET], data_quality, deadline=deadline)
+
+    # Initialize factor model
+    factor_model = FactorModel(
+        df=df.reset_index(),
+        factor=Factor.MARKET,
+        breakpoint=config.breakpoint,
+        min_assets=config.min_assets,
+        weighting_method=config.weighting_method,
+        run_id=config.run_id,
+        deadline=deadline,
+        stop_event=compute_stop_event(),
+        transaction_cost_bps=config.transaction_cost_bps,
+    )
+
+    prepare_period_data(factor_model, config)
+    apply_universe_filters(factor_model, config)
+    if config.min_valid_periods:
+        factor_model.minimum_valid_periods("mc_t_minus_1", config.min_valid_periods)
+
+    # Long the largest assets, weighted by their market cap going into the period
+    factor_model.form_long_only_portfolio("mc_t_minus_1", config.top_n, weight_col="mc_t_minus_1")
+
+    return finalize_factor_run(Factor.MARKET, config, factor_model, data_quality, log_extra={"top_n": config.top_n})
+
+
+@app.post("/compute/growth")
+async def compute_growth_factor(
+    config: FactorConfig,
+    lookback_periods: int = Query(2, ge=1, le=52, description="Periods over which each metric's growth is measured"),
+):
+    """
+    Compute Growth factor model.
+
+    Blends the cross-sectional z-scores of each fundamental metric's growth rate
+    (fees, DAU and revenue by default) using `growth_weights`, long high growth.
+    """
+    return await dispatch_compute(Factor.GROWTH, config, lookback_periods=lookback_periods)
+
+
+def run_growth_factor(config: FactorConfig, lookback_periods: int) -> dict:
+    """Compute and log a growth run (blocking)"""
+    # Validate factor name matches endpoint
+    ensure_endpoint_factor(config, Factor.GROWTH)
+
+    metrics = list(config.growth_weights)
+    ensure_growth_weights(config)
+
+    ensure_run_id_available(Factor.GROWTH, config.run_id)
+    deadline = compute_deadline(config)
+
+    ApiData, FactorModel, Logger, cumulative_returns = _load_utils()
+
+    # Only market cap is required: assets missing some growth metrics are kept
+    # and handled by the partial metric policy
+    data_quality = {}
+    df = fetch_factor_data(config, ["mc", *metrics], data_quality, required_metrics=["mc"], deadline=deadline)
+
+    # Initialize factor model
+    factor_model = FactorModel(
+        df=df.reset_index(),
+        factor=Factor.GROWTH,
+        breakpoint=config.breakpoint,
+        min_assets=config.min_assets,
+        weighting_method=config.weighting_method,
+        run_id=config.run_id,
+        empty_leg_policy=config.empty_leg_policy,
+        leg_overlap_policy=config.leg_overlap_policy,
+        portfolio_mode=config.portfolio_mode,
+        deadline=deadline,
+        stop_event=compute_stop_event(),
+        transaction_cost_bps=config.transaction_cost_bps,
+    )
+
+    # Average the daily metrics over each week; a week with no values stays NaN
+    prepare_period_data(factor_model, config, {metric: "mean" for metric in metrics})
+    add_growth_rates(factor_model, config, lookback_periods)
+
+    apply_universe_filters(factor_model, config)
+    signal_col = combine_growth_signal(factor_model, config, config.partial_metric_policy or "average_available")
+
+    if config.min_valid_periods:
+        factor_model.minimum_valid_periods(signal_col, config.min_valid_periods)
+
+    # Long high growth, short low growth by default
+    form_long_short_portfolios(factor_model, signal_col, long_high=signal_long_high(config, Factor.GROWTH))
+
+    return finalize_factor_run(
+        Factor.GROWTH,
+        config,
+        factor_model,
+        data_quality,
+        config_extra={"lookback_periods": lookback_periods},
+        log_extra={"growth_metrics": "|".join(metrics), "growth_lookback_periods": lookback_periods},
+    )
+
+
+def run_value_factor(config: FactorConfig) -> dict:
+    """Compute and log a value run (blocking)"""
+    # Validate factor name matches endpoint
+    ensure_endpoint_factor(config, Factor.VALUE)
+
+    ensure_run_id_available(Factor.VALUE, config.run_id)
+    deadline = compute_deadline(config)
+
+    ApiData, FactorModel, Logger, cumulative_returns = _load_utils()
+
+    data_quality = {}
+    df = fetch_factor_data(config, FACTOR_METRICS[Factor.VALUE], data_quality, deadline=deadline)
+
+    # Initialize factor model
+    factor_model = FactorModel(
+        df=df.reset_index(),
+        factor=Factor.VALUE,
+        breakpoint=config.breakpoint,
+        min_assets=config.min_assets,
+        weighting_method=config.weighting_method,
+        run_id=config.run_id,
+        empty_leg_policy=config.empty_leg_policy,
+        leg_overlap_policy=config.leg_overlap_policy,
+        portfolio_mode=config.portfolio_mode,
+        deadline=deadline,
+        stop_event=compute_stop_event(),
+        transaction_cost_bps=config.transaction_cost_bps,
+    )
+
+    # Sum each week's fees
+    prepare_period_data(factor_model, config, {"fees": "sum"})
+    signal_col = add_value_signal(factor_model, config)
+
+    apply_universe_filters(factor_model, config)
+    # Winsorize after filtering so the percentiles are over the tradeable universe
+    if config.winsorize_pct is not None:
+        factor_model.df[signal_col] = cross_sectional_winsorize(factor_model.df, signal_col, config.winsorize_pct)
+    if config.min_valid_periods:
+        factor_model.minimum_valid_periods(signal_col, config.min_valid_periods)
+
+    # Long cheap (low MC/fees), short expensive by default
+    long_high = signal_long_high(config, Factor.VALUE)
+    form_long_short_portfolios(factor_model, signal_col, long_high=long_high)
+
+    return finalize_factor_run(
+        Factor.VALUE, config, factor_model, data_quality, log_extra={"signal_direction": "high" if long_high else "low"}
+    )
+
+
+@app.post("/compute/momentum")
+async def compute_momentum_factor(
+    config: FactorConfig,
+    lookback_periods: Optional[int] = Query(
+        None, ge=1, le=12, deprecated=True, description="Deprecated: the lookback in rebalance periods; use lookback_days"
+    ),
+):
+    """
+    Compute Momentum factor model.
+
+    Ranks assets on their trailing return over `lookback_days`, long the
+    winners and short the losers.
+    """
+    return await dispatch_compute(Factor.MOMENTUM, momentum_lookback_alias(config, lookback_periods))
+
+
+def momentum_lookback_alias(config: FactorConfig, lookback_periods: Optional[int]) -> FactorConfig:
+    """
+    Apply momentum's deprecated `lookback_periods` query parameter as the equivalent lookback_days.
+
+    The lookback used to be a count of rebalance periods; it's now config.lookback_days.
+    Giving both is a 400 unless they agree.
+    """
+    if lookback_periods is None:
+        return config
+    lookback_days = round(lookback_periods * DAYS_PER_PERIOD[config.rebalance_frequency])
+    if "lookback_days" in config.model_fields_set and config.lookback_days != lookback_days:
+        raise HTTPException(
+            status_code=400, detail="Pass either lookback_days or the deprecated lookback_periods, not both"
+        )
+    log.warning(f"Momentum lookback_periods={lookback_periods} is deprecated; using lookback_days={lookback_days}")
+    return config.model_copy(update={"lookback_days": lookback_days})
+
+
+def run_momentum_factor(config: FactorConfig) -> dict:
+    """Compute and log a momentum run (blocking)"""
+    # Validate factor name matches endpoint
+    ensure_endpoint_factor(config, Factor.MOMENTUM)
+
+    ensure_run_id_available(Factor.MOMENTUM, config.run_id)
+    deadline = compute_deadline(config)
+
+    ApiData, FactorModel, Logger, cumulative_returns = _load_utils()
+
+    data_quality = {}
+    df = fetch_factor_data(config, FACTOR_METRICS[Factor.MOMENTUM], data_quality, deadline=deadline)
+
+    # Initialize factor model
+    factor_model = FactorModel(
+        df=df.reset_index(),
+        factor=Factor.MOMENTUM,
+        breakpoint=config.breakpoint,
+        min_assets=config.min_assets,
+        weighting_method=config.weighting_method,
+        run_id=config.run_id,
+        empty_leg_policy=config.empty_leg_policy,
+        leg_overlap_policy=config.leg_overlap_policy,
+        portfolio_mode=config.portfolio_mode,
+        deadline=deadline,
+        stop_event=compute_stop_event(),
+        transaction_cost_bps=config.transaction_cost_bps,
+    )
+
+    prepare_period_data(factor_model, config)
+    lookback_periods = lookback_days_to_periods(config.lookback_days, config.rebalance_frequency)
+    signal_col = add_trailing_return_signal(factor_model, lookback_periods)
+    apply_universe_filters(factor_model, config)
+    if config.min_valid_periods:
+        factor_model.minimum_valid_periods(signal_col, config.min_valid_periods)
+
+    # Long high momentum, short low momentum by default
+    form_long_short_portfolios(factor_model, signal_col, long_high=signal_long_high(config, Factor.MOMENTUM))
+
+    return finalize_factor_run(
+        Factor.MOMENTUM,
+        config,
+        factor_model,
+        data_quality,
+        log_extra={"trailing_momentum_lookback_periods": lookback_periods, "lookback_days": config.lookback_days},
+    )
+
+
+@app.post("/compute/momentum_v2")
+async def compute_momentum_v2_factor(
+    config: FactorConfig,
+    lookback_periods: int = Query(3, ge=1, le=12, description="Lookback periods for momentum calculation"),
+):
+    """
+    Compute Momentum V2 factor model with volatility adjustment.
 
-    This endpoint fetches data from Artemis API and computes the size factor.
+    Uses vol-adjusted momentum: raw_momentum * (|mean_return| / std)
     """
+    return await dispatch_compute(Factor.MOMENTUM_V2, config, lookback_periods=lookback_periods)
+
+
+def run_momentum_v2_factor(config: FactorConfig, lookback_periods: int) -> dict:
+    """Compute and log a vol-adjusted momentum run (blocking)"""
     # Validate factor name matches endpoint
-    if config.factor not in ("smb", "SMB"):
-        raise HTTPException(
-            status_code=400,
-            detail=f"Factor name '{config.factor}' does not match endpoint. Expected 'smb'.",
-        )
+    ensure_endpoint_factor(config, Factor.MOMENTUM_V2)
+
+    ensure_run_id_available(Factor.MOMENTUM_V2, config.run_id)
+    deadline = compute_deadline(config)
 
     ApiData, FactorModel, Logger, cumulative_returns = _load_utils()
 
-    try:
-        df = _fetch_merged_crypto_data(
-            start_date=config.start_date,
-            end_date=config.end_date,
-            artemis_metrics=["mc"],
-            api_key=API_KEY,
-        )
-    except Exception as e:
-        raise HTTPException(
-            status_code=502,
-            detail=f"Failed to fetch merged crypto data: {str(e)}",
-        )
+    data_quality = {}
+    df = fetch_factor_data(config, FACTOR_METRICS[Factor.MOMENTUM_V2], data_quality, deadline=deadline)
 
     # Initialize factor model
     factor_model = FactorModel(
         df=df.reset_index(),
-        factor="smb",
+        factor=Factor.MOMENTUM_V2,
         breakpoint=config.breakpoint,
         min_assets=config.min_assets,
         weighting_method=config.weighting_method,
+        run_id=config.run_id,
+        empty_leg_policy=config.empty_leg_policy,
+        leg_overlap_policy=config.leg_overlap_policy,
+        portfolio_mode=config.portfolio_mode,
+        deadline=deadline,
+        stop_event=compute_stop_event(),
+        transaction_cost_bps=config.transaction_cost_bps,
     )
 
-    # Resample to weekly
-    factor_model.resample("W", {"price": "last", "mc": "last", "24h_volume": "sum"})
-
-    # Calculate metrics
-    factor_model.calculate_price_pct_change(periods=1)
-    factor_model.get_t_minus_1_metrics(["mc", "24h_volume"])
+    prepare_period_data(factor_model, config)
+    signal_col = add_momentum_signal(factor_model, lookback_periods)
+    apply_universe_filters(factor_model, config)
+    if config.min_valid_periods:
+        factor_model.minimum_valid_periods(signal_col, config.min_valid_periods)
 
-    # Apply filters
-    if config.market_cap_threshold:
-        factor_model.market_cap_threshold(config.market_cap_threshold)
-    if config.liquidity_threshold:
-        factor_model.liquidity_threshold(config.liquidity_threshold)
-    if config.min_lifetime_days:
-        factor_model.minimum_lifetime(config.min_lifetime_days)
+    # Long high filtered momentum, short low filtered momentum by default
+    form_long_short_portfolios(factor_model, signal_col, long_high=signal_long_high(config, Factor.MOMENTUM_V2))
 
-    # Compute factor returns (simplified - actual logic is more complex in notebooks)
-    dates = factor_model.df["date"].unique()
-    for date in dates:
-        period_data = factor_model.df[factor_model.df["date"] == date].copy()
-        if len(period_data) < config.min_assets:
-            continue
+    return finalize_factor_run(
+        Factor.MOMENTUM_V2,
+        config,
+        factor_model,
+        data_quality,
+        config_extra={"lookback_periods": lookback_periods},
+        log_extra={"trailing_momentum_lookback_periods": lookback_periods},
+    )
 
-        # Sort by market cap
-        period_data = period_data.sort_values("mc_t_minus_1")
-        n = len(period_data)
-        cutoff = int(n * config.breakpoint)
 
-        # Long small, short big
-        long_portfolio = period_data.head(cutoff)
-        short_portfolio = period_data.tail(cutoff)
+@app.post("/compute/equity")
+async def compute_equity_factor(config: FactorConfig):
+    """
+    Compute Equity momentum factor model.
 
-        if len(long_portfolio) == 0 or len(short_portfolio) == 0:
-            continue
+    Ranks the equity universe (`tickers`, default EQUITY_TICKERS) on trailing
+    return over `lookback_days` using Yahoo Finance prices, long the winners and
+    short the losers.
+    """
+    return await dispatch_compute(Factor.EQUITY, config)
 
-        long_return = long_portfolio["price_pct_change_p1"].mean()
-        short_return = short_portfolio["price_pct_change_p1"].mean()
 
-        factor_model.factor_returns[date] = long_return - short_return
-        factor_model.long_portfolio_returns[date] = long_return
-        factor_model.short_portfolio_returns[date] = short_return
+def run_equity_factor(config: FactorConfig) -> dict:
+    """Compute and log an equity momentum run (blocking)"""
+    from utils import EmptyUniverseError, fetch_equity_data
 
-    if not factor_model.factor_returns:
-        raise HTTPException(status_code=400, detail="No factor returns computed - check data availability")
+    # Validate factor name matches endpoint
+    ensure_endpoint_factor(config, Factor.EQUITY)
+    if config.weighting_method == "market_cap":
+        raise HTTPException(status_code=400, detail="market_cap weighting isn't available for equities (no market caps)")
 
-    # Calculate performance metrics
-    returns_df = cumulative_returns(factor_model.factor_returns)
-    total_cumulative = returns_df["cumulative_returns"].iloc[-1] if not returns_df.empty else 0
+    ensure_run_id_available(Factor.EQUITY, config.run_id)
+    deadline = compute_deadline(config)
 
-    dates_list = list(factor_model.factor_returns.keys())
-    if len(dates_list) >= 2:
-        days = (pd.to_datetime(dates_list[-1]) - pd.to_datetime(dates_list[0])).days
-        if days > 0:
-            years = days / 365
-            annualized = ((total_cumulative + 1) ** (1 / years)) - 1
-        else:
-            years = 0
-            annualized = 0
-    else:
-        years = 0
-        annualized = 0
+    ApiData, FactorModel, Logger, cumulative_returns = _load_utils()
 
-    # Calculate Sharpe ratio (simplified)
-    returns_series = pd.Series(list(factor_model.factor_returns.values()))
-    sharpe = (returns_series.mean() / returns_series.std()) * np.sqrt(52) if returns_series.std() > 0 else 0
+    data_quality = {}
+    checkpoint = fetch_checkpoint(deadline)
+    try:
+        df = reuse_sweep_fetch(
+            ("equity", tuple(config.tickers or ()), config.start_date, config.end_date),
+            data_quality,
+            lambda quality: fetch_equity_data(
+                config.start_date, config.end_date, config.tickers, quality, progress=checkpoint
+            ),
+        )
+    except (ComputeCancelled, HTTPException):
+        raise
+    except EmptyUniverseError as e:
+        raise HTTPException(status_code=400, detail=f"Empty asset universe: {str(e)}")
+    except Exception as e:
+        raise HTTPException(status_code=502, detail=f"Failed to fetch equity data: {str(e)}")
+    checkpoint("computing")
 
-    # Calculate Sortino ratio (downside deviation)
-    downside_returns = returns_series[returns_series < 0]
-    if len(downside_returns) > 0:
-        downside_std = downside_returns.std()
-        sortino = (returns_series.mean() / downside_std) * np.sqrt(52) if downside_std > 0 else None
-    else:
-        sortino = None
+    # Initialize factor model
+    factor_model = FactorModel(
+        df=df.reset_index(),
+        factor=Factor.EQUITY,
+        breakpoint=config.breakpoint,
+        min_assets=config.min_assets,
+        weighting_method=config.weighting_method,
+        run_id=config.run_id,
+        empty_leg_policy=config.empty_leg_policy,
+        leg_overlap_policy=config.leg_overlap_policy,
+        portfolio_mode=config.portfolio_mode,
+        deadline=deadline,
+        stop_event=compute_stop_event(),
+        transaction_cost_bps=config.transaction_cost_bps,
+    )
 
-    # Calculate long-only and short-only cumulative returns
-    long_returns_df = cumulative_returns(factor_model.long_portfolio_returns)
-    short_returns_df = cumulative_returns(factor_model.short_portfolio_returns)
-    long_only_cumulative = long_returns_df["cumulative_returns"].iloc[-1] if not long_returns_df.empty else 0
-    short_only_cumulative = short_returns_df["cumulative_returns"].iloc[-1] if not short_returns_df.empty else 0
+    prepare_period_data(factor_model, config)
+    lookback_periods = lookback_days_to_periods(config.lookback_days, config.rebalance_frequency)
+    signal_col = add_trailing_return_signal(factor_model, lookback_periods)
 
-    result = {
-        "factor": "smb",
-        "config": config.model_dump(),
-        "performance": {
-            "cumulative_returns": float(total_cumulative),
-            "annualized_return": float(annualized),
-            "sharpe_ratio": float(sharpe),
-            "sortino_ratio": float(sortino) if sortino is not None else None,
-            "years": float(years),
-            "num_periods": len(factor_model.factor_returns),
-            "long_only_returns": float(long_only_cumulative),
-            "short_only_returns": float(short_only_cumulative),
-        },
-        "returns": {
-            str(k): float(v) for k, v in list(factor_model.factor_returns.items())[-10:]
-        },
-    }
+    # Yahoo has no market caps, and the liquidity default is sized for crypto
+    # volumes, so only an explicitly requested liquidity threshold applies
+    universe_config = config.model_copy(
+        update={
+            "market_cap_threshold": None,
+            "liquidity_threshold": config.liquidity_threshold if "liquidity_threshold" in config.model_fields_set else None,
+        }
+    )
+    apply_universe_filters(factor_model, universe_config)
+    if config.min_valid_periods:
+        factor_model.minimum_valid_periods(signal_col, config.min_valid_periods)
 
-    # Log results
-    logger = Logger(FACTOR_LOGS_DIR, factor_model)
-    factor_model.results_dict = {
-        "cumulative_returns": total_cumulative,
-        "annualized_return": annualized,
-        "sharpe_ratio": sharpe,
-        "sortino_ratio": sortino,
-        "years": years,
-        "long_only_returns": long_only_cumulative,
-        "short_only_returns": short_only_cumulative,
-        "start_date": str(dates_list[0]) if dates_list else None,
-        "end_date": str(dates_list[-1]) if dates_list else None,
-    }
-    logger.log_results(factor_model.results_dict)
+    # Long high momentum, short low momentum by default
+    form_long_short_portfolios(factor_model, signal_col, long_high=signal_long_high(config, Factor.EQUITY))
 
-    return result
+    return finalize_factor_run(
+        Factor.EQUITY,
+        config,
+        factor_model,
+        data_quality,
+        log_extra={
+            "trailing_momentum_lookback_periods": lookback_periods,
+            "lookback_days": config.lookback_days,
+            "num_tickers": len(config.tickers) if config.tickers else None,
+        },
+    )
 
 
-@app.post("/compute/momentum")
-async def compute_momentum_factor(
+@app.post("/compute/composite")
+async def compute_composite_factor(
     config: FactorConfig,
-    lookback_periods: int = Query(3, ge=1, le=12, description="Lookback periods for momentum calculation"),
+    weights: Dict[str, float] = Body(..., description="Base factor weights, e.g. {\"smb\": 0.5, \"momentum\": 0.5}"),
+    lookback_periods: int = Query(
+        3, ge=1, le=12, description="Lookback periods for the momentum signal and growth rates"
+    ),
 ):
     """
-    Compute Momentum factor model with volatility adjustment.
+    Compute a composite factor that blends the signals of several base factors.
 
-    Uses vol-adjusted momentum: raw_momentum * (|mean_return| / std)
+    Each base signal is oriented so that higher values go long, z-scored across
+    assets on each date so scales are comparable, then combined using the
+    weights normalized by their absolute sum.
     """
-    # Validate factor name matches endpoint
-    if config.factor not in ("momentum", "MOMENTUM"):
+    return await dispatch_compute(
+        Factor.COMPOSITE, config, weights=weights, lookback_periods=lookback_periods
+    )
+
+
+def run_composite_factor(config: FactorConfig, weights: Dict[str, float], lookback_periods: int) -> dict:
+    """Compute and log a composite run (blocking)"""
+    ensure_endpoint_factor(config, Factor.COMPOSITE)
+
+    unsupported = [factor for factor in weights if factor not in COMPOSITE_BASE_FACTORS]
+    if unsupported:
         raise HTTPException(
             status_code=400,
-            detail=f"Factor name '{config.factor}' does not match endpoint. Expected 'momentum'.",
+            detail=f"Unsupported composite factors: {unsupported}. Supported: {COMPOSITE_BASE_FACTORS}",
         )
+    total_weight = sum(abs(weight) for weight in weights.values())
+    if total_weight == 0:
+        raise HTTPException(status_code=400, detail="Composite weights must not all be zero")
+    if Factor.GROWTH in weights:
+        ensure_growth_weights(config)
+    elif config.normalize_by_mc:
+        raise HTTPException(status_code=400, detail="normalize_by_mc requires growth in the composite weights")
+    ensure_run_id_available(Factor.COMPOSITE, config.run_id)
+    deadline = compute_deadline(config)
 
     ApiData, FactorModel, Logger, cumulative_returns = _load_utils()
 
-    try:
-        df = _fetch_merged_crypto_data(
-            start_date=config.start_date,
-            end_date=config.end_date,
-            artemis_metrics=["mc"],
-            api_key=API_KEY,
-        )
-    except Exception as e:
-        raise HTTPException(
-            status_code=502,
-            detail=f"Failed to fetch merged crypto data: {str(e)}",
-        )
+    # Only market cap is required: assets missing a base factor's metrics are kept
+    # and handled by the partial metric policy
+    metrics = list(FACTOR_METRICS[Factor.COMPOSITE])
+    extra_aggs = {}
+    if Factor.VALUE in weights:
+        metrics.append("fees")
+    if Factor.GROWTH in weights:
+        metrics.extend(metric for metric in config.growth_weights if metric not in metrics)
+        extra_aggs.update({metric: "mean" for metric in config.growth_weights})
+    data_quality = {}
+    df = fetch_factor_data(config, metrics, data_quality, required_metrics=["mc"], deadline=deadline)
+    if Factor.VALUE in weights:
+        # Value sums each period's fees while growth averages them, so it gets its own column
+        df = df.assign(value_fees=df["fees"])
+        extra_aggs["value_fees"] = "sum"
 
-    # Initialize factor model
     factor_model = FactorModel(
         df=df.reset_index(),
-        factor="momentum",
+        factor=Factor.COMPOSITE,
         breakpoint=config.breakpoint,
         min_assets=config.min_assets,
         weighting_method=config.weighting_method,
+        run_id=config.run_id,
+        empty_leg_policy=config.empty_leg_policy,
+        leg_overlap_policy=config.leg_overlap_policy,
+        portfolio_mode=config.portfolio_mode,
+        deadline=deadline,
+        stop_event=compute_stop_event(),
+        transaction_cost_bps=config.transaction_cost_bps,
     )
 
-    # Resample to weekly
-    factor_model.resample("W", {"price": "last", "mc": "last", "24h_volume": "sum"})
-
-    # Calculate price changes and momentum metrics
-    factor_model.calculate_price_pct_change(periods=1)
-    factor_model.calculate_price_pct_change(periods=lookback_periods)
-
-    # Calculate rolling volatility metrics
-    factor_model.df["rolling_mean"] = factor_model.df.groupby("asset")[
-        "price_pct_change_p1"
-    ].transform(lambda x: x.rolling(lookback_periods, min_periods=1).mean())
+    prepare_period_data(factor_model, config, extra_aggs)
 
-    factor_model.df["rolling_std"] = factor_model.df.groupby("asset")[
-        "price_pct_change_p1"
-    ].transform(lambda x: x.rolling(lookback_periods, min_periods=1).std())
+    # Orient each signal so high values go long; size and value use logs so
+    # the z-score isn't dominated by the largest assets or richest valuations
+    factor_model.df["smb_signal"] = -np.log(factor_model.df["mc_t_minus_1"].where(lambda mc: mc > 0))
+    signal_cols = {Factor.SMB: "smb_signal"}
+    if Factor.MOMENTUM in weights:
+        signal_cols[Factor.MOMENTUM] = add_momentum_signal(factor_model, lookback_periods)
+    if Factor.VALUE in weights:
+        value_col = add_value_signal(factor_model, config, fees_col="value_fees")
+        factor_model.df["value_signal"] = -np.log(factor_model.df[value_col].where(lambda ratio: ratio > 0))
+        signal_cols[Factor.VALUE] = "value_signal"
+    if Factor.GROWTH in weights:
+        add_growth_rates(factor_model, config, lookback_periods)
 
-    # Vol ratio (essentially rolling Sharpe)
-    factor_model.df["vol_ratio"] = (
-        factor_model.df["rolling_mean"].abs() / factor_model.df["rolling_std"]
-    ).replace([np.inf, -np.inf], 0).fillna(0)
+    apply_universe_filters(factor_model, config)
+    if Factor.VALUE in weights and config.winsorize_pct is not None:
+        factor_model.df["value_signal"] = cross_sectional_winsorize(factor_model.df, "value_signal", config.winsorize_pct)
+    if Factor.GROWTH in weights:
+        # Growth blends its metrics the way a growth run does, over the filtered universe
+        signal_cols[Factor.GROWTH] = combine_growth_signal(factor_model, config, "average_available")
 
-    # Filtered momentum
-    factor_model.df["filtered_momentum"] = (
-        factor_model.df[f"price_pct_change_p{lookback_periods}"] * factor_model.df["vol_ratio"]
+    # Z-score after filtering so each date is standardized over the tradeable universe
+    signal_col = combine_composite_signal(
+        factor_model, signal_cols, weights, config.partial_metric_policy or "require_all"
     )
 
-    factor_model.get_t_minus_1_metrics(["mc", "24h_volume", "filtered_momentum"])
+    if config.min_valid_periods:
+        factor_model.minimum_valid_periods(signal_col, config.min_valid_periods)
 
-    # Apply filters
-    if config.market_cap_threshold:
-        factor_model.market_cap_threshold(config.market_cap_threshold)
-    if config.liquidity_threshold:
-        factor_model.liquidity_threshold(config.liquidity_threshold)
-    if config.min_lifetime_days:
-        factor_model.minimum_lifetime(config.min_lifetime_days)
+    form_long_short_portfolios(factor_model, signal_col, long_high=signal_long_high(config, Factor.COMPOSITE))
 
-    # Compute factor returns
-    dates = factor_model.df["date"].unique()
-    for date in dates:
-        period_data = factor_model.df[factor_model.df["date"] == date].copy()
-        period_data = period_data.dropna(subset=["filtered_momentum_t_minus_1", "price_pct_change_p1"])
+    return finalize_factor_run(
+        Factor.COMPOSITE,
+        config,
+        factor_model,
+        data_quality,
+        config_extra={"weights": weights, "lookback_periods": lookback_periods},
+    )
 
-        if len(period_data) < config.min_assets:
-            continue
 
-        # Sort by filtered momentum
-        period_data = period_data.sort_values("filtered_momentum_t_minus_1", ascending=False)
-        n = len(period_data)
-        cutoff = int(n * config.breakpoint)
+@dataclass(frozen=True)
+class FactorDefinition:
+    """
+    A computable factor: its name and the blocking run that computes and logs it.
 
-        # Long high momentum, short low momentum
-        long_portfolio = period_data.head(cutoff)
-        short_portfolio = period_data.tail(cutoff)
+    `run` takes the request config plus any factor-specific parameters as
+    keyword arguments (e.g. lookback_periods) and returns the compute response.
+    `lookback_periods` is the default for factors whose run takes one, and
+    `generic_route` is False for factors that need more than the config and
+    query parameters, so they're only reachable through their named route.
+    """
 
-        if len(long_portfolio) == 0 or len(short_portfolio) == 0:
-            continue
+    factor: Factor
+    run: Callable[..., dict]
+    lookback_periods: Optional[int] = None
+    generic_route: bool = True
 
-        long_return = long_portfolio["price_pct_change_p1"].mean()
-        short_return = short_portfolio["price_pct_change_p1"].mean()
+    @property
+    def name(self) -> str:
+        return self.factor.value
 
-        factor_model.factor_returns[date] = long_return - short_return
-        factor_model.long_portfolio_returns[date] = long_return
-        factor_model.short_portfolio_returns[date] = short_return
+    @property
+    def required_metrics(self) -> List[str]:
+        """Artemis metrics the factor fetches (price and volume come from the price source)"""
+        return FACTOR_METRICS[self.factor]
 
-    if not factor_model.factor_returns:
-        raise HTTPException(status_code=400, detail="No factor returns computed - check data availability")
+    def compute(self, config: FactorConfig, **params) -> dict:
+        """Run the factor, listing every asset left out of the run's universe as skipped_assets"""
+        ensure_benchmark_mapped(config)
+        result = self.run(config, **params)
+        result["skipped_assets"] = sorted(result.get("data_quality", {}).get("dropped_assets", {}))
+        return result
 
-    # Calculate performance metrics
-    returns_df = cumulative_returns(factor_model.factor_returns)
-    total_cumulative = returns_df["cumulative_returns"].iloc[-1] if not returns_df.empty else 0
 
-    dates_list = list(factor_model.factor_returns.keys())
-    if len(dates_list) >= 2:
-        days = (pd.to_datetime(dates_list[-1]) - pd.to_datetime(dates_list[0])).days
-        if days > 0:
-            years = days / 365
-            annualized = ((total_cumulative + 1) ** (1 / years)) - 1
-        else:
-            years = 0
-            annualized = 0
-    else:
-        years = 0
-        annualized = 0
+# Every computable factor; adding a factor is one entry here plus its run function
+FACTOR_REGISTRY = {
+    definition.factor: definition
+    for definition in [
+        FactorDefinition(Factor.SMB, run_smb_factor),
+        FactorDefinition(Factor.MARKET, run_market_factor),
+        FactorDefinition(Factor.VALUE, run_value_factor),
+        FactorDefinition(Factor.MOMENTUM, run_momentum_factor),
+        FactorDefinition(Factor.MOMENTUM_V2, run_momentum_v2_factor, lookback_periods=3),
+        FactorDefinition(Factor.GROWTH, run_growth_factor, lookback_periods=2),
+        # Composite also takes base factor weights in the request body
+        FactorDefinition(Factor.COMPOSITE, run_composite_factor, lookback_periods=3, generic_route=False),
+        FactorDefinition(Factor.EQUITY, run_equity_factor),
+    ]
+}
 
-    returns_series = pd.Series(list(factor_model.factor_returns.values()))
-    sharpe = (returns_series.mean() / returns_series.std()) * np.sqrt(52) if returns_series.std() > 0 else 0
 
-    # Calculate Sortino (downside deviation) - return None if no downside returns
-    downside_returns = returns_series[returns_series < 0]
-    if len(downside_returns) > 0:
-        downside_std = downside_returns.std()
-        sortino = (returns_series.mean() / downside_std) * np.sqrt(52) if downside_std > 0 else None
-    else:
-        sortino = None
+def get_factor_definition(name: str) -> FactorDefinition:
+    """Look up a computable factor by name, raising a 404 for unknown ones"""
+    definition = FACTOR_REGISTRY.get(Factor.parse(name))
+    if definition is None:
+        raise HTTPException(status_code=404, detail=f"Factor '{name}' cannot be computed")
+    return definition
 
-    result = {
-        "factor": "momentum",
-        "config": {**config.model_dump(), "lookback_periods": lookback_periods},
-        "performance": {
-            "cumulative_returns": float(total_cumulative),
-            "annualized_return": float(annualized),
-            "sharpe_ratio": float(sharpe),
-            "sortino_ratio": float(sortino) if sortino is not None else None,
-            "years": float(years),
-            "num_periods": len(factor_model.factor_returns),
-        },
-        "returns": {
-            str(k): float(v) for k, v in list(factor_model.factor_returns.items())[-10:]
-        },
-    }
 
-    # Log results
-    logger = Logger(FACTOR_LOGS_DIR, factor_model)
-    factor_model.results_dict = {
-        "cumulative_returns": total_cumulative,
-        "annualized_return": annualized,
-        "sharpe_ratio": sharpe,
-        "sortino_ratio": sortino,
-        "years": years,
-        "trailing_momentum_lookback_periods": lookback_periods,
-        "start_date": str(dates_list[0]) if dates_list else None,
-        "end_date": str(dates_list[-1]) if dates_list else None,
-    }
-    logger.log_results(factor_model.results_dict)
+def start_factor_compute(definition: FactorDefinition, config: FactorConfig, stream: bool = False, **params):
+    """Start a registered factor's run, or join the identical one in flight, and schedule the caller's callback"""
+    computation = start_single_flight(
+        definition.factor,
+        {"config": config.model_dump(exclude={"callback_url"}), **params},
+        functools.partial(definition.compute, config, **params),
+        stream=stream,
+    )
+    schedule_callback(computation.task, config, definition.factor)
+    return computation
 
-    return result
+
+async def dispatch_compute(factor: str, config: FactorConfig, **params) -> dict:
+    """Run a registered factor through the single-flight executor and schedule the caller's callback"""
+    task = start_factor_compute(get_factor_definition(factor), config, **params).task
+    # Shield so one client disconnecting doesn't cancel the computation for the others
+    return caller_result(await asyncio.shield(task), config)
 
 
 @app.post("/compute/equity-factors")
@@ -701,13 +4015,13 @@ async def compute_equity_factors(
         # For MVP, return existing factor comparison data
         # In production, this would return equity-specific factor exposures
         comparison_data = []
-        for factor in AVAILABLE_FACTORS:
+        for factor in Factor:
             try:
-                df = load_factor_logs(factor)
+                df = complete_runs(load_factor_logs(factor))
                 if not df.empty:
                     row = df.iloc[-1]
                     comparison_data.append({
-                        "factor": factor,
+                        "factor": factor.value,
                         "annualized_return": row.get("annualized_return"),
                         "sharpe_ratio": row.get("sharpe_ratio"),
                         "sortino_ratio": row.get("sortino_ratio"),
@@ -730,6 +4044,284 @@ async def compute_equity_factors(
         )
 
 
+# Registered after the named routes so /compute/smb, /compute/equity-factors, etc. match first
+@app.post("/compute/{factor}")
+async def compute_factor(
+    factor: str,
+    config: FactorConfig,
+    lookback_periods: Optional[int] = Query(
+        None, ge=1, le=52, description="Lookback periods, for factors that take one (default: the factor's own)"
+    ),
+):
+    """
+    Compute any registered factor by name.
+
+    Runs the same computation as the factor's named route, so a factor added to
+    FACTOR_REGISTRY is computable here without a route of its own.
+    """
+    definition = get_factor_definition(factor)
+    params = generic_compute_params(definition, lookback_periods)
+    return await dispatch_compute(definition.factor, config, **params)
+
+
+def generic_compute_params(definition: FactorDefinition, lookback_periods: Optional[int]) -> dict:
+    """
+    The run parameters of a factor computed by name, rejecting factors that need a route of their own.
+    Shared by /compute/{factor} and its stream and sweep routes, so they accept and reject the same factors.
+    """
+    if not definition.generic_route:
+        raise HTTPException(
+            status_code=400, detail=f"Factor '{definition.name}' takes extra parameters; use /compute/{definition.name}"
+        )
+
+    params = {}
+    if definition.lookback_periods is not None:
+        params["lookback_periods"] = lookback_periods or definition.lookback_periods
+    elif lookback_periods is not None:
+        raise HTTPException(status_code=400, detail=f"Factor '{definition.name}' does not take lookback_periods")
+    return params
+
+
+def sse_event(event: str, data: dict) -> str:
+    """Format one server-sent event"""
+    return f"event: {event}\ndata: {json.dumps(jsonable_encoder(data))}\n\n"
+
+
+@app.get("/compute/{factor}/stream")
+async def stream_compute(
+    factor: str,
+    config: str = Query(..., description="The FactorConfig the POST routes take, as JSON"),
+    lookback_periods: Optional[int] = Query(
+        None, ge=1, le=52, description="Lookback periods, for factors that take one (default: the factor's own)"
+    ),
+):
+    """
+    Compute a registered factor by name, streaming its progress as server-sent events.
+
+    Each phase ("fetching_metrics", "fetching_prices", "computing") is an event of
+    that name with the phase's percent complete where known, and the run ends with
+    a "done" event carrying the same result as POST /compute/{factor}, or an
+    "error" event. The run is shared with identical requests in flight, streamed
+    or not; it's cancelled once every stream sharing it has disconnected, unless a
+    POST request shares it too.
+    """
+    definition = get_factor_definition(factor)
+    params = generic_compute_params(definition, lookback_periods)
+    try:
+        config = FactorConfig.model_validate_json(config)
+    except ValidationError as e:
+        raise RequestValidationError(
+            [{**err, "loc": ("query", "config", *err["loc"])} for err in e.errors(include_url=False)]
+        )
+
+    async def events():
+        computation = start_factor_compute(definition, config, stream=True, **params)
+        task, queue = computation.task, computation.progress.subscribe()
+        next_event = None
+        try:
+            while True:
+                next_event = asyncio.ensure_future(queue.get())
+                done, _ = await asyncio.wait({next_event, task}, return_when=asyncio.FIRST_COMPLETED)
+                if next_event not in done:
+                    break
+                event = next_event.result()
+                yield sse_event(event["phase"], event)
+            while not queue.empty():
+                event = queue.get_nowait()
+                yield sse_event(event["phase"], event)
+
+            try:
+                result = caller_result(task.result(), config)
+            except HTTPException as e:
+                yield sse_event("error", {"status_code": e.status_code, "detail": e.detail})
+                return
+            except Exception as e:
+                log.exception(f"Streamed compute of {definition.name} failed")
+                yield sse_event("error", {"status_code": 500, "detail": f"Compute failed: {str(e)}"})
+                return
+
+            yield sse_event("done", {"phase": "done", "percent": 100.0, "result": result})
+        finally:
+            # The generator is closed early when the client disconnects: the run stops at
+            # its next checkpoint once no other caller shares it
+            if next_event is not None:
+                next_event.cancel()
+            computation.progress.unsubscribe(queue)
+
+    return StreamingResponse(
+        events(),
+        media_type="text/event-stream",
+        headers={"Cache-Control": "no-cache", "X-Accel-Buffering": "no"},
+    )
+
+
+def sweep_configs(config: FactorConfig, breakpoints: List[float], min_assets: Optional[List[int]]) -> List[FactorConfig]:
+    """Expand a base config into one validated config per (breakpoint, min_assets) combination"""
+    if config.run_id:
+        raise HTTPException(status_code=400, detail="run_id can't be set for a sweep: every combination is its own run")
+    min_assets_values = min_assets or [config.min_assets]
+    num_runs = len(breakpoints) * len(min_assets_values)
+    if num_runs > SWEEP_MAX_RUNS:
+        raise HTTPException(
+            status_code=400,
+            detail=f"Sweep has {num_runs} parameter combinations; at most {SWEEP_MAX_RUNS} are allowed",
+        )
+
+    # Dates are already resolved, so lookback_period is dropped rather than conflicting with start_date
+    base = config.model_dump(exclude={"lookback_period", "callback_url"})
+    configs = []
+    for breakpoint, assets in itertools.product(breakpoints, min_assets_values):
+        try:
+            configs.append(FactorConfig.model_validate({**base, "breakpoint": breakpoint, "min_assets": assets}))
+        except ValidationError as e:
+            errors = "; ".join(f"{'.'.join(map(str, err['loc']))}: {err['msg']}" for err in e.errors())
+            raise HTTPException(
+                status_code=400,
+                detail=f"Invalid sweep combination breakpoint={breakpoint}, min_assets={assets}: {errors}",
+            )
+    return configs
+
+
+def run_sweep(definition: FactorDefinition, configs: List[FactorConfig], **params) -> dict:
+    """
+    Run a factor once per config (blocking), fetching the data only once.
+
+    Every run is computed and logged like a normal compute; a combination that
+    fails (e.g. too few assets for its min_assets) is reported under "errors"
+    without stopping the rest of the sweep.
+    """
+    results, errors = [], []
+    token = _sweep_fetches.set({})
+    try:
+        for config in configs:
+            try:
+                results.append(definition.compute(config, **params))
+            except HTTPException as e:
+                # Data source failures hit every combination alike, so don't keep going
+                if e.status_code == 502:
+                    raise
+                errors.append({
+                    "breakpoint": config.breakpoint,
+                    "min_assets": config.min_assets,
+                    "status_code": e.status_code,
+                    "detail": e.detail,
+                })
+    finally:
+        _sweep_fetches.reset(token)
+
+    return {
+        "factor": definition.name,
+        "num_runs": len(results),
+        "results": results,
+        "errors": errors,
+    }
+
+
+@app.post("/compute/{factor}/sweep")
+async def compute_factor_sweep(
+    factor: str,
+    config: FactorConfig,
+    breakpoints: List[float] = Body(..., min_length=1, description="Breakpoints to run, e.g. [0.1, 0.2, 0.3]"),
+    min_assets: Optional[List[int]] = Body(
+        None, min_length=1, description="min_assets values to cross with the breakpoints (default: the config's)"
+    ),
+    lookback_periods: Optional[int] = Query(
+        None, ge=1, le=52, description="Lookback periods, for factors that take one (default: the factor's own)"
+    ),
+):
+    """
+    Compute a factor over a grid of breakpoints (and optionally min_assets values).
+
+    The market data is fetched once and shared by every combination; each
+    combination is logged as its own run, with a generated run id.
+    """
+    definition = get_factor_definition(factor)
+    params = generic_compute_params(definition, lookback_periods)
+    ensure_endpoint_factor(config, definition.factor)
+
+    configs = sweep_configs(config, breakpoints, min_assets)
+    task = asyncio.ensure_future(asyncio.to_thread(run_sweep, definition, configs, **params))
+    schedule_callback(task, config, definition.factor)
+    return await asyncio.shield(task)
+
+
+@app.post("/factors/{factor}/runs/{run_id}/reproduce")
+async def reproduce_run(factor: str, run_id: str):
+    """
+    Recompute a logged run from its saved config, as a new run.
+
+    The saved config is upgraded to the current schema first; every field it
+    lacks takes today's default and is reported in `upgrade_warnings` (and logged),
+    since a default that differs from the original run's behavior changes results.
+    """
+    definition = get_factor_definition(factor)
+    saved, schema_version = await asyncio.to_thread(load_run_config, definition.factor, run_id)
+    config, params, warnings = upgrade_run_config(saved, schema_version)
+
+    if schema_version == 0:
+        warnings.insert(
+            0, "Run predates saved configs; it was rebuilt from its log columns, whose dates are the performance window"
+        )
+    if definition.factor == Factor.COMPOSITE and "weights" not in params:
+        raise HTTPException(status_code=400, detail=f"Run '{run_id}' can't be reproduced: its weights weren't saved")
+    if definition.lookback_periods is None:
+        params.pop("lookback_periods", None)
+    elif "lookback_periods" in params:
+        params["lookback_periods"] = int(params["lookback_periods"])
+    else:
+        params["lookback_periods"] = definition.lookback_periods
+        warnings.append(
+            f"'lookback_periods' wasn't saved; using the current default {definition.lookback_periods}, "
+            "which may change results"
+        )
+    for warning in warnings:
+        log.warning(f"Reproducing {definition.name} run {run_id}: {warning}")
+
+    result = await dispatch_compute(definition.factor, config, **params)
+    return {
+        **result,
+        "reproduced_from": run_id,
+        "config_schema_version": schema_version,
+        "upgrade_warnings": warnings,
+    }
+
+
+@app.delete("/factors/{factor}/logs")
+async def delete_factor_logs(factor: str):
+    """Delete all of a factor's logged runs: its log csv, index manifest and every run's saved files"""
+    factor = Factor.parse(factor)
+    _, _, Logger, _ = _load_utils()
+
+    deleted = await asyncio.to_thread(
+        Logger.delete_factor_logs, FACTOR_LOGS_DIR, factor.value, tuple(f.value for f in Factor)
+    )
+    log.info(f"Deleted {len(deleted)} log files for factor {factor.value}")
+
+    return {"factor": factor, "files_deleted": len(deleted), "files": deleted}
+
+
+@app.post("/admin/purge")
+async def purge_old_runs(
+    older_than_days: int = Query(..., ge=1, description="Delete runs created more than this many days ago"),
+):
+    """Delete runs older than a retention window across all factors: log rows, time series and compositions"""
+    _, _, Logger, _ = _load_utils()
+    cutoff = datetime.now() - pd.Timedelta(days=older_than_days)
+
+    purged = {}
+    for factor in Factor:
+        run_ids = await asyncio.to_thread(Logger.purge, FACTOR_LOGS_DIR, factor.value, cutoff)
+        if run_ids:
+            purged[factor.value] = run_ids
+
+    return {
+        "older_than_days": older_than_days,
+        "cutoff": cutoff.isoformat(timespec="seconds"),
+        "purged_runs": sum(len(run_ids) for run_ids in purged.values()),
+        "by_factor": purged,
+    }
+
+
 @app.get("/health")
 async def health_check():
     """Health check endpoint"""
@@ -740,7 +4332,91 @@ async def health_check():
     }
 
 
+def check_dependency(check: Callable[[], object]) -> dict:
+    """Run a connectivity check, reporting "up" or "down" (with the error) and its latency"""
+    started = time.monotonic()
+    try:
+        check()
+        status = {"status": "up"}
+    except Exception as e:
+        status = {"status": "down", "error": str(e)}
+    status["latency_ms"] = round((time.monotonic() - started) * 1000, 1)
+    return status
+
+
+def ping_artemis():
+    """List Artemis asset symbols once, without retries, as a connectivity check"""
+    if not API_KEY:
+        raise RuntimeError("ARTEMIS_API_KEY environment variable not set")
+    ApiData, _, _, _ = _load_utils()
+    ApiData(API_KEY, timeout=HEALTH_CHECK_TIMEOUT_SECONDS).client.asset.list_asset_symbols()
+
+
+def ping_coinbase():
+    """Fetch one Coinbase candle, without retries, as a connectivity check"""
+    from utils import CoinbaseData
+
+    CoinbaseData(timeout=HEALTH_CHECK_TIMEOUT_SECONDS).ping()
+
+
+@app.get("/health/deep")
+async def deep_health_check():
+    """
+    Readiness check that calls Artemis and Coinbase, with per-dependency status and latency.
+
+    Both are checked concurrently with short timeouts; any dependency down gives a
+    503, still with the full breakdown in the body.
+    """
+    artemis, coinbase = await asyncio.gather(
+        asyncio.to_thread(check_dependency, ping_artemis),
+        asyncio.to_thread(check_dependency, ping_coinbase),
+    )
+    dependencies = {"artemis": artemis, "coinbase": coinbase}
+    healthy = all(dependency["status"] == "up" for dependency in dependencies.values())
+
+    return JSONResponse(
+        status_code=200 if healthy else 503,
+        content={
+            "status": "healthy" if healthy else "unhealthy",
+            "timestamp": datetime.now().isoformat(),
+            "dependencies": dependencies,
+        },
+    )
+
+
+def resolve_bind_host(address: str) -> str:
+    """Validate a bind address: an IPv4/IPv6 address or a hostname such as localhost"""
+    address = address.strip()
+    if address.startswith("[") and address.endswith("]"):
+        address = address[1:-1]
+    try:
+        return str(ipaddress.ip_address(address))
+    except ValueError:
+        pass
+    if not HOSTNAME_PATTERN.match(address) or address.replace(".", "").isdigit():
+        raise ValueError(f"Invalid BIND_ADDRESS '{address}', expected an IP address or hostname")
+    return address
+
+
 if __name__ == "__main__":
     import uvicorn
 
-    uvicorn.run(app, host="0.0.0.0", port=8000)
+    class DrainingServer(uvicorn.Server):
+        """Uvicorn server logging the requests it drains when a SIGTERM/SIGINT starts a graceful shutdown"""
+
+        def handle_exit(self, sig, frame):
+            if not self.should_exit:
+                log.info(
+                    f"Received {signal.Signals(sig).name}: draining {_in_flight_requests} in-flight requests "
+                    f"for up to {SHUTDOWN_TIMEOUT_SECONDS:g}s"
+                )
+            super().handle_exit(sig, frame)
+
+    DrainingServer(
+        uvicorn.Config(
+            app,
+            host=resolve_bind_host(BIND_ADDRESS),
+            port=BIND_PORT,
+            timeout_graceful_shutdown=SHUTDOWN_TIMEOUT_SECONDS,
+        )
+    ).run()
@@ -0,0 +1,608 @@
+"""
+Statistics helpers shared by the API and factor models.
+
+Only depends on numpy/pandas so the read-only endpoints can use these
+without pulling in the heavier utils module.
+"""
+
+from statistics import NormalDist
+
+import numpy as np
+import pandas as pd
+
+
+# ============================================================================
+# Return Helpers
+# ============================================================================
+
+
+def annualize_return(cumulative_return: float, days: float) -> float:
+    """
+    Compound a cumulative return earned over `days` calendar days to an annual rate.
+
+    Returns 0 for non-positive windows, matching how the compute endpoints
+    report runs with a single period.
+    """
+    if days <= 0:
+        return 0.0
+    years = days / 365
+    return ((1 + cumulative_return) ** (1 / years)) - 1
+
+
+def simple_returns(prices, periods: int = 1) -> pd.Series:
+    """
+    Simple returns p[t] / p[t - periods] - 1 of a price series, index preserved.
+
+    Convention shared by the return helpers: the first `periods` values have no
+    prior price and are NaN, as is any return whose prior price is missing or not
+    positive, rather than an infinite or misleading value.
+    """
+    prices = pd.Series(prices, dtype=float)
+    prior = prices.shift(periods)
+    return prices / prior.where(prior > 0) - 1
+
+
+def log_returns(prices, periods: int = 1) -> pd.Series:
+    """
+    Log returns ln(p[t] / p[t - periods]) of a price series, index preserved.
+
+    Leading values are NaN as for simple_returns; non-positive prices also give NaN.
+    """
+    prices = pd.Series(prices, dtype=float)
+    return np.log(prices.where(prices > 0)).diff(periods)
+
+
+def cumulative_returns(returns) -> pd.Series:
+    """
+    Compounded cumulative return, prod(1 + r) - 1, at each point of a return series.
+
+    Leading NaNs (e.g. the first value of simple_returns) stay NaN since nothing
+    has been earned yet; a NaN after the first return counts as a flat period, so
+    the cumulative return carries forward.
+    """
+    returns = pd.Series(returns, dtype=float)
+    started = returns.notna().cumsum() > 0
+    return ((1 + returns.fillna(0.0)).cumprod() - 1).where(started)
+
+
+# ============================================================================
+# Risk Helpers
+# ============================================================================
+
+
+def sharpe_ratio(returns, periods_per_year: float = 52, risk_free: float = 0.0) -> float:
+    """
+    Annualized Sharpe ratio of periodic returns.
+
+    `risk_free` is the per-period rate. Returns 0 when the returns have no
+    variance, matching the compute endpoints' historical behavior.
+    """
+    excess = pd.Series(returns, dtype=float).dropna() - risk_free
+    std = excess.std()
+    if not std > 0:
+        return 0.0
+    return float((excess.mean() / std) * np.sqrt(periods_per_year))
+
+
+def sortino_ratio(returns, periods_per_year: float = 52, risk_free: float = 0.0):
+    """
+    Annualized Sortino ratio: mean excess return over the downside deviation.
+
+    Downside deviation is the sample standard deviation of the below-`risk_free`
+    excess returns only, the definition every stored run was computed with. Returns
+    None when fewer than two periods fall below `risk_free` or they don't vary,
+    since the ratio is then unbounded or undefined.
+    """
+    excess = pd.Series(returns, dtype=float).dropna() - risk_free
+    downside_std = excess[excess < 0].std()
+    if not downside_std > 0:
+        return None
+    return float((excess.mean() / downside_std) * np.sqrt(periods_per_year))
+
+
+def ic_information_ratio(ics, periods_per_year: float = 52):
+    """
+    Annualized information ratio of per-period ICs: their mean over their standard deviation.
+
+    Like a Sharpe ratio of the signal's predictive power, it rewards a stable IC
+    over a higher but erratic one. NaN ICs are ignored; returns None with fewer
+    than two ICs or no variance.
+    """
+    ics = pd.Series(ics, dtype=float).dropna()
+    std = ics.std()
+    if len(ics) < 2 or not std > 0:
+        return None
+    return float((ics.mean() / std) * np.sqrt(periods_per_year))
+
+
+def tracking_error(active, periods_per_year: float = 52):
+    """
+    Annualized tracking error: the standard deviation of active returns (portfolio
+    minus benchmark, per period). NaNs are ignored; returns None below two values.
+    """
+    active = pd.Series(active, dtype=float).dropna()
+    if len(active) < 2:
+        return None
+    return float(active.std() * np.sqrt(periods_per_year))
+
+
+def information_ratio(active, periods_per_year: float = 52):
+    """
+    Annualized information ratio: the annualized mean active return over the tracking error.
+
+    Returns None with fewer than two active returns or a tracking error that is 0 up
+    to rounding, e.g. a portfolio that replicates its benchmark.
+    """
+    active = pd.Series(active, dtype=float).dropna()
+    error = tracking_error(active, periods_per_year)
+    if error is None or np.isclose(error, 0):
+        return None
+    return float(active.mean() * periods_per_year / error)
+
+
+def max_drawdown(cumulative) -> float:
+    """
+    Largest peak-to-trough decline of a cumulative return path, as a value <= 0.
+
+    `cumulative` holds cumulative returns (e.g. from cumulative_returns), so wealth
+    is 1 + cumulative and the starting wealth of 1 counts as the first peak.
+    Returns 0 for an empty or always-rising path.
+    """
+    wealth = 1 + pd.Series(cumulative, dtype=float).dropna()
+    if wealth.empty:
+        return 0.0
+    running_max = wealth.cummax().clip(lower=1.0)
+    return float(min((wealth / running_max - 1).min(), 0.0))
+
+
+def calmar_ratio(annualized_return, max_drawdown_value):
+    """
+    Calmar ratio: annualized return over the magnitude of the max drawdown.
+
+    `max_drawdown_value` is a value <= 0 as returned by max_drawdown. Returns None
+    when the path never fell, since the ratio is unbounded, or without an
+    annualized return.
+    """
+    if annualized_return is None or not max_drawdown_value < 0:
+        return None
+    return float(annualized_return / abs(max_drawdown_value))
+
+
+def drawdown_series(cumulative) -> list:
+    """
+    Drawdown from the running peak at each point of a cumulative return path, as values <= 0.
+
+    Wealth and peaks are as in max_drawdown; missing cumulative values give None.
+    """
+    cumulative = pd.Series(cumulative, dtype=float)
+    wealth = 1 + cumulative
+    running_max = wealth.cummax().clip(lower=1.0)
+    drawdown = (wealth / running_max - 1).clip(upper=0.0)
+    return [None if pd.isna(value) else float(value) for value in drawdown]
+
+
+def worst_drawdown(cumulative: pd.Series) -> dict:
+    """
+    The deepest drawdown episode of a cumulative return path indexed by date.
+
+    Returns the peak, trough and recovery labels (recovery is None if wealth never
+    regained the peak; the peak is None if it was the starting wealth of 1) with
+    the depth as a value <= 0, or None when the path never fell.
+    """
+    wealth = 1 + pd.Series(cumulative, dtype=float).dropna()
+    if wealth.empty:
+        return None
+    running_max = wealth.cummax().clip(lower=1.0)
+    drawdown = wealth / running_max - 1
+    if not (drawdown < 0).any():
+        return None
+
+    trough = drawdown.idxmin()
+    before_trough = wealth.loc[:trough]
+    peak_value = running_max.loc[trough]
+    at_peak = before_trough[before_trough >= peak_value]
+    peak = at_peak.index[-1] if not at_peak.empty else None
+    recovered = wealth.loc[trough:]
+    recovered = recovered[recovered >= peak_value]
+
+    return {
+        "peak": peak,
+        "trough": trough,
+        "recovery": recovered.index[0] if not recovered.empty else None,
+        "depth": float(drawdown.loc[trough]),
+    }
+
+
+def skewness(returns) -> float:
+    """Sample skewness of returns (bias-corrected, as pandas computes it); NaN below 3 values"""
+    return float(pd.Series(returns, dtype=float).dropna().skew())
+
+
+def excess_kurtosis(returns) -> float:
+    """Sample excess kurtosis of returns (0 for a normal distribution); NaN below 4 values"""
+    return float(pd.Series(returns, dtype=float).dropna().kurt())
+
+
+def value_at_risk(returns, confidence: float = 0.95, method: str = "gaussian") -> float:
+    """
+    Parametric one-period value at risk, as the return threshold (usually negative).
+
+    "gaussian" uses mean + z * std with the normal quantile z at 1 - confidence.
+    "cornish_fisher" first adjusts z for the sample skewness S and excess kurtosis K:
+    z + (z² - 1)S/6 + (z³ - 3z)K/24 - (2z³ - 5z)S²/36, so fat left tails give a
+    lower (worse) threshold than the Gaussian estimate.
+    """
+    returns = pd.Series(returns, dtype=float).dropna()
+    z = NormalDist().inv_cdf(1 - confidence)
+    if method == "cornish_fisher":
+        skew, kurt = skewness(returns), excess_kurtosis(returns)
+        z = z + (z**2 - 1) * skew / 6 + (z**3 - 3 * z) * kurt / 24 - (2 * z**3 - 5 * z) * skew**2 / 36
+    elif method != "gaussian":
+        raise ValueError(f"Unknown VaR method: {method}")
+    return float(returns.mean() + z * returns.std())
+
+
+# ============================================================================
+# Rolling Helpers
+# ============================================================================
+
+
+def rolling_correlation(x: pd.Series, y: pd.Series, window: int) -> pd.Series:
+    """
+    Trailing Pearson correlation of two aligned series over `window` observations.
+
+    Leading positions without a full window are NaN, as are windows where
+    either series is constant.
+    """
+    x = pd.Series(x, dtype=float)
+    y = pd.Series(y, dtype=float)
+    correlation = x.rolling(window, min_periods=window).corr(y)
+    return correlation.replace([np.inf, -np.inf], np.nan)
+
+
+def rolling_beta(y: pd.Series, x: pd.Series, window: int) -> pd.Series:
+    """
+    Trailing OLS beta of `y` on `x` (cov / var) over `window` aligned observations.
+
+    Leading positions without a full window are NaN, as are windows where `x`
+    is constant.
+    """
+    x = pd.Series(x, dtype=float)
+    y = pd.Series(y, dtype=float)
+    beta = y.rolling(window, min_periods=window).cov(x) / x.rolling(window, min_periods=window).var()
+    return beta.replace([np.inf, -np.inf], np.nan)
+
+
+def _rolling_values(returns, window: int, statistic) -> list:
+    """Apply a pandas rolling statistic and return it as a list with None for missing values"""
+    if window < 1:
+        raise ValueError("window must be at least 1")
+    rolled = statistic(pd.Series(returns, dtype=float).rolling(window, min_periods=window))
+    rolled = rolled.replace([np.inf, -np.inf], np.nan)
+    return [None if pd.isna(value) else float(value) for value in rolled]
+
+
+def rolling_mean(returns, window: int) -> list:
+    """
+    Trailing mean over `window` observations, aligned to the input.
+
+    The first window - 1 entries are None, so a window longer than the series
+    gives all None; a window containing a missing value is None too.
+    """
+    return _rolling_values(returns, window, lambda rolling: rolling.mean())
+
+
+def rolling_std(returns, window: int) -> list:
+    """
+    Trailing sample standard deviation over `window` observations, aligned as rolling_mean.
+
+    A window of 1 is accepted but a single observation has no sample std, so every entry is None.
+    """
+    return _rolling_values(returns, window, lambda rolling: rolling.std())
+
+
+def rolling_volatility(returns, window: int, periods_per_year: float = 52) -> list:
+    """Trailing annualized volatility over `window` observations, aligned as rolling_std"""
+    return [
+        float(std * np.sqrt(periods_per_year)) if std is not None else None for std in rolling_std(returns, window)
+    ]
+
+
+def rolling_sharpe(returns, window: int, periods_per_year: float = 52, risk_free: float = 0.0) -> list:
+    """
+    Trailing annualized Sharpe ratio over `window` observations, aligned as rolling_mean.
+
+    Entries are None where the window has no variance (including window=1).
+    """
+    excess = pd.Series(returns, dtype=float) - risk_free
+    means = rolling_mean(excess, window)
+    stds = rolling_std(excess, window)
+    return [
+        float(mean / std * np.sqrt(periods_per_year)) if mean is not None and std else None
+        for mean, std in zip(means, stds)
+    ]
+
+
+# ============================================================================
+# Cross-Sectional Helpers
+# ============================================================================
+
+
+# Scales the MAD to the standard deviation for normally distributed values
+MAD_TO_STD = 1.4826
+
+
+def zscore(values) -> pd.Series:
+    """
+    Standardize values to mean 0 and (sample) standard deviation 1.
+
+    Takes any array-like; a Series keeps its index. Missing values stay NaN.
+    Values that are all equal carry no ranking information, so their z-scores
+    are 0 rather than NaN; a single value has no spread and stays NaN.
+    """
+    values = pd.Series(values, dtype=float)
+    std = values.std()
+    if std == 0:
+        return values.where(values.isna(), 0.0)
+    return (values - values.mean()) / std
+
+
+def leave_one_out_zscore(values) -> pd.Series:
+    """
+    Z-score each value against the mean and sample standard deviation of the other values.
+
+    A value can't inflate the spread it's measured against, so one extreme value
+    scores as extreme however few values there are; zscore caps |z| at
+    (n - 1) / sqrt(n). Needs at least three values, else all NaN. Missing values
+    stay NaN, as does a value whose others don't vary.
+    """
+    values = pd.Series(values, dtype=float)
+    others = values.count() - 1
+    if others < 2:
+        return pd.Series(np.nan, index=values.index)
+    # Centering first keeps the sums of squares from cancelling away small spreads
+    centered = values - values.mean()
+    others_mean = (centered.sum() - centered) / others
+    others_var = ((centered**2).sum() - centered**2 - others * others_mean**2) / (others - 1)
+    spread = others_var > 1e-10 * (centered**2).mean()
+    return ((centered - others_mean) / np.sqrt(others_var.where(spread))).where(values.notna())
+
+
+def robust_zscore(values) -> pd.Series:
+    """
+    Outlier-resistant z-score: distance from the median in units of the median
+    absolute deviation, scaled by MAD_TO_STD so it matches zscore for normal data.
+
+    A few extreme values barely move the median or MAD, so they don't compress
+    everyone else's scores the way they inflate a standard deviation. Missing
+    values stay NaN, and values with no MAD (e.g. all equal) score 0.
+    """
+    values = pd.Series(values, dtype=float)
+    median = values.median()
+    mad = (values - median).abs().median()
+    if mad == 0:
+        return values.where(values.isna(), 0.0)
+    return (values - median) / (MAD_TO_STD * mad)
+
+
+def cross_sectional_zscore(
+    df: pd.DataFrame, value_col: str, group_col: str = "date"
+) -> pd.Series:
+    """
+    Z-score a column within each group (by default, across assets on each date),
+    treating missing values and constant groups like zscore does. Vectorized over
+    the groups rather than calling zscore once per date.
+    """
+    values = df[value_col].astype(float)
+    grouped = values.groupby(df[group_col])
+    std = grouped.transform("std")
+    zscores = (values - grouped.transform("mean")) / std
+    return zscores.mask((std == 0) & values.notna(), 0.0)
+
+
+def winsorize(values, lower_pct: float, upper_pct: float) -> pd.Series:
+    """
+    Clamp values to their `lower_pct` and `1 - upper_pct` quantiles, e.g. 0.01 and
+    0.01 cap the bottom and top 1%. NaNs are ignored and stay NaN.
+
+    Raises ValueError for a percentile outside [0, 0.5), where the bounds would cross.
+    """
+    for name, pct in (("lower_pct", lower_pct), ("upper_pct", upper_pct)):
+        if not 0 <= pct < 0.5:
+            raise ValueError(f"{name} must be in [0, 0.5), got {pct}")
+    values = pd.Series(values, dtype=float)
+    return values.clip(lower=values.quantile(lower_pct), upper=values.quantile(1 - upper_pct))
+
+
+def cross_sectional_winsorize(df: pd.DataFrame, value_col: str, pct: float, group_col: str = "date") -> pd.Series:
+    """Winsorize a column at `pct` in both tails within each group (by default, across assets on each date)"""
+    values = df[value_col].astype(float)
+    return values.groupby(df[group_col]).transform(lambda group: winsorize(group, pct, pct))
+
+
+def combine_zscores(zscores: pd.DataFrame, weights: dict, policy: str = "require_all") -> pd.Series:
+    """
+    Weighted combination of per-component z-scores (one column per component).
+
+    `policy` decides how rows missing some components are treated:
+    - "require_all": the combined score is NaN unless every component is present
+    - "average_available": weights are renormalized over the components present
+    - "impute_mean": missing components count as the cross-sectional mean (z = 0)
+
+    Weights are normalized by their absolute sum. Rows with no components are NaN
+    under every policy.
+    """
+    weight_row = pd.Series(weights, dtype=float).reindex(zscores.columns).fillna(0.0)
+    present = zscores.notna()
+    weighted_sum = zscores.fillna(0.0).mul(weight_row, axis=1).sum(axis=1)
+
+    if policy == "average_available":
+        present_weight = present.mul(weight_row.abs(), axis=1).sum(axis=1)
+        combined = weighted_sum / present_weight.where(present_weight > 0)
+    elif policy in ("require_all", "impute_mean"):
+        combined = weighted_sum / weight_row.abs().sum()
+        if policy == "require_all":
+            combined = combined.where(present.all(axis=1))
+    else:
+        raise ValueError(f"Unknown partial metric policy: {policy}")
+
+    return combined.where(present.any(axis=1))
+
+
+# ============================================================================
+# Weighting Helpers
+# ============================================================================
+
+
+WEIGHTING_METHODS = ("equal", "market_cap", "inverse_variance")
+
+
+def apply_weights(num_assets: int, method: str, market_caps=None, variances=None) -> np.ndarray:
+    """
+    Portfolio weights for `num_assets` holdings under a weighting method.
+
+    - "equal": 1/n each
+    - "market_cap": proportional to `market_caps`
+    - "inverse_variance": proportional to 1/variance; `variances` is one variance
+      per asset or a covariance matrix, whose diagonal is used
+
+    Weights always sum to 1. Raises ValueError for an unknown method, a missing
+    or mis-sized input, or inputs that can't form weights (non-finite, negative,
+    zero variance, or a zero total).
+    """
+    if num_assets < 1:
+        raise ValueError("Cannot weight an empty portfolio")
+
+    if method == "equal":
+        return np.full(num_assets, 1 / num_assets)
+    if method == "market_cap":
+        if market_caps is None:
+            raise ValueError("market_cap weighting requires market caps")
+        raw = np.asarray(market_caps, dtype=float)
+    elif method == "inverse_variance":
+        if variances is None:
+            raise ValueError("inverse_variance weighting requires variances or a covariance matrix")
+        variances = np.asarray(variances, dtype=float)
+        if variances.ndim == 2:
+            if variances.shape[0] != variances.shape[1]:
+                raise ValueError("Covariance matrix must be square")
+            variances = np.diag(variances)
+        if np.any(variances <= 0):
+            raise ValueError("inverse_variance weighting requires positive variances")
+        raw = 1 / variances
+    else:
+        raise ValueError(f"Unknown weighting method: {method}")
+
+    if raw.shape != (num_assets,):
+        raise ValueError(f"{method} weighting expected {num_assets} values, got {raw.size}")
+    if not np.isfinite(raw).all() or np.any(raw < 0) or raw.sum() <= 0:
+        raise ValueError(f"{method} weighting inputs must be finite, non-negative and not all zero")
+    return raw / raw.sum()
+
+
+# ============================================================================
+# Regression Helpers
+# ============================================================================
+
+
+def ols_regression(y, x) -> dict:
+    """
+    Ordinary least squares of `y` on `x` (one column per regressor) with an intercept.
+
+    Returns alpha, the regressor betas, and r²; rows with any missing value are
+    dropped first. r² is 0 when `y` has no variance.
+    """
+    y = np.asarray(y, dtype=float)
+    x = np.asarray(x, dtype=float)
+    if x.ndim == 1:
+        x = x[:, None]
+
+    valid = ~np.isnan(y) & ~np.isnan(x).any(axis=1)
+    y, x = y[valid], x[valid]
+
+    design = np.column_stack([np.ones(len(y)), x])
+    coefficients, *_ = np.linalg.lstsq(design, y, rcond=None)
+    residuals = y - design @ coefficients
+
+    total_ss = float(np.sum((y - y.mean()) ** 2))
+    r_squared = 1 - float(np.sum(residuals**2)) / total_ss if total_ss > 0 else 0.0
+
+    return {
+        "alpha": float(coefficients[0]),
+        "betas": [float(beta) for beta in coefficients[1:]],
+        "r_squared": r_squared,
+        "num_observations": int(len(y)),
+    }
+
+
+def newey_west_regression(y, x, lags=None, names=None, max_collinearity: float = 0.999) -> dict:
+    """
+    Multivariate OLS of `y` on `x` with an intercept and Newey-West (HAC) standard errors.
+
+    `lags` defaults to floor(4 * (n / 100) ** (2 / 9)); Bartlett weights are used.
+    A regressor whose r² on the intercept and earlier kept regressors is at least
+    `max_collinearity` (or that is constant) is dropped before fitting and listed
+    in `collinear`, so the remaining loadings are stable. Returns alpha, per-regressor beta,
+    standard error and t-stat keyed by `names` (default column indexes), r²,
+    the lag count and the design's condition number. Rows with any missing
+    value are dropped first.
+    """
+    y = np.asarray(y, dtype=float)
+    x = np.asarray(x, dtype=float)
+    if x.ndim == 1:
+        x = x[:, None]
+    names = list(names) if names is not None else [str(i) for i in range(x.shape[1])]
+
+    valid = ~np.isnan(y) & ~np.isnan(x).any(axis=1)
+    y, x = y[valid], x[valid]
+    n = len(y)
+
+    # Keep regressors the design doesn't already (nearly) explain; drop the rest as collinear
+    kept, collinear = [], []
+    design = np.ones((n, 1))
+    for i, name in enumerate(names):
+        column = x[:, i]
+        fitted = design @ np.linalg.lstsq(design, column, rcond=None)[0]
+        column_ss = float(np.sum((column - column.mean()) ** 2))
+        explained = 1 - float(np.sum((column - fitted) ** 2)) / column_ss if column_ss > 0 else 1.0
+        if explained >= max_collinearity:
+            collinear.append(name)
+        else:
+            design = np.column_stack([design, column])
+            kept.append(name)
+    if n <= design.shape[1]:
+        raise ValueError(f"Need more than {design.shape[1]} observations, got {n}")
+
+    coefficients, *_ = np.linalg.lstsq(design, y, rcond=None)
+    residuals = y - design @ coefficients
+    total_ss = float(np.sum((y - y.mean()) ** 2))
+    r_squared = 1 - float(np.sum(residuals**2)) / total_ss if total_ss > 0 else 0.0
+
+    if lags is None:
+        lags = int(np.floor(4 * (n / 100) ** (2 / 9)))
+    scores = design * residuals[:, None]
+    meat = scores.T @ scores
+    for lag in range(1, lags + 1):
+        weight = 1 - lag / (lags + 1)
+        gamma = scores[lag:].T @ scores[:-lag]
+        meat += weight * (gamma + gamma.T)
+    bread = np.linalg.inv(design.T @ design)
+    covariance = bread @ meat @ bread
+    std_errors = np.sqrt(np.clip(np.diag(covariance), 0, None))
+
+    def t_stat(i):
+        return float(coefficients[i] / std_errors[i]) if std_errors[i] > 0 else None
+
+    return {
+        "alpha": float(coefficients[0]),
+        "alpha_t_stat": t_stat(0),
+        "loadings": {
+            name: {"beta": float(coefficients[i + 1]), "std_error": float(std_errors[i + 1]), "t_stat": t_stat(i + 1)}
+            for i, name in enumerate(kept)
+        },
+        "collinear": collinear,
+        "r_squared": r_squared,
+        "num_observations": int(n),
+        "newey_west_lags": int(lags),
+        "condition_number": float(np.linalg.cond(design)),
+    }
@@ -0,0 +1,72 @@
+"""
+Shared pytest fixtures. Run from the factors directory with: pytest
+"""
+
+import sys
+from pathlib import Path
+
+import pandas as pd
+import pytest
+
+# The service modules import each other as top-level modules (from stats import ...)
+sys.path.insert(0, str(Path(__file__).resolve().parent.parent))
+
+import api  # noqa: E402
+
+
+@pytest.fixture
+def logs_dir(tmp_path, monkeypatch):
+    """An empty FACTOR_LOGS_DIR for the test"""
+    monkeypatch.setattr(api, "FACTOR_LOGS_DIR", tmp_path)
+    return tmp_path
+
+
+@pytest.fixture
+def client(logs_dir):
+    """A TestClient for the app, writing logs to logs_dir; the lifespan isn't run"""
+    from fastapi.testclient import TestClient
+
+    return TestClient(api.app)
+
+
+@pytest.fixture
+def seed_run(logs_dir):
+    """
+    Log a run the way a compute does, returning its Logger: a log row with the
+    given results, then (if any) its per-period returns series, keyed by date.
+    """
+    from utils import FactorModel, Logger
+
+    def seed(factor: str, run_id: str, returns: dict | None = None, **results) -> Logger:
+        model = FactorModel(pd.DataFrame(), factor, breakpoint=0.3, min_assets=5, weighting_method="equal", run_id=run_id)
+        logger = Logger(logs_dir, model)
+        logger.log_results(results)
+        if returns is not None:
+            logger.save_time_series(returns)
+        return logger
+
+    return seed
+
+
+@pytest.fixture
+def no_benchmark(monkeypatch):
+    """Benchmark prices are unavailable, so benchmark metrics come back None instead of fetching"""
+    from fastapi import HTTPException
+
+    def unavailable(*args, **kwargs):
+        raise HTTPException(status_code=502, detail="no network in tests")
+
+    monkeypatch.setattr(api, "fetch_asset_returns", unavailable)
+
+
+@pytest.fixture
+def dispatched(monkeypatch):
+    """Stand in for dispatch_compute, recording each (factor, config, params) instead of running it"""
+    calls = []
+
+    async def dispatch(factor, config, **params):
+        calls.append((factor, config, params))
+        return {"factor": factor, "run_id": "test_run"}
+
+    monkeypatch.setattr(api, "dispatch_compute", dispatch)
+    return calls
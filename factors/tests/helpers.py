@@ -0,0 +1,46 @@
+"""Builders for synthetic test data"""
+
+import json
+from types import SimpleNamespace
+
+import pandas as pd
+
+
+def weekly_returns(values, start: str = "2024-01-07") -> dict:
+    """Per-period returns keyed by consecutive weekly dates"""
+    dates = pd.date_range(start, periods=len(values), freq="W")
+    return dict(zip(dates, map(float, values)))
+
+
+def daily_returns(values, start: str = "2024-01-01") -> dict:
+    """Per-period returns keyed by consecutive daily dates"""
+    dates = pd.date_range(start, periods=len(values), freq="D")
+    return dict(zip(dates, map(float, values)))
+
+
+def backdate(logs_dir, factor: str, run_id: str, created_at: str):
+    """Set a run's manifest creation time, as if it had been logged then"""
+    manifest_path = logs_dir / f"{factor}_index.json"
+    manifest = json.loads(manifest_path.read_text())
+    manifest["runs"][run_id]["created_at"] = created_at
+    manifest_path.write_text(json.dumps(manifest))
+
+
+def fake_factor_model(factor_returns: dict, long_returns: dict | None = None, short_returns: dict | None = None, **attrs):
+    """The FactorModel results summarize_performance reads, without running a backtest"""
+    return SimpleNamespace(
+        factor="smb",
+        run_id="test_run",
+        factor_returns=factor_returns,
+        gross_factor_returns=factor_returns,
+        long_portfolio_returns=long_returns or {},
+        short_portfolio_returns=short_returns or {},
+        period_ics={},
+        turnover={},
+        **attrs,
+    )
+
+
+def holdings(**weights) -> dict:
+    """A leg's {asset: {weighting, price_pct_change}} holdings, as FactorModel.factor_assets records them"""
+    return {asset: {"weighting": float(weight), "price_pct_change": 0.0} for asset, weight in weights.items()}
@@ -0,0 +1,70 @@
+import pytest
+
+import api
+
+TOKEN = "test-token"
+AUTHORIZED = {"Authorization": f"Bearer {TOKEN}"}
+UPLOAD = {"returns": [{"date": "2024-01-07", "value": 0.01}, {"date": "2024-01-14", "value": -0.02}]}
+
+
+@pytest.fixture(autouse=True)
+def auth_token(monkeypatch):
+    monkeypatch.setattr(api, "API_AUTH_TOKEN", TOKEN)
+
+
+@pytest.mark.parametrize(
+    "method, path",
+    [
+        ("post", "/compute/smb"),
+        ("post", "/factors/smb/runs/run1/reproduce"),
+        ("delete", "/factors/smb/logs"),
+        ("post", "/admin/purge?older_than_days=30"),
+        ("post", "/factors/smb/runs/run1/time-series"),
+    ],
+)
+def test_compute_and_destructive_routes_need_the_token(client, method, path):
+    response = client.request(method, path, json={})
+
+    assert response.status_code == 401
+    assert response.headers["WWW-Authenticate"] == "Bearer"
+
+
+def test_a_wrong_token_is_refused(client):
+    response = client.delete("/factors/smb/logs", headers={"Authorization": "Bearer not-the-token"})
+
+    assert response.status_code == 401
+
+
+def test_deleting_logs_with_the_token_is_allowed(client, seed_run):
+    seed_run("smb", "run1", sharpe_ratio=1.0)
+
+    response = client.delete("/factors/smb/logs", headers=AUTHORIZED)
+
+    assert response.status_code == 200
+
+
+def test_purging_with_the_token_is_allowed(client):
+    response = client.post("/admin/purge", params={"older_than_days": 30}, headers=AUTHORIZED)
+
+    assert response.status_code == 200
+
+
+def test_backfilling_a_series_with_the_token_is_allowed(client, seed_run):
+    seed_run("smb", "run1", sharpe_ratio=1.0)
+
+    response = client.post("/factors/smb/runs/run1/time-series", json=UPLOAD, headers=AUTHORIZED)
+
+    assert response.status_code == 201
+
+
+def test_read_routes_stay_open(client, seed_run):
+    seed_run("smb", "run1", sharpe_ratio=1.0)
+
+    assert client.get("/factors/smb/logs").status_code == 200
+    assert client.get("/health").status_code == 200
+
+
+def test_without_a_configured_token_every_route_is_open(client, monkeypatch):
+    monkeypatch.setattr(api, "API_AUTH_TOKEN", None)
+
+    assert client.post("/admin/purge", params={"older_than_days": 30}).status_code == 200
@@ -0,0 +1,79 @@
+import pytest
+
+# Uploaded out of order; the saved series is date-sorted
+UPLOAD = {
+    "returns": [
+        {"date": "2024-01-14", "value": -0.02},
+        {"date": "2024-01-07", "value": 0.01},
+        {"date": "2024-01-21", "value": 0.03},
+    ]
+}
+CUMULATIVE = [0.01, 1.01 * 0.98 - 1, 1.01 * 0.98 * 1.03 - 1]
+
+
+def backfill(client, run_id: str = "run1", upload: dict = UPLOAD):
+    return client.post(f"/factors/smb/runs/{run_id}/time-series", json=upload)
+
+
+@pytest.fixture
+def logged(seed_run):
+    """A logged run without a time series, as runs from before series were saved are"""
+    seed_run("smb", "run1", sharpe_ratio=1.0)
+
+
+@pytest.mark.usefixtures("logged")
+class TestBackfill:
+    def test_summarizes_the_saved_series(self, client):
+        response = backfill(client)
+
+        assert response.status_code == 201
+        assert response.json() == {
+            "factor": "smb",
+            "run_id": "run1",
+            "num_periods": 3,
+            "start_date": "2024-01-07",
+            "end_date": "2024-01-21",
+            "cumulative_return": pytest.approx(CUMULATIVE[-1]),
+        }
+
+    def test_the_backfilled_series_can_be_retrieved(self, client):
+        backfill(client)
+
+        response = client.get("/factors/smb/runs/run1/series")
+
+        assert response.status_code == 200
+        series = response.json()
+        assert series["run_id"] == "run1"
+        assert series["total"] == 3
+        assert series["dates"] == ["2024-01-07", "2024-01-14", "2024-01-21"]
+        assert series["returns"] == pytest.approx([0.01, -0.02, 0.03])
+        assert series["cumulative_returns"] == pytest.approx(CUMULATIVE)
+
+    def test_the_backfilled_run_is_served_as_the_latest_series(self, client):
+        backfill(client)
+
+        response = client.get("/factors/time-series", params={"factors": "smb"})
+
+        (series,) = response.json()["time_series"]
+        assert series["run_id"] == "run1"
+        assert series["returns"] == pytest.approx([0.01, -0.02, 0.03])
+
+    def test_a_run_with_a_series_is_a_409(self, client):
+        backfill(client)
+
+        response = backfill(client)
+
+        assert response.status_code == 409
+        assert client.get("/factors/smb/runs/run1/series").json()["total"] == 3
+
+    def test_an_unlogged_run_is_a_404(self, client):
+        assert backfill(client, run_id="run2").status_code == 404
+
+    def test_duplicate_dates_are_a_400(self, client):
+        upload = {"returns": [{"date": "2024-01-07", "value": 0.01}, {"date": "2024-01-07", "value": 0.02}]}
+
+        response = backfill(client, upload=upload)
+
+        assert response.status_code == 400
+        assert response.json()["detail"] == "Series contains duplicate dates"
+        assert client.get("/factors/smb/runs/run1/series").status_code == 404
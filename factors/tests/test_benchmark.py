@@ -0,0 +1,71 @@
+import pandas as pd
+import pytest
+
+import api
+import stats
+
+from .helpers import fake_factor_model, weekly_returns
+
+BENCHMARK = [0.02, -0.01, 0.03, -0.02, 0.01, 0.04]
+
+
+def config(**fields) -> api.FactorConfig:
+    return api.FactorConfig(factor="smb", start_date="2024-01-01", end_date="2024-06-30", **fields)
+
+
+@pytest.fixture
+def benchmark_returns(monkeypatch):
+    """The benchmark's returns over the run's weekly dates, recording the assets fetched"""
+    fetched = []
+
+    def fetch(asset, start_date, end_date, rebalance_frequency="weekly"):
+        fetched.append(asset)
+        return pd.Series(weekly_returns(BENCHMARK))
+
+    monkeypatch.setattr(api, "fetch_asset_returns", fetch)
+    return fetched
+
+
+def test_a_known_beta_and_alpha_are_recovered(benchmark_returns):
+    factor_returns = weekly_returns([0.001 + 0.5 * r for r in BENCHMARK])
+
+    metrics = api.benchmark_metrics(fake_factor_model(factor_returns), config(benchmark="ethereum"))
+
+    assert metrics["beta"] == pytest.approx(0.5)
+    assert metrics["alpha"] == pytest.approx(0.001)
+    assert benchmark_returns == ["ethereum"]
+
+
+def test_active_metrics_are_of_the_factor_minus_the_benchmark(benchmark_returns):
+    active = [0.01, -0.01, 0.03, 0.01, 0.0, 0.02]
+    factor_returns = weekly_returns([b + a for b, a in zip(BENCHMARK, active)])
+
+    metrics = api.benchmark_metrics(fake_factor_model(factor_returns), config())
+
+    assert metrics["tracking_error"] == pytest.approx(stats.tracking_error(active))
+    assert metrics["information_ratio"] == pytest.approx(stats.information_ratio(active))
+
+
+def test_too_few_shared_periods_give_none(benchmark_returns):
+    metrics = api.benchmark_metrics(fake_factor_model(weekly_returns([0.01, 0.02])), config())
+
+    assert metrics == {"alpha": None, "beta": None, "tracking_error": None, "information_ratio": None}
+
+
+@pytest.mark.usefixtures("no_benchmark")
+def test_unavailable_benchmark_prices_give_none():
+    metrics = api.benchmark_metrics(fake_factor_model(weekly_returns(BENCHMARK)), config())
+
+    assert metrics["alpha"] is None and metrics["beta"] is None
+
+
+def test_the_latest_run_shows_its_logged_benchmark_metrics(client, seed_run):
+    seed_run("smb", "old", sharpe_ratio=1.0)
+    assert client.get("/factors/smb/latest").json()["beta"] is None
+
+    seed_run("smb", "new", sharpe_ratio=1.0, alpha=0.001, beta=0.5, tracking_error=0.2, information_ratio=1.5)
+    latest = client.get("/factors/smb/latest").json()
+
+    assert latest["run_id"] == "new"
+    assert (latest["alpha"], latest["beta"]) == (0.001, 0.5)
+    assert (latest["tracking_error"], latest["information_ratio"]) == (0.2, 1.5)
@@ -0,0 +1,54 @@
+import pandas as pd
+import pytest
+
+import api
+
+from .helpers import daily_returns, weekly_returns
+
+ASSET = [0.02, -0.01, 0.03, -0.02, 0.01, 0.04, -0.03, 0.0, 0.015, -0.005, 0.025, -0.015]
+
+
+@pytest.fixture
+def asset_returns(monkeypatch):
+    """A synthetic asset's returns on whichever dates the run has, recording each fetch's frequency"""
+    fetched = []
+
+    def fetch(asset, start_date, end_date, rebalance_frequency="weekly"):
+        fetched.append(rebalance_frequency)
+        dates = daily_returns if rebalance_frequency == "daily" else weekly_returns
+        return pd.Series(dates(ASSET))
+
+    monkeypatch.setattr(api, "fetch_asset_returns", fetch)
+    return fetched
+
+
+def test_a_known_beta_and_alpha_are_recovered(client, seed_run, asset_returns):
+    seed_run("smb", "smb1", weekly_returns([0.002 + 1.5 * r for r in ASSET]))
+
+    response = client.get("/factors/smb/beta", params={"to": "bitcoin"})
+
+    assert response.status_code == 200
+    body = response.json()
+    assert body["beta"] == pytest.approx(1.5)
+    assert body["alpha"] == pytest.approx(0.002)
+    assert body["r_squared"] == pytest.approx(1.0)
+    assert body["num_observations"] == len(ASSET)
+
+
+def test_the_asset_is_fetched_at_the_runs_frequency(client, seed_run, asset_returns):
+    seed_run("smb", "smb_daily", daily_returns([-0.5 * r for r in ASSET]), rebalance_frequency="daily")
+
+    body = client.get("/factors/smb/beta", params={"to": "bitcoin"}).json()
+
+    assert asset_returns == ["daily"]
+    assert body["beta"] == pytest.approx(-0.5)
+    assert body["num_observations"] == len(ASSET)
+
+
+def test_too_few_overlapping_periods_is_a_400(client, seed_run, asset_returns):
+    seed_run("smb", "smb1", weekly_returns(ASSET[:4]))
+
+    response = client.get("/factors/smb/beta", params={"to": "bitcoin"})
+
+    assert response.status_code == 400
+    assert "overlapping periods" in response.json()["detail"]
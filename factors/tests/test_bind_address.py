@@ -0,0 +1,57 @@
+import os
+import subprocess
+import sys
+from pathlib import Path
+
+import pytest
+
+import api
+
+
+def imported_bind_address(**env) -> str:
+    """BIND_ADDRESS as a fresh interpreter importing api with `env` reads it"""
+    environment = {key: value for key, value in os.environ.items() if key != "BIND_ADDRESS"}
+    result = subprocess.run(
+        [sys.executable, "-c", "import api; print(api.BIND_ADDRESS)"],
+        cwd=Path(api.__file__).parent,
+        env={**environment, **env},
+        capture_output=True,
+        text=True,
+        check=True,
+    )
+    return result.stdout.strip().splitlines()[-1]
+
+
+def test_binds_every_interface_by_default():
+    assert imported_bind_address() == "0.0.0.0"
+
+
+def test_reads_the_bind_address_from_the_environment():
+    assert imported_bind_address(BIND_ADDRESS="127.0.0.1") == "127.0.0.1"
+
+
+@pytest.mark.parametrize(
+    "address, host",
+    [
+        ("0.0.0.0", "0.0.0.0"),
+        ("127.0.0.1", "127.0.0.1"),
+        (" 127.0.0.1 ", "127.0.0.1"),
+        ("::", "::"),
+        ("::1", "::1"),
+        ("[::1]", "::1"),
+        ("0:0:0:0:0:0:0:1", "::1"),
+        ("localhost", "localhost"),
+        ("api.internal", "api.internal"),
+    ],
+)
+def test_accepts_ip_addresses_and_hostnames(address, host):
+    assert api.resolve_bind_host(address) == host
+
+
+@pytest.mark.parametrize(
+    "address",
+    ["", "256.0.0.1", "127.0.0", "http://127.0.0.1", "127.0.0.1:8000", "-localhost", "api_internal", "[localhost"],
+)
+def test_rejects_anything_else(address):
+    with pytest.raises(ValueError, match="Invalid BIND_ADDRESS"):
+        api.resolve_bind_host(address)
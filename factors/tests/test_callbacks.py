@@ -0,0 +1,225 @@
+import asyncio
+import socket
+import threading
+from types import SimpleNamespace
+
+import pytest
+import requests
+from fastapi import HTTPException
+from pydantic import ValidationError
+
+import api
+
+
+def config(**fields) -> api.FactorConfig:
+    return api.FactorConfig(factor="smb", start_date="2024-01-01", end_date="2024-06-30", **fields)
+
+
+def answered(monkeypatch, status_code: int, **headers) -> list:
+    """Requests deliver_callback sends, as the transport sees them after address pinning, each answered status_code"""
+    calls = []
+
+    def send(adapter, request, **kwargs):
+        calls.append(request)
+        response = requests.Response()
+        response.status_code = status_code
+        response.headers.update(headers)
+        response._content = b""
+        response.request, response.url = request, request.url
+        return response
+
+    monkeypatch.setattr(requests.adapters.HTTPAdapter, "send", send)
+    return calls
+
+
+@pytest.fixture
+def posts(monkeypatch):
+    return answered(monkeypatch, 200)
+
+
+def resolves_to(monkeypatch, address: str):
+    def getaddrinfo(host, port, **kwargs):
+        return [(socket.AF_INET, socket.SOCK_STREAM, socket.IPPROTO_TCP, "", (address, port))]
+
+    monkeypatch.setattr(api.socket, "getaddrinfo", getaddrinfo)
+
+
+class TestCallbackUrlValidation:
+    @pytest.mark.parametrize(
+        "url",
+        [
+            "http://127.0.0.1/hook",
+            "http://10.0.0.5/hook",
+            "http://192.168.1.1:8080/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+            "http://0.0.0.0/hook",
+            "http://224.0.0.1/hook",
+        ],
+    )
+    def test_internal_addresses_are_rejected(self, url):
+        with pytest.raises(ValidationError):
+            config(callback_url=url)
+
+    @pytest.mark.parametrize("url", ["https://hooks.example.com/factor", "http://93.184.216.34/hook"])
+    def test_public_hosts_are_accepted(self, url):
+        assert config(callback_url=url).callback_url == url
+
+
+class TestDeliverCallback:
+    def test_a_hostname_resolving_to_an_internal_address_isnt_called(self, monkeypatch, posts):
+        resolves_to(monkeypatch, "169.254.169.254")
+
+        api.deliver_callback("https://metadata.example.com/hook", {"run_id": "r"})
+
+        assert posts == []
+
+    def test_public_hosts_are_posted_the_payload_at_the_checked_address(self, monkeypatch, posts):
+        resolves_to(monkeypatch, "93.184.216.34")
+
+        api.deliver_callback("https://hooks.example.com/hook?key=1", {"run_id": "r"})
+
+        (request,) = posts
+        assert request.url == "https://93.184.216.34/hook?key=1"
+        assert request.headers["Host"] == "hooks.example.com"
+        assert '"run_id": "r"' in request.body
+
+    def test_the_host_isnt_resolved_again_when_connecting(self, monkeypatch, posts):
+        lookups = []
+
+        def rebinding_getaddrinfo(host, port, **kwargs):
+            # Public when checked, internal for any later lookup
+            address = "93.184.216.34" if not lookups else "169.254.169.254"
+            lookups.append(host)
+            return [(socket.AF_INET, socket.SOCK_STREAM, socket.IPPROTO_TCP, "", (address, port))]
+
+        monkeypatch.setattr(socket, "getaddrinfo", rebinding_getaddrinfo)
+
+        api.deliver_callback("http://rebind.example.com:8080/hook", {"run_id": "r"})
+
+        assert lookups == ["rebind.example.com"]
+        (request,) = posts
+        assert request.url == "http://93.184.216.34:8080/hook"
+        assert request.headers["Host"] == "rebind.example.com:8080"
+
+    def test_ipv6_addresses_are_bracketed(self, monkeypatch, posts):
+        resolves_to(monkeypatch, "2606:2800:220:1:248:1893:25c8:1946")
+
+        api.deliver_callback("https://hooks.example.com/hook", {"run_id": "r"})
+
+        (request,) = posts
+        assert request.url == "https://[2606:2800:220:1:248:1893:25c8:1946]/hook"
+
+    def test_https_checks_the_certificate_against_the_hostname(self):
+        adapter = api.PinnedAddressAdapter("https://hooks.example.com/hook", "93.184.216.34")
+
+        assert adapter.poolmanager.connection_pool_kw["server_hostname"] == "hooks.example.com"
+        assert adapter.poolmanager.connection_pool_kw["assert_hostname"] == "hooks.example.com"
+
+    def test_redirects_arent_followed(self, monkeypatch):
+        resolves_to(monkeypatch, "93.184.216.34")
+        monkeypatch.setattr(api, "CALLBACK_MAX_ATTEMPTS", 1)
+        calls = answered(monkeypatch, 302, Location="http://169.254.169.254/latest/meta-data")
+
+        api.deliver_callback("https://hooks.example.com/hook", {"run_id": "r"})
+
+        assert [request.url for request in calls] == ["https://93.184.216.34/hook"]
+
+
+@pytest.fixture
+def deliveries(monkeypatch):
+    """(url, payload) pairs schedule_callback hands to deliver_callback"""
+    calls = []
+    monkeypatch.setattr(api, "deliver_callback", lambda url, payload: calls.append((url, payload)))
+    return calls
+
+
+async def finish(task: asyncio.Future):
+    try:
+        await task
+    except Exception:
+        pass
+    # Let the done callbacks run
+    await asyncio.sleep(0)
+
+
+class TestScheduleCallback:
+    def test_a_failed_run_calls_back_with_its_status_code_and_detail(self, deliveries):
+        async def failing():
+            raise HTTPException(status_code=400, detail="Empty asset universe: nothing mapped")
+
+        async def run():
+            task = asyncio.ensure_future(failing())
+            api.schedule_callback(task, config(callback_url="https://hooks.example.com/a"), "smb")
+            await finish(task)
+
+        asyncio.run(run())
+
+        assert deliveries == [
+            (
+                "https://hooks.example.com/a",
+                {
+                    "factor": "smb",
+                    "run_id": None,
+                    "status": "failed",
+                    "status_code": 400,
+                    "detail": "Empty asset universe: nothing mapped",
+                },
+            )
+        ]
+
+    def test_an_unexpected_error_calls_back_as_a_500(self, deliveries):
+        async def failing():
+            raise KeyError("mc")
+
+        async def run():
+            task = asyncio.ensure_future(failing())
+            api.schedule_callback(task, config(callback_url="https://hooks.example.com/a"), "smb")
+            await finish(task)
+
+        asyncio.run(run())
+
+        (_, payload), = deliveries
+        assert payload["status"] == "failed"
+        assert payload["status_code"] == 500
+
+    def test_without_a_callback_url_nothing_is_delivered(self, deliveries):
+        async def run():
+            task = asyncio.ensure_future(asyncio.sleep(0, result={"run_id": "r"}))
+            api.schedule_callback(task, config(), "smb")
+            await finish(task)
+
+        asyncio.run(run())
+
+        assert deliveries == []
+
+
+def test_every_caller_sharing_a_run_is_called_back_at_its_own_url(monkeypatch, deliveries):
+    release = threading.Event()
+    calls = []
+
+    def run_smb(config):
+        calls.append(config)
+        release.wait(timeout=5)
+        return {"run_id": "shared", "config": config.model_dump(), "data_quality": {}}
+
+    monkeypatch.setitem(api.FACTOR_REGISTRY, api.Factor.SMB, api.FactorDefinition(api.Factor.SMB, run_smb))
+    urls = ["https://hooks.example.com/a", "https://hooks.example.com/b"]
+
+    async def run():
+        tasks = [asyncio.create_task(api.dispatch_compute("smb", config(callback_url=url))) for url in urls]
+        await asyncio.sleep(0.1)
+        release.set()
+        results = await asyncio.gather(*tasks)
+        await asyncio.sleep(0)
+        return results
+
+    results = asyncio.run(run())
+
+    assert len(calls) == 1
+    assert [result["config"]["callback_url"] for result in results] == urls
+    assert sorted(url for url, _ in deliveries) == urls
+    for url, payload in deliveries:
+        assert payload["run_id"] == "shared"
+        assert payload["config"]["callback_url"] == url
@@ -0,0 +1,204 @@
+from types import SimpleNamespace
+
+import pandas as pd
+import pytest
+
+from utils import CoinbaseData, InvalidDateRangeError, YahooData, parse_date_range
+
+
+def hourly_candles(start: str, hours: int) -> pd.DataFrame:
+    """Hourly candles whose close is the hour's index and whose volume is 1 each"""
+    return pd.DataFrame(
+        {
+            "date": pd.date_range(start, periods=hours, freq="h"),
+            "open": [float(i) for i in range(hours)],
+            "high": [float(i) + 0.5 for i in range(hours)],
+            "low": [float(i) - 0.5 for i in range(hours)],
+            "close": [float(i) for i in range(hours)],
+            "volume": 1.0,
+        }
+    )
+
+
+@pytest.fixture
+def requested(monkeypatch):
+    """get_candles calls, each served 72 hourly candles from Jan 1 (three UTC days)"""
+    calls = []
+
+    def get_candles(self, product_id, start_date, end_date, granularity="ONE_DAY"):
+        calls.append((product_id, start_date, end_date, granularity))
+        return hourly_candles("2024-01-01", 72)
+
+    monkeypatch.setattr(CoinbaseData, "get_candles", get_candles)
+    return calls
+
+
+def test_hourly_candles_become_one_row_per_day(requested):
+    df, skipped = CoinbaseData().get_price_volume_for_symbols(
+        ["bitcoin"], "2024-01-01", "2024-01-02", granularity="ONE_HOUR"
+    )
+
+    assert skipped == []
+    assert df["date"].tolist() == list(pd.to_datetime(["2024-01-01", "2024-01-02"]))
+    # Each day's price is its last hour's close and its volume the sum of its 24 hours
+    assert df["price"].tolist() == [23.0, 47.0]
+    assert df["24h_volume"].tolist() == [24.0, 24.0]
+
+
+def test_sub_daily_candles_are_fetched_through_the_end_dates_last_hour(requested):
+    CoinbaseData().get_price_volume_for_symbols(["bitcoin"], "2024-01-01", "2024-01-02", granularity="SIX_HOUR")
+
+    assert requested == [("BTC-USD", "2024-01-01", "2024-01-03", "SIX_HOUR")]
+
+
+def test_daily_candles_are_used_as_fetched(requested):
+    df, _ = CoinbaseData().get_price_volume_for_symbols(["bitcoin"], "2024-01-01", "2024-01-02")
+
+    assert requested == [("BTC-USD", "2024-01-01", "2024-01-02", "ONE_DAY")]
+    assert len(df) == 72
+
+
+def test_daily_candles_aggregate_open_high_and_low():
+    daily = CoinbaseData.daily_candles(hourly_candles("2024-01-01", 48), "2024-01-01")
+
+    assert daily.to_dict("records") == [
+        {"date": pd.Timestamp("2024-01-01"), "open": 0.0, "high": 23.5, "low": -0.5, "close": 23.0, "volume": 24.0}
+    ]
+
+
+class UnreachableSession:
+    """A requests session standing in for the network, recording any request made"""
+
+    def __init__(self):
+        self.requests = []
+
+    def get(self, url, **kwargs):
+        self.requests.append(url)
+        raise AssertionError(f"unexpected request to {url}")
+
+
+class TestInvertedDateRange:
+    def test_parse_date_range_rejects_a_start_after_the_end(self):
+        assert parse_date_range("2024-01-01", "2024-01-01") == (pd.Timestamp("2024-01-01"),) * 2
+        with pytest.raises(InvalidDateRangeError):
+            parse_date_range("2024-02-01", "2024-01-01")
+
+    @pytest.mark.parametrize("source", [CoinbaseData, YahooData])
+    def test_get_candles_raises_rather_than_returning_empty(self, source):
+        client = source()
+        client._session = UnreachableSession()
+
+        with pytest.raises(InvalidDateRangeError):
+            client.get_candles("BTC-USD", "2024-02-01", "2024-01-01")
+
+        assert client._session.requests == []
+
+    @pytest.mark.parametrize("source, symbol", [(CoinbaseData, "bitcoin"), (YahooData, "AAPL")])
+    def test_get_price_volume_for_symbols_raises_before_any_request(self, source, symbol, monkeypatch):
+        candle_calls = []
+        monkeypatch.setattr(source, "get_candles", lambda self, *args, **kwargs: candle_calls.append(args))
+
+        with pytest.raises(InvalidDateRangeError):
+            source().get_price_volume_for_symbols([symbol], "2024-02-01", "2024-01-01")
+
+        assert candle_calls == []
+
+
+class CandleSession:
+    """A requests session serving fixed Coinbase candles to every request"""
+
+    def __init__(self, candles: list):
+        self.candles = candles
+
+    def get(self, url, **kwargs):
+        return SimpleNamespace(status_code=200, raise_for_status=lambda: None, json=lambda: {"candles": self.candles})
+
+
+JAN_1, JAN_2 = 1704067200, 1704153600  # 2024-01-01 and 2024-01-02 00:00 UTC in UNIX seconds
+
+
+def coinbase_candles(starts: list) -> list:
+    return [
+        {"start": start, "open": "1", "high": "2", "low": "0.5", "close": "1.5", "volume": "10"} for start in starts
+    ]
+
+
+class TestTimestampUnits:
+    @pytest.mark.parametrize(
+        "starts",
+        [
+            [str(JAN_1), str(JAN_2)],
+            [str(JAN_1 * 1000), str(JAN_2 * 1000)],
+            [JAN_1, JAN_2],
+            ["2024-01-01T00:00:00Z", "2024-01-02T00:00:00Z"],
+        ],
+        ids=["seconds", "milliseconds", "numeric_seconds", "rfc3339"],
+    )
+    def test_every_unit_parses_to_the_same_dates(self, starts):
+        parsed = CoinbaseData.parse_candle_timestamps(pd.Series(starts))
+
+        assert parsed.tolist() == list(pd.to_datetime(["2024-01-01", "2024-01-02"]))
+
+    @pytest.mark.parametrize("scale", [1, 1000], ids=["seconds", "milliseconds"])
+    def test_get_candles_dates_dont_depend_on_the_unit(self, scale, monkeypatch):
+        monkeypatch.setattr(CoinbaseData, "RATE_LIMIT_DELAY", 0)
+        client = CoinbaseData()
+        client._session = CandleSession(coinbase_candles([str(JAN_1 * scale), str(JAN_2 * scale)]))
+
+        df = client.get_candles("BTC-USD", "2024-01-01", "2024-01-02")
+
+        assert df["date"].tolist() == list(pd.to_datetime(["2024-01-01", "2024-01-02"]))
+        assert df["close"].tolist() == [1.5, 1.5]
+
+    def test_candles_dated_outside_the_requested_range_are_dropped(self, monkeypatch):
+        monkeypatch.setattr(CoinbaseData, "RATE_LIMIT_DELAY", 0)
+        client = CoinbaseData()
+        # A millisecond value read as seconds would be tens of thousands of years out; this one is a year off
+        client._session = CandleSession(coinbase_candles([str(JAN_1), str(JAN_1 + 366 * 86400)]))
+
+        df = client.get_candles("BTC-USD", "2024-01-01", "2024-01-02")
+
+        assert df["date"].tolist() == [pd.Timestamp("2024-01-01")]
+
+
+class ChartSession:
+    """A requests session serving a fixed Yahoo chart payload to every request"""
+
+    def __init__(self, payload: dict):
+        self.payload = payload
+
+    def get(self, url, **kwargs):
+        return SimpleNamespace(status_code=200, raise_for_status=lambda: None, json=lambda: self.payload)
+
+
+class TestEmptyYahooResults:
+    @pytest.mark.parametrize(
+        "payload",
+        [{}, {"chart": None}, {"chart": {"result": None}}, {"chart": {"result": []}}, {"chart": {"result": [{}]}}],
+    )
+    def test_parse_chart_without_timestamps_has_the_candle_columns(self, payload):
+        df = YahooData.parse_chart(payload)
+
+        assert df.empty
+        assert list(df.columns) == ["date", "close", "volume"]
+
+    def test_get_candles_without_any_days_has_the_candle_columns(self, monkeypatch):
+        monkeypatch.setattr(YahooData, "RATE_LIMIT_DELAY", 0)
+        client = YahooData()
+        client._session = ChartSession({"chart": {"result": [{"timestamp": []}]}})
+
+        df = client.get_candles("AAPL", "2024-01-01", "2024-01-31")
+
+        assert df.empty
+        assert list(df.columns) == ["date", "close", "volume"]
+
+    def test_no_prices_for_any_ticker_matches_coinbases_empty_frame(self, monkeypatch):
+        empty = pd.DataFrame(columns=["date", "close", "volume"])
+        monkeypatch.setattr(YahooData, "get_candles", lambda self, *args, **kwargs: empty)
+        monkeypatch.setattr(CoinbaseData, "get_candles", lambda self, *args, **kwargs: empty)
+
+        yahoo = YahooData().get_price_volume_for_symbols(["AAPL", "MSFT"], "2024-01-01", "2024-01-31")
+        coinbase, _ = CoinbaseData().get_price_volume_for_symbols(["bitcoin"], "2024-01-01", "2024-01-31")
+
+        assert yahoo.empty
+        assert list(yahoo.columns) == list(coinbase.columns) == ["date", "asset", "price", "24h_volume"]
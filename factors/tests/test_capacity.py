@@ -0,0 +1,76 @@
+import pandas as pd
+import pytest
+
+from .helpers import holdings, weekly_returns
+
+
+def traded(**positions) -> dict:
+    """A leg's holdings, each (weight, average daily dollar volume over the period)"""
+    legs = holdings(**{asset: weight for asset, (weight, _) in positions.items()})
+    for asset, (_, dollar_volume) in positions.items():
+        legs[asset]["daily_dollar_volume"] = dollar_volume
+    return legs
+
+
+# Max trades: asset_a and asset_b enter at 0.5, asset_c at 1.0; average volumes 2M, 4M and 2M
+COMPOSITIONS = {
+    pd.Timestamp("2024-01-07"): {
+        "long_portfolio": traded(asset_a=(0.5, 1e6), asset_b=(0.5, 4e6)),
+        "short_portfolio": traded(asset_c=(1.0, 2e6)),
+    },
+    pd.Timestamp("2024-01-14"): {
+        "long_portfolio": traded(asset_a=(0.25, 3e6), asset_b=(0.75, 4e6)),
+        "short_portfolio": traded(asset_c=(1.0, 2e6)),
+    },
+}
+
+
+@pytest.fixture
+def persisted(seed_run):
+    seed_run("smb", "run1", weekly_returns([0.01, 0.02])).save_compositions(COMPOSITIONS)
+
+
+def capacity(client, **params):
+    return client.get("/factors/smb/capacity", params=params)
+
+
+@pytest.mark.usefixtures("persisted")
+class TestCapacity:
+    def test_is_set_by_the_most_constrained_asset(self, client):
+        response = capacity(client)
+
+        assert response.status_code == 200
+        result = response.json()
+        assert result["run_id"] == "run1"
+        # 10% of asset_c's $2M a day, traded at its full weight
+        assert result["capacity"] == pytest.approx(2e5)
+        assert result["binding_asset"] == "asset_c"
+
+    def test_lists_each_assets_constraint_tightest_first(self, client):
+        constraints = capacity(client).json()["constraints"]
+
+        assert [(c["asset"], c["max_trade_weight"], c["avg_daily_dollar_volume"]) for c in constraints] == [
+            ("asset_c", 1.0, 2e6),
+            ("asset_a", 0.5, 2e6),
+            ("asset_b", 0.5, 4e6),
+        ]
+        assert [c["capacity"] for c in constraints] == pytest.approx([2e5, 4e5, 8e5])
+
+    def test_scales_with_the_participation_rate(self, client):
+        assert capacity(client, participation_rate=0.2).json()["capacity"] == pytest.approx(4e5)
+
+    def test_top_n_limits_the_constraints_listed(self, client):
+        result = capacity(client, top_n=1).json()
+
+        assert [c["asset"] for c in result["constraints"]] == ["asset_c"]
+        assert result["capacity"] == pytest.approx(2e5)
+
+
+def test_a_run_without_dollar_volumes_is_a_404(client, seed_run):
+    compositions = {pd.Timestamp("2024-01-07"): {"long_portfolio": holdings(asset_a=1.0), "short_portfolio": {}}}
+    seed_run("smb", "run1", weekly_returns([0.01])).save_compositions(compositions)
+
+    response = capacity(client)
+
+    assert response.status_code == 404
+    assert "no dollar volumes" in response.json()["detail"]
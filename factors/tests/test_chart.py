@@ -0,0 +1,29 @@
+import pytest
+
+import stats
+
+from .helpers import weekly_returns
+
+RETURNS = weekly_returns([0.01 * (i % 5 - 2) for i in range(30)])
+
+
+@pytest.mark.parametrize(
+    "frequency, window_periods, periods_per_year",
+    [("daily", 90, 365), ("weekly", 13, 52), ("monthly", 3, 12), (None, 13, 52)],
+)
+def test_rolling_windows_follow_the_runs_frequency(client, seed_run, frequency, window_periods, periods_per_year):
+    results = {"rebalance_frequency": frequency} if frequency else {}
+    seed_run("smb", "run1", RETURNS, **results)
+
+    response = client.get("/factors/smb/chart", params={"window": 90})
+
+    assert response.status_code == 200
+    chart = response.json()
+    returns = list(RETURNS.values())
+    assert chart["window_periods"] == window_periods
+    assert chart["rolling_sharpe"] == pytest.approx(
+        stats.rolling_sharpe(returns, window_periods, periods_per_year=periods_per_year)
+    )
+    assert chart["rolling_volatility"] == pytest.approx(
+        stats.rolling_volatility(returns, window_periods, periods_per_year=periods_per_year)
+    )
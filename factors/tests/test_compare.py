@@ -0,0 +1,72 @@
+import csv
+import io
+
+import pytest
+
+import api
+
+
+@pytest.fixture
+def logged(seed_run):
+    """Latest runs for three factors, momentum's logged before sortino_ratio existed"""
+    seed_run("smb", "smb1", sharpe_ratio=1.5, sortino_ratio=2.0)
+    seed_run("value", "value1", sharpe_ratio=0.5, sortino_ratio=-1.0)
+    seed_run("momentum", "momentum1", sharpe_ratio=1.0)
+
+
+def ranked(client, **params) -> list:
+    response = client.get("/factors/compare", params=params)
+    assert response.status_code == 200
+    return [row["factor"] for row in response.json()["comparison"]]
+
+
+@pytest.mark.usefixtures("logged")
+class TestSorting:
+    def test_sorts_by_the_metric(self, client):
+        assert ranked(client, sort_by="sharpe_ratio") == ["smb", "momentum", "value"]
+        assert ranked(client, sort_by="sharpe_ratio", order="asc") == ["value", "momentum", "smb"]
+
+    @pytest.mark.parametrize("order, expected", [("desc", ["smb", "value"]), ("asc", ["value", "smb"])])
+    def test_a_missing_metric_ranks_last_in_either_order(self, client, order, expected):
+        # value's negative Sortino ranks ahead of the missing one rather than below a 0
+        assert ranked(client, sort_by="sortino_ratio", order=order) == [*expected, "momentum"]
+
+    def test_an_unknown_metric_fails_validation(self, client):
+        response = client.get("/factors/compare", params={"sort_by": "alpha"})
+
+        assert response.status_code == 422
+
+
+def csv_rows(response) -> list:
+    return list(csv.reader(io.StringIO(response.text)))
+
+
+@pytest.mark.usefixtures("logged")
+class TestCsv:
+    def test_is_text_csv_with_a_header_row(self, client):
+        response = client.get("/factors/compare", params={"format": "csv"})
+
+        assert response.status_code == 200
+        assert response.headers["content-type"].startswith("text/csv")
+        assert csv_rows(response)[0] == ["factor", *api.COMPARE_METRICS]
+
+    def test_the_accept_header_selects_csv(self, client):
+        response = client.get("/factors/compare", headers={"Accept": "text/csv"})
+
+        assert response.headers["content-type"].startswith("text/csv")
+
+    def test_rows_follow_the_sort_with_missing_metrics_as_empty_cells(self, client):
+        response = client.get("/factors/compare", params={"format": "csv", "sort_by": "sortino_ratio"})
+
+        header, *rows = csv_rows(response)
+        sortino = header.index("sortino_ratio")
+        assert [row[0] for row in rows] == ["smb", "value", "momentum"]
+        assert [row[sortino] for row in rows] == ["2.0", "-1.0", ""]
+
+
+def test_csv_numbers_keep_full_precision(client, seed_run):
+    seed_run("smb", "smb1", sharpe_ratio=1 / 3)
+
+    header, row = csv_rows(client.get("/factors/compare", params={"format": "csv"}))
+
+    assert float(row[header.index("sharpe_ratio")]) == 1 / 3
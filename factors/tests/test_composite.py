@@ -0,0 +1,76 @@
+from types import SimpleNamespace
+
+import numpy as np
+import pandas as pd
+import pytest
+
+import api
+
+
+def test_equal_weighted_base_factors_rank_by_their_averaged_zscores():
+    signals = pd.DataFrame(
+        {
+            "date": pd.Timestamp("2024-01-07"),
+            "asset": ["a", "b", "c", "d"],
+            "momentum_signal": [1.0, 2.0, 3.0, 4.0],
+            "value_signal": [4.0, 1.0, 3.0, 2.0],
+        }
+    )
+    factor_model = SimpleNamespace(df=signals.copy())
+
+    signal_col = api.combine_composite_signal(
+        factor_model,
+        {api.Factor.MOMENTUM: "momentum_signal", api.Factor.VALUE: "value_signal"},
+        {"momentum": 1.0, "value": 1.0},
+        "require_all",
+    )
+
+    def zscore(values):
+        return (values - values.mean()) / values.std()
+
+    expected = (zscore(signals["momentum_signal"]) + zscore(signals["value_signal"])) / 2
+    composite = factor_model.df[signal_col]
+    np.testing.assert_allclose(composite, expected)
+    # Momentum alone ranks d first and value alone a; blended, c and d tie at the top
+    assert composite[1] < composite[0] < composite[2]
+    assert composite[2] == pytest.approx(composite[3])
+
+
+def test_composite_zscores_each_date_separately():
+    signals = pd.DataFrame(
+        {
+            "date": pd.to_datetime(["2024-01-07"] * 2 + ["2024-01-14"] * 2),
+            "asset": ["a", "b", "a", "b"],
+            "smb_signal": [1.0, 2.0, 100.0, 300.0],
+        }
+    )
+    factor_model = SimpleNamespace(df=signals)
+
+    signal_col = api.combine_composite_signal(factor_model, {api.Factor.SMB: "smb_signal"}, {"smb": 1.0}, "require_all")
+
+    np.testing.assert_allclose(factor_model.df[signal_col], [-1, 1, -1, 1] / np.sqrt(2))
+
+
+def test_value_and_growth_are_composite_bases():
+    assert {api.Factor.SMB, api.Factor.MOMENTUM, api.Factor.VALUE, api.Factor.GROWTH} <= set(api.COMPOSITE_BASE_FACTORS)
+
+
+def test_value_signal_is_market_cap_over_annualized_fees():
+    config = api.FactorConfig(factor="value", start_date="2024-01-01", end_date="2024-06-30")
+    factor_model = SimpleNamespace(
+        df=pd.DataFrame(
+            {
+                "date": pd.to_datetime(["2024-01-07", "2024-01-14", "2024-01-07", "2024-01-14"]),
+                "asset": ["a", "a", "b", "b"],
+                "mc": [5_200.0, 5_200.0, 1_000.0, 1_000.0],
+                "fees": [10.0, 10.0, 0.0, 0.0],
+            }
+        )
+    )
+    factor_model.get_t_minus_1_metrics = lambda metrics: None
+
+    api.add_value_signal(factor_model, config)
+
+    # 10 a week is 520 a year; without positive fees the ratio is undefined
+    assert factor_model.df["mc_fees_ratio"].tolist()[:2] == [10.0, 10.0]
+    assert factor_model.df["mc_fees_ratio"][2:].isna().all()
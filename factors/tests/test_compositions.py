@@ -0,0 +1,121 @@
+import pandas as pd
+import pytest
+
+from .helpers import holdings
+
+COMPOSITIONS = {
+    pd.Timestamp("2024-01-07"): {
+        "long_portfolio": holdings(bitcoin=0.5, ethereum=0.3, solana=0.2),
+        "short_portfolio": holdings(dogecoin=0.6, litecoin=0.4),
+    },
+    pd.Timestamp("2024-01-14"): {
+        "long_portfolio": holdings(bitcoin=0.4, ethereum=0.3, cardano=0.3),
+        "short_portfolio": holdings(dogecoin=0.6, litecoin=0.4),
+    },
+}
+
+
+@pytest.fixture
+def persisted(seed_run):
+    seed_run("smb", "run1").save_compositions(COMPOSITIONS)
+
+
+def diff(client, **params):
+    return client.get("/factors/smb/runs/run1/composition/diff", params=params)
+
+
+@pytest.mark.usefixtures("persisted")
+class TestCompositionDiff:
+    def test_lists_assets_added_removed_and_reweighted(self, client):
+        response = diff(client, **{"from": "2024-01-07", "to": "2024-01-14"})
+
+        assert response.status_code == 200
+        result = response.json()
+        assert (result["from"], result["to"]) == ("2024-01-07", "2024-01-14")
+        assert result["long_portfolio"]["added"] == ["cardano"]
+        assert result["long_portfolio"]["removed"] == ["solana"]
+        assert result["long_portfolio"]["weight_changes"] == {
+            "bitcoin": {"from": 0.5, "to": 0.4, "delta": pytest.approx(-0.1)}
+        }
+
+    def test_an_unchanged_leg_has_an_empty_diff(self, client):
+        result = diff(client, **{"from": "2024-01-07", "to": "2024-01-14"}).json()
+
+        assert result["short_portfolio"] == {"added": [], "removed": [], "weight_changes": {}}
+
+    def test_diffing_backwards_swaps_added_and_removed(self, client):
+        result = diff(client, **{"from": "2024-01-14", "to": "2024-01-07"}).json()
+
+        assert result["long_portfolio"]["added"] == ["solana"]
+        assert result["long_portfolio"]["removed"] == ["cardano"]
+        assert result["long_portfolio"]["weight_changes"]["bitcoin"]["delta"] == pytest.approx(0.1)
+
+    def test_a_date_without_a_rebalance_is_a_404(self, client):
+        response = diff(client, **{"from": "2024-01-07", "to": "2024-01-10"})
+
+        assert response.status_code == 404
+        assert "2024-01-10" in response.json()["detail"]
+
+
+def test_a_run_without_compositions_is_a_404(client, seed_run):
+    seed_run("smb", "run1")
+
+    assert diff(client, **{"from": "2024-01-07", "to": "2024-01-14"}).status_code == 404
+
+
+def long_only(*rebalances: list) -> dict:
+    """Equal-weighted long-only compositions on consecutive weekly dates, one list of held assets per rebalance"""
+    dates = pd.date_range("2024-01-07", periods=len(rebalances), freq="W")
+    return {
+        date: {"long_portfolio": holdings(**{asset: 1 / len(held) for asset in held}), "short_portfolio": {}}
+        for date, held in zip(dates, rebalances)
+    }
+
+
+class TestHoldingPeriods:
+    @pytest.fixture(autouse=True)
+    def persisted(self, seed_run):
+        # bitcoin: 3 rebalances then dropped; ethereum: 1, dropped, then back for 3 still open; solana: 2 open
+        seed_run("smb", "run1").save_compositions(
+            long_only(
+                ["bitcoin", "ethereum"],
+                ["bitcoin"],
+                ["bitcoin", "ethereum"],
+                ["ethereum", "solana"],
+                ["ethereum", "solana"],
+            )
+        )
+
+    def holding_periods(self, client, **params) -> dict:
+        response = client.get("/factors/smb/runs/run1/holding-periods", params=params)
+        assert response.status_code == 200
+        return response.json()
+
+    def test_counts_consecutive_rebalances_held_before_each_drop(self, client):
+        result = self.holding_periods(client)
+
+        assert result["num_rebalances"] == 5
+        assert result["long_portfolio"] == {
+            "histogram": {"1": 1, "3": 1},
+            "num_positions": 2,
+            "open_positions": 2,
+            "mean_holding_periods": 2.0,
+            "median_holding_periods": 2.0,
+        }
+
+    def test_include_open_counts_positions_held_at_the_last_rebalance(self, client):
+        long_leg = self.holding_periods(client, include_open=True)["long_portfolio"]
+
+        assert long_leg["histogram"] == {"1": 1, "2": 1, "3": 2}
+        assert long_leg["num_positions"] == 4
+        assert long_leg["mean_holding_periods"] == pytest.approx(2.25)
+        assert long_leg["median_holding_periods"] == pytest.approx(2.5)
+
+    def test_a_leg_never_held_has_an_empty_histogram(self, client):
+        assert self.holding_periods(client)["short_portfolio"] == {
+            "histogram": {},
+            "num_positions": 0,
+            "open_positions": 0,
+            "mean_holding_periods": None,
+            "median_holding_periods": None,
+        }
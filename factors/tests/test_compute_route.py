@@ -0,0 +1,88 @@
+import json
+
+import pytest
+from fastapi import HTTPException
+
+import api
+
+
+def body(factor: str) -> dict:
+    return {"factor": factor, "start_date": "2024-01-01", "end_date": "2024-06-30"}
+
+
+def test_an_unknown_factor_is_a_404(client, dispatched):
+    response = client.post("/compute/not_a_factor", json=body("not_a_factor"))
+
+    assert response.status_code == 404
+    assert dispatched == []
+
+
+def test_a_factor_without_a_named_route_is_dispatched_by_name(client, dispatched):
+    response = client.post("/compute/value", json=body("value"))
+
+    assert response.status_code == 200
+    assert dispatched == [(api.Factor.VALUE, api.FactorConfig(**body("value")), {})]
+
+
+def test_lookback_periods_for_a_factor_without_one_is_a_400(client, dispatched):
+    response = client.post("/compute/value", params={"lookback_periods": 4}, json=body("value"))
+
+    assert response.status_code == 400
+    assert dispatched == []
+
+
+@pytest.mark.parametrize("factor", [factor for factor in api.FACTOR_REGISTRY if factor != api.Factor.COMPOSITE])
+def test_every_registered_factor_resolves_by_name(factor):
+    definition = api.get_factor_definition(factor.value)
+
+    assert definition.factor is factor
+    assert api.generic_compute_params(definition, None) == (
+        {} if definition.lookback_periods is None else {"lookback_periods": definition.lookback_periods}
+    )
+
+
+def test_a_given_lookback_overrides_the_factors_default():
+    definition = api.get_factor_definition("momentum_v2")
+
+    assert api.generic_compute_params(definition, 6) == {"lookback_periods": 6}
+
+
+def test_composite_needs_its_own_route():
+    with pytest.raises(HTTPException) as error:
+        api.generic_compute_params(api.get_factor_definition("composite"), None)
+
+    assert error.value.status_code == 400
+
+
+def request_by_name(client, route: str, factor: str, params: dict):
+    """Call one of the routes computing a factor by name: the POST, its stream or its sweep"""
+    if route == "stream":
+        return client.get(f"/compute/{factor}/stream", params={**params, "config": json.dumps(body(factor))})
+    if route == "sweep":
+        sweep = {"config": body(factor), "breakpoints": [0.3]}
+        return client.post(f"/compute/{factor}/sweep", params=params, json=sweep)
+    return client.post(f"/compute/{factor}", params=params, json=body(factor))
+
+
+def generic_rejection(factor: str, lookback_periods=None) -> str:
+    with pytest.raises(HTTPException) as error:
+        api.generic_compute_params(api.get_factor_definition(factor), lookback_periods)
+    return error.value.detail
+
+
+@pytest.mark.parametrize("route", ["compute", "stream", "sweep"])
+def test_every_by_name_route_rejects_an_unused_lookback_alike(client, dispatched, route):
+    response = request_by_name(client, route, "value", {"lookback_periods": 4})
+
+    assert response.status_code == 400
+    assert response.json()["detail"] == generic_rejection("value", 4)
+    assert dispatched == []
+
+
+# /compute/composite is the composite factor's own route, so only the stream and sweep take it by name
+@pytest.mark.parametrize("route", ["stream", "sweep"])
+def test_the_stream_and_sweep_routes_reject_composite_alike(client, route):
+    response = request_by_name(client, route, "composite", {})
+
+    assert response.status_code == 400
+    assert response.json()["detail"] == generic_rejection("composite")
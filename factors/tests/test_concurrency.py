@@ -0,0 +1,49 @@
+import threading
+import time
+
+import pytest
+
+import utils
+from utils import ApiData
+
+
+@pytest.mark.parametrize("value", ["0", "-2", "four", ""])
+def test_an_invalid_max_concurrent_requests_is_a_config_error(monkeypatch, value):
+    monkeypatch.setenv("ARTEMIS_MAX_CONCURRENT_REQUESTS", value)
+
+    with pytest.raises(RuntimeError, match="ARTEMIS_MAX_CONCURRENT_REQUESTS must be a whole number of at least 1"):
+        utils.positive_int_setting("ARTEMIS_MAX_CONCURRENT_REQUESTS", 4)
+
+
+def test_max_concurrent_requests_defaults_when_unset(monkeypatch):
+    monkeypatch.delenv("ARTEMIS_MAX_CONCURRENT_REQUESTS", raising=False)
+
+    assert utils.positive_int_setting("ARTEMIS_MAX_CONCURRENT_REQUESTS", 4) == 4
+
+
+def test_a_limit_of_one_serializes_requests():
+    api_data = ApiData("key", max_concurrent_requests=1)
+    lock = threading.Lock()
+    in_flight = {"now": 0, "most": 0}
+
+    def request():
+        with lock:
+            in_flight["now"] += 1
+            in_flight["most"] = max(in_flight["most"], in_flight["now"])
+        time.sleep(0.02)
+        with lock:
+            in_flight["now"] -= 1
+
+    threads = [threading.Thread(target=api_data._with_retry, args=("test", request)) for _ in range(4)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join(5)
+
+    assert in_flight["most"] == 1
+
+
+def test_clients_with_the_same_limit_share_its_slots():
+    first, second = ApiData("key", max_concurrent_requests=1), ApiData("other", max_concurrent_requests=1)
+
+    assert first.request_slots is second.request_slots
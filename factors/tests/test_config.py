@@ -0,0 +1,142 @@
+import pandas as pd
+import pytest
+from pydantic import ValidationError
+
+import api
+
+BODY = {"factor": "smb", "start_date": "2024-01-01", "end_date": "2024-06-30"}
+
+
+@pytest.fixture(autouse=True)
+def no_auth_token(monkeypatch):
+    monkeypatch.setattr(api, "API_AUTH_TOKEN", None)
+
+
+class TestUnknownFields:
+    def test_a_typod_field_is_a_400_naming_it(self, client, dispatched):
+        response = client.post("/compute/smb", json={**BODY, "breakpont": 0.3})
+
+        assert response.status_code == 400
+        assert response.json()["detail"] == "Unknown field(s) in request body: breakpont"
+        assert dispatched == []
+
+    def test_every_unknown_field_is_named(self, client, dispatched):
+        response = client.post("/compute/smb", json={**BODY, "breakpont": 0.3, "min_asset": 10})
+
+        assert response.status_code == 400
+        assert "breakpont" in response.json()["detail"] and "min_asset" in response.json()["detail"]
+
+    def test_a_body_without_unknown_fields_is_computed(self, client, dispatched):
+        response = client.post("/compute/smb", json={**BODY, "breakpoint": 0.3})
+
+        assert response.status_code == 200
+        ((_, config, _),) = dispatched
+        assert config.breakpoint == 0.3
+
+    def test_every_existing_field_still_deserializes(self):
+        saved = api.FactorConfig(**BODY).model_dump()
+
+        assert api.FactorConfig.model_validate(saved).model_dump() == saved
+
+    def test_other_validation_errors_keep_the_422(self, client, dispatched):
+        response = client.post("/compute/smb", json={**BODY, "breakpoint": 0.9})
+
+        assert response.status_code == 422
+
+
+def config(**fields) -> api.FactorConfig:
+    return api.FactorConfig(**{**BODY, **fields})
+
+
+class TestDateRange:
+    def test_a_reversed_range_is_rejected(self):
+        with pytest.raises(ValidationError, match="start_date 2024-06-30 must be before end_date 2024-01-01"):
+            config(start_date="2024-06-30", end_date="2024-01-01")
+
+    def test_an_empty_range_is_rejected(self):
+        with pytest.raises(ValidationError, match="must be before end_date"):
+            config(start_date="2024-01-01", end_date="2024-01-01")
+
+    def test_a_range_shorter_than_one_rebalance_is_rejected(self):
+        with pytest.raises(ValidationError, match="is 5 days, shorter than the 7 days needed for one weekly rebalance"):
+            config(start_date="2024-01-01", end_date="2024-01-06")
+
+    def test_one_rebalance_is_enough(self):
+        assert config(start_date="2024-01-01", end_date="2024-01-08").end_date == "2024-01-08"
+
+    def test_a_daily_run_needs_a_single_day(self):
+        assert config(start_date="2024-01-01", end_date="2024-01-02", rebalance_frequency="daily")
+
+    def test_momentum_needs_its_lookback_before_the_first_rebalance(self):
+        with pytest.raises(ValidationError, match="shorter than the 97 days needed for one weekly rebalance after"):
+            config(factor="momentum", start_date="2024-01-01", end_date="2024-03-01", lookback_days=90)
+
+        assert config(factor="momentum", start_date="2024-01-01", end_date="2024-04-07", lookback_days=90)
+
+    def test_a_future_end_date_is_rejected(self):
+        tomorrow = (pd.Timestamp.now(tz="UTC").normalize() + pd.Timedelta(days=1)).strftime("%Y-%m-%d")
+
+        with pytest.raises(ValidationError, match="is in the future"):
+            config(end_date=tomorrow)
+
+    @pytest.mark.parametrize(
+        "dates", [{"start_date": "2024-06-30", "end_date": "2024-01-01"}, {"end_date": "2024-01-03"}]
+    )
+    def test_a_reversed_or_too_short_range_is_a_422(self, client, dispatched, dates):
+        response = client.post("/compute/smb", json={**BODY, **dates})
+
+        assert response.status_code == 422
+        assert dispatched == []
+
+
+def utc_today() -> pd.Timestamp:
+    return pd.Timestamp.now(tz="UTC").tz_localize(None).normalize()
+
+
+class TestRelativeDates:
+    def test_minus_2y_resolves_to_two_years_before_today(self):
+        today = utc_today()
+        resolved = api.FactorConfig(factor="smb", start_date="-2y")
+
+        assert resolved.start_date == (today - pd.DateOffset(years=2)).strftime("%Y-%m-%d")
+        assert resolved.end_date == today.strftime("%Y-%m-%d")
+
+    @pytest.mark.parametrize(
+        "start_date, offset",
+        [
+            ("-6m", pd.DateOffset(months=6)),
+            ("-4w", pd.DateOffset(weeks=4)),
+            ("-30d", pd.DateOffset(days=30)),
+            ("-2Y", pd.DateOffset(years=2)),
+        ],
+    )
+    def test_every_unit_counts_back_from_today(self, start_date, offset):
+        expected = (utc_today() - offset).strftime("%Y-%m-%d")
+
+        assert api.FactorConfig(factor="smb", start_date=start_date).start_date == expected
+
+    def test_a_relative_end_date_is_counted_from_today_too(self):
+        today = utc_today()
+        resolved = api.FactorConfig(factor="smb", start_date="-2y", end_date="-1y")
+
+        assert resolved.start_date == (today - pd.DateOffset(years=2)).strftime("%Y-%m-%d")
+        assert resolved.end_date == (today - pd.DateOffset(years=1)).strftime("%Y-%m-%d")
+
+    def test_lookback_period_counts_back_from_the_end_date(self):
+        assert config(start_date=None, lookback_period="2y").start_date == "2022-06-30"
+
+    def test_calendar_arithmetic_clamps_to_the_end_of_the_month(self):
+        assert api.resolve_relative_date("-2y", pd.Timestamp("2024-02-29")) == pd.Timestamp("2022-02-28")
+        assert api.resolve_relative_date("-1m", pd.Timestamp("2024-03-31")) == pd.Timestamp("2024-02-29")
+
+    def test_concrete_dates_are_kept(self):
+        assert (config().start_date, config().end_date) == ("2024-01-01", "2024-06-30")
+
+    @pytest.mark.parametrize("start_date", ["-2x", "-y", "two years ago"])
+    def test_an_unparseable_date_is_rejected(self, start_date):
+        with pytest.raises(ValidationError, match="Invalid"):
+            config(start_date=start_date)
+
+    def test_start_date_and_lookback_period_together_are_rejected(self):
+        with pytest.raises(ValidationError, match="either start_date or lookback_period"):
+            config(lookback_period="1y")
@@ -0,0 +1,70 @@
+import io
+
+import pandas as pd
+import pytest
+
+from .helpers import weekly_returns
+
+RETURNS = weekly_returns([0.01, -0.02, 0.03, 0.015])
+
+ENDPOINTS = {
+    "logs": ("/factors/smb/logs", {}),
+    "time_series": ("/factors/time-series", {"factors": "smb"}),
+}
+
+
+@pytest.fixture(autouse=True)
+def logged(seed_run):
+    seed_run("smb", "run1", RETURNS, sharpe_ratio=1.2, start_date="2024-01-07", end_date="2024-01-28")
+
+
+def get(client, endpoint: str, accept: str | None):
+    path, params = ENDPOINTS[endpoint]
+    response = client.get(path, params=params, headers={"Accept": accept} if accept else {})
+    assert response.status_code == 200
+    return response
+
+
+def parsed_rows(endpoint: str, table: pd.DataFrame) -> list:
+    """The run ids of a logs table, or the dates of a long-format time series"""
+    if endpoint == "logs":
+        return table["run_id"].astype(str).tolist()
+    return pd.to_datetime(table["date"]).dt.strftime("%Y-%m-%d").tolist()
+
+
+EXPECTED_ROWS = {"logs": ["run1"], "time_series": ["2024-01-07", "2024-01-14", "2024-01-21", "2024-01-28"]}
+
+
+@pytest.mark.parametrize("endpoint", ENDPOINTS)
+class TestAcceptFormats:
+    def test_csv(self, client, endpoint):
+        response = get(client, endpoint, "text/csv")
+
+        assert response.headers["content-type"].startswith("text/csv")
+        assert parsed_rows(endpoint, pd.read_csv(io.StringIO(response.text))) == EXPECTED_ROWS[endpoint]
+
+    @pytest.mark.parametrize("accept", ["application/vnd.apache.parquet", "application/octet-stream"])
+    def test_parquet(self, client, endpoint, accept):
+        response = get(client, endpoint, accept)
+
+        assert response.headers["content-type"] == accept
+        assert response.content[:4] == b"PAR1"
+        assert parsed_rows(endpoint, pd.read_parquet(io.BytesIO(response.content))) == EXPECTED_ROWS[endpoint]
+
+    @pytest.mark.parametrize("accept", [None, "application/json", "application/xml", "*/*"])
+    def test_json_is_the_fallback(self, client, endpoint, accept):
+        response = get(client, endpoint, accept)
+
+        assert response.headers["content-type"] == "application/json"
+        body = response.json()
+        if endpoint == "logs":
+            assert [run["run_id"] for run in body] == EXPECTED_ROWS["logs"]
+        else:
+            assert body["time_series"][0]["dates"] == EXPECTED_ROWS["time_series"]
+
+    def test_csv_and_parquet_carry_the_same_table(self, client, endpoint):
+        csv = pd.read_csv(io.StringIO(get(client, endpoint, "text/csv").text))
+        parquet = pd.read_parquet(io.BytesIO(get(client, endpoint, "application/vnd.apache.parquet").content))
+
+        assert list(csv.columns) == list(parquet.columns)
+        assert len(csv) == len(parquet)
@@ -0,0 +1,87 @@
+import pandas as pd
+import pytest
+
+from .helpers import weekly_returns
+
+RETURNS = weekly_returns([0.01 * ((i * 3) % 7 - 3) for i in range(20)])
+NOISE = [0.002 * ((i * 5) % 3 - 1) for i in range(20)]
+
+
+def points(values: dict, shift_days: int = 0) -> list:
+    return [
+        {"date": (date + pd.Timedelta(days=shift_days)).strftime("%Y-%m-%d"), "value": value}
+        for date, value in values.items()
+    ]
+
+
+def linear_series(slope: float, intercept: float = 0.0) -> dict:
+    """An external series that's an exact linear function of the factor's returns"""
+    return {date: intercept + slope * value for date, value in RETURNS.items()}
+
+
+@pytest.fixture(autouse=True)
+def logged(seed_run):
+    seed_run("smb", "run1", RETURNS)
+
+
+def correlate(client, series: list, **body):
+    return client.post("/factors/smb/correlate", json={"series": series, **body})
+
+
+class TestCorrelate:
+    def test_a_linear_series_is_perfectly_correlated_and_regressed(self, client):
+        response = correlate(client, points(linear_series(2.0, intercept=0.5)))
+
+        assert response.status_code == 200
+        result = response.json()
+        assert result["run_id"] == "run1"
+        assert result["correlation"] == pytest.approx(1.0)
+        # return = -0.25 + 0.5 * value
+        assert result["beta"] == pytest.approx(0.5)
+        assert result["alpha"] == pytest.approx(-0.25)
+        assert result["r_squared"] == pytest.approx(1.0)
+        assert result["num_observations"] == 20
+        assert (result["start_date"], result["end_date"]) == ("2024-01-07", "2024-05-19")
+
+    def test_an_inverse_series_is_negatively_correlated(self, client):
+        result = correlate(client, points(linear_series(-3.0))).json()
+
+        assert result["correlation"] == pytest.approx(-1.0)
+        assert result["beta"] == pytest.approx(-1 / 3)
+
+    def test_a_noisy_series_is_correlated_less_than_perfectly(self, client):
+        noisy = {date: value + noise for (date, value), noise in zip(RETURNS.items(), NOISE)}
+        expected = pd.Series(list(RETURNS.values())).corr(pd.Series(list(noisy.values())))
+
+        result = correlate(client, points(noisy)).json()
+
+        assert result["correlation"] == pytest.approx(expected)
+        assert 0.5 < result["correlation"] < 1.0
+
+    def test_asof_alignment_uses_observations_from_earlier_in_the_week(self, client):
+        # Observed each Friday, two days before each Sunday rebalance
+        friday_series = points(linear_series(2.0), shift_days=-2)
+
+        assert correlate(client, friday_series).json()["correlation"] == pytest.approx(1.0)
+
+    def test_exact_alignment_needs_the_same_dates(self, client):
+        response = correlate(client, points(linear_series(2.0), shift_days=-2), alignment="exact")
+
+        assert response.status_code == 400
+        assert "Only 0 factor dates align" in response.json()["detail"]
+
+    @pytest.mark.parametrize("observed, aligned", [("2023-12-31", 1), ("2023-12-30", 0)])
+    def test_observations_more_than_a_week_old_dont_align(self, client, observed, aligned):
+        # The first rebalance is 2024-01-07: a week after the 31st, eight days after the 30th
+        response = correlate(client, [{"date": observed, "value": 1.0}])
+
+        assert response.json()["detail"].startswith(f"Only {aligned} factor dates align")
+
+    def test_a_too_short_overlap_is_a_400(self, client):
+        short = points(dict(list(linear_series(2.0).items())[:5]))
+
+        response = correlate(client, short)
+
+        assert response.status_code == 400
+        assert response.json()["detail"] == "Only 5 factor dates align with the series, need at least 10"
+        assert correlate(client, short, min_observations=5).status_code == 200
@@ -0,0 +1,77 @@
+import logging
+
+import numpy as np
+import pandas as pd
+import pytest
+
+import api
+from utils import FactorModel
+
+# Weekly returns of one asset; the first week has no prior price
+RETURNS = [np.nan, 0.1, -0.1, 0.2, 0.0, 0.1, -0.05]
+
+
+class PreResampled(FactorModel):
+    """A FactorModel whose data is already on the weekly grid, so resampling keeps it as is"""
+
+    def resample(self, freq: str, metric_agg_methods: dict):
+        pass
+
+
+def weekly_panel() -> pd.DataFrame:
+    prices = 100 * np.cumprod([1.0, *[1 + r for r in RETURNS[1:]]])
+    return pd.DataFrame(
+        {
+            "date": pd.date_range("2024-01-07", periods=len(prices), freq="W"),
+            "asset": "asset0",
+            "price": prices,
+            "mc": 1e9,
+        }
+    )
+
+
+def prepared_variance(**config) -> list:
+    factor_model = PreResampled(weekly_panel(), "smb", weighting_method="inverse_variance")
+    api.prepare_period_data(
+        factor_model, api.FactorConfig(factor="smb", start_date="2024-01-01", end_date="2024-06-30", **config)
+    )
+    return factor_model.df["variance"].tolist()
+
+
+def test_the_variance_is_taken_over_the_trailing_covariance_window():
+    variance = prepared_variance(covariance_lookback_days=28)
+
+    # Four prior weeks are needed: the first full window is weeks 1-4, ahead of week 5
+    assert np.isnan(variance[:5]).all()
+    assert variance[5:] == pytest.approx([0.05 / 3, 0.05 / 3])
+
+
+def test_the_covariance_window_is_independent_of_the_signal_window():
+    short_signal = prepared_variance(covariance_lookback_days=14, lookback_days=14)
+    long_signal = prepared_variance(covariance_lookback_days=14, lookback_days=365)
+
+    assert short_signal == pytest.approx(long_signal, nan_ok=True)
+    assert short_signal[3] == pytest.approx(0.02)
+
+
+def test_without_a_covariance_window_the_full_history_is_used():
+    variance = prepared_variance()
+
+    assert variance == pytest.approx([pd.Series(RETURNS).var()] * len(RETURNS))
+
+
+def test_holdings_without_a_full_window_fall_back_to_an_equal_share(caplog):
+    factor_model = FactorModel(pd.DataFrame(), "smb", weighting_method="inverse_variance")
+    holdings = pd.DataFrame({"variance": [1.0, 3.0, np.nan, np.nan]})
+
+    weights = factor_model.leg_weights(holdings)
+
+    # The two fallbacks take 1/4 each; the others split the remaining half 3:1
+    assert weights.tolist() == pytest.approx([0.375, 0.125, 0.25, 0.25])
+    assert factor_model.covariance_fallback_assets == 2
+
+    data_quality = {}
+    with caplog.at_level(logging.WARNING):
+        api.record_portfolio_quality(factor_model, data_quality)
+    assert data_quality["covariance_fallback_assets"] == 2
+    assert "equal-weighted" in caplog.text
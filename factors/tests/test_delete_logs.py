@@ -0,0 +1,76 @@
+import pytest
+
+import api
+
+from .helpers import weekly_returns
+
+RETURNS = weekly_returns([0.01, -0.02, 0.03])
+
+
+@pytest.fixture(autouse=True)
+def no_auth_token(monkeypatch):
+    monkeypatch.setattr(api, "API_AUTH_TOKEN", None)
+
+
+@pytest.fixture
+def logged(seed_run):
+    """Two smb runs with time series, and a momentum_v2 run whose files also start with "momentum" """
+    seed_run("smb", "run1", RETURNS, sharpe_ratio=1.0)
+    seed_run("smb", "run2", RETURNS, sharpe_ratio=0.5)
+    seed_run("momentum_v2", "run3", RETURNS, sharpe_ratio=2.0)
+
+
+def log_files(logs_dir) -> set:
+    return {path.name for path in logs_dir.iterdir() if not path.name.startswith(".")}
+
+
+@pytest.mark.usefixtures("logged")
+class TestDeleteFactorLogs:
+    def test_removes_the_factors_files_and_counts_them(self, client, logs_dir):
+        smb_files = {name for name in log_files(logs_dir) if name.startswith("smb")}
+
+        response = client.delete("/factors/smb/logs")
+
+        assert response.status_code == 200
+        body = response.json()
+        assert body["files_deleted"] == len(smb_files) == len(body["files"])
+        assert set(body["files"]) == smb_files
+        assert {"smb.csv", "smb_index.json", "smb_run1_returns.csv", "smb_run2_returns.csv"} <= smb_files
+        assert not any(name.startswith("smb") for name in log_files(logs_dir))
+
+    def test_leaves_other_factors_alone(self, client, logs_dir):
+        before = {name for name in log_files(logs_dir) if name.startswith("momentum_v2")}
+
+        client.delete("/factors/momentum/logs")
+
+        assert {name for name in log_files(logs_dir) if name.startswith("momentum_v2")} == before
+        assert client.get("/factors/momentum_v2/logs").status_code == 200
+
+    def test_the_runs_are_gone_afterwards(self, client):
+        client.delete("/factors/smb/logs")
+
+        assert client.get("/factors/smb/logs").status_code == 404
+
+    def test_a_second_delete_finds_nothing(self, client):
+        client.delete("/factors/smb/logs")
+
+        response = client.delete("/factors/smb/logs")
+
+        assert response.status_code == 200
+        assert response.json()["files_deleted"] == 0
+
+    def test_an_unknown_factor_is_a_404_and_deletes_nothing(self, client, logs_dir):
+        before = log_files(logs_dir)
+
+        response = client.delete("/factors/not_a_factor/logs")
+
+        assert response.status_code == 404
+        assert log_files(logs_dir) == before
+
+
+def test_requires_the_token_when_one_is_set(client, seed_run, logs_dir, monkeypatch):
+    seed_run("smb", "run1", RETURNS, sharpe_ratio=1.0)
+    monkeypatch.setattr(api, "API_AUTH_TOKEN", "test-token")
+
+    assert client.delete("/factors/smb/logs").status_code == 401
+    assert "smb.csv" in log_files(logs_dir)
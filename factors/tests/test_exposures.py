@@ -0,0 +1,69 @@
+import pytest
+
+from .helpers import daily_returns, weekly_returns
+
+SMB = [0.01 * ((i * 3) % 7 - 3) for i in range(30)]
+MOMENTUM = [0.004 * ((i * 5) % 11 - 5) for i in range(30)]
+# A known linear combination of the two factors
+TARGET = [0.001 + 0.6 * s - 0.3 * m for s, m in zip(SMB, MOMENTUM)]
+
+
+def points(returns: dict) -> list:
+    return [{"date": date.strftime("%Y-%m-%d"), "value": value} for date, value in returns.items()]
+
+
+@pytest.fixture
+def weekly_runs(seed_run):
+    """Weekly smb and momentum runs, and a daily momentum run logged after them"""
+    seed_run("smb", "smb_weekly", weekly_returns(SMB), rebalance_frequency="weekly")
+    seed_run("momentum", "momentum_weekly", weekly_returns(MOMENTUM), rebalance_frequency="weekly")
+    seed_run("momentum", "momentum_daily", daily_returns(MOMENTUM[::-1]), rebalance_frequency="daily")
+
+
+def exposures(client, **body):
+    return client.post("/factors/exposures", json={"series": points(weekly_returns(TARGET)), **body})
+
+
+@pytest.mark.usefixtures("weekly_runs")
+class TestExposures:
+    def test_recovers_the_loadings_of_a_known_linear_combination(self, client):
+        response = exposures(client, factors=["smb", "momentum"], newey_west_lags=0)
+
+        assert response.status_code == 200
+        body = response.json()
+        assert body["loadings"]["smb"]["beta"] == pytest.approx(0.6)
+        assert body["loadings"]["momentum"]["beta"] == pytest.approx(-0.3)
+        assert body["alpha"] == pytest.approx(0.001)
+        assert body["r_squared"] == pytest.approx(1.0)
+
+    def test_uses_each_factors_latest_run_at_the_targets_frequency(self, client):
+        body = exposures(client, factors=["smb", "momentum"]).json()
+
+        assert body["rebalance_frequency"] == "weekly"
+        assert body["factor_run_ids"] == {"smb": "smb_weekly", "momentum": "momentum_weekly"}
+        assert body["num_observations"] == len(TARGET)
+
+    def test_a_target_factor_sets_the_frequency(self, client, seed_run):
+        seed_run("growth", "growth_daily", daily_returns(TARGET), rebalance_frequency="daily")
+
+        body = client.post("/factors/exposures", json={"target_factor": "growth", "factors": ["momentum"]}).json()
+
+        assert body["rebalance_frequency"] == "daily"
+        assert body["factor_run_ids"] == {"momentum": "momentum_daily"}
+
+    def test_unrequested_factors_without_a_run_at_the_frequency_are_skipped(self, client):
+        daily_target = {"series": points(daily_returns(TARGET)), "rebalance_frequency": "daily"}
+
+        body = client.post("/factors/exposures", json=daily_target).json()
+
+        assert body["factor_run_ids"] == {"momentum": "momentum_daily"}
+        assert "smb" in body["skipped_factors"]
+
+    def test_a_requested_factor_only_at_another_frequency_is_a_400(self, client):
+        response = exposures(client, factors=["smb", "momentum"], rebalance_frequency="daily")
+
+        assert response.status_code == 400
+        assert "no daily run" in response.json()["detail"]
+
+    def test_a_requested_factor_without_any_series_is_a_404(self, client):
+        assert exposures(client, factors=["value"]).status_code == 404
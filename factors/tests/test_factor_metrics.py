@@ -0,0 +1,81 @@
+from types import SimpleNamespace
+
+import pytest
+
+import api
+
+# The metric dependencies the factor docs give for each factor
+DOCUMENTED_METRICS = {
+    "smb": ["mc"],
+    "market": ["mc"],
+    "value": ["mc", "fees"],
+    "momentum": ["mc"],
+    "momentum_v2": ["mc"],
+    "growth": ["mc", "fees", "dau", "revenue"],
+    "composite": ["mc"],
+    "equity": [],
+}
+
+
+def metrics(client, factor: str, **params):
+    return client.get(f"/factors/{factor}/metrics", params=params)
+
+
+def test_every_factor_is_documented():
+    assert set(DOCUMENTED_METRICS) == {factor.value for factor in api.Factor}
+
+
+@pytest.mark.parametrize("factor, expected", DOCUMENTED_METRICS.items())
+def test_each_factor_reports_its_documented_metrics(client, factor, expected):
+    response = metrics(client, factor)
+
+    assert response.status_code == 200
+    body = response.json()
+    assert [metric["metric"] for metric in body["artemis_metrics"]] == expected
+    assert all(metric["source"] == "artemis" and metric["available"] is None for metric in body["artemis_metrics"])
+    assert body["compute_endpoint"] == f"/compute/{factor}"
+
+
+@pytest.mark.parametrize("factor, source", [("smb", "coinbase"), ("growth", "coinbase"), ("equity", "yahoo")])
+def test_price_fields_name_their_source(client, factor, source):
+    assert metrics(client, factor).json()["price_fields"] == [
+        {"field": "price", "source": source},
+        {"field": "24h_volume", "source": source},
+    ]
+
+
+def test_a_symbol_checks_each_metrics_availability(client, monkeypatch):
+    calls = []
+
+    def supported(symbol):
+        calls.append(symbol)
+        return ["mc", "fees", "dau", "price"]
+
+    monkeypatch.setattr(api, "get_api_data", lambda: SimpleNamespace(get_all_metrics_for_symbol=supported))
+
+    body = metrics(client, "growth", symbol="solana").json()
+
+    assert calls == ["solana"]
+    assert body["symbol"] == "solana"
+    assert {metric["metric"]: metric["available"] for metric in body["artemis_metrics"]} == {
+        "mc": True,
+        "fees": True,
+        "dau": True,
+        "revenue": False,
+    }
+
+
+def test_an_artemis_failure_checking_a_symbol_is_a_502(client, monkeypatch):
+    def unavailable(symbol):
+        raise ConnectionError("timed out")
+
+    monkeypatch.setattr(api, "get_api_data", lambda: SimpleNamespace(get_all_metrics_for_symbol=unavailable))
+
+    response = metrics(client, "smb", symbol="solana")
+
+    assert response.status_code == 502
+    assert "solana" in response.json()["detail"]
+
+
+def test_an_unknown_factor_is_a_404(client):
+    assert metrics(client, "not_a_factor").status_code == 404
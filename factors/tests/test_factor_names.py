@@ -0,0 +1,33 @@
+import pytest
+from fastapi import HTTPException
+
+import api
+
+
+@pytest.mark.parametrize("factor", list(api.Factor))
+def test_names_round_trip_through_parse_and_display(factor):
+    assert api.Factor.parse(str(factor)) is factor
+    assert f"{factor}" == factor.value
+
+
+def test_unknown_names_are_a_404():
+    with pytest.raises(HTTPException) as error:
+        api.Factor.parse("not_a_factor")
+
+    assert error.value.status_code == 404
+
+
+def test_names_are_case_sensitive():
+    with pytest.raises(HTTPException):
+        api.Factor.parse("SMB")
+
+
+def test_routes_reject_unknown_factors(client):
+    response = client.get("/factors/not_a_factor/logs")
+
+    assert response.status_code == 404
+    assert "not_a_factor" in response.json()["detail"]
+
+
+def test_the_root_lists_every_factor(client):
+    assert client.get("/").json()["available_factors"] == [factor.value for factor in api.Factor]
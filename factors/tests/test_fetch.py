@@ -0,0 +1,54 @@
+import pytest
+from fastapi import HTTPException
+
+import api
+import utils
+
+
+class UnmappedArtemis:
+    """An Artemis client whose universe has no Coinbase mappings; fetching any metric fails the test"""
+
+    def __init__(self, api_key=None):
+        pass
+
+    def with_cache(self, cache=None):
+        return self
+
+    def list_crypto_symbols(self):
+        return ["not-a-listed-asset", "another-unlisted-asset"]
+
+    def get_metric_for_all_symbols(self, *args, **kwargs):
+        pytest.fail("metrics were fetched for an empty universe")
+
+
+def never_built(*args, **kwargs):
+    pytest.fail("Coinbase was called for an empty universe")
+
+
+@pytest.fixture
+def empty_universe(monkeypatch):
+    monkeypatch.setattr(utils, "ApiData", UnmappedArtemis)
+    monkeypatch.setattr(utils, "CoinbaseData", never_built)
+
+
+@pytest.mark.usefixtures("empty_universe")
+class TestEmptyUniverse:
+    def test_fails_before_any_metric_or_price_fetch(self):
+        with pytest.raises(utils.EmptyUniverseError):
+            utils.fetch_merged_crypto_data(
+                start_date="2024-01-01", end_date="2024-06-30", artemis_metrics=["mc"], api_key="key"
+            )
+
+    def test_an_unmapped_allowlist_fails_the_same_way(self):
+        with pytest.raises(utils.EmptyUniverseError):
+            utils.fetch_merged_crypto_data(
+                start_date="2024-01-01", end_date="2024-06-30", api_key="key", universe=["not-a-listed-asset"]
+            )
+
+    def test_compute_requests_get_a_400(self):
+        config = api.FactorConfig(factor="smb", start_date="2024-01-01", end_date="2024-06-30")
+
+        with pytest.raises(HTTPException) as error:
+            api.fetch_factor_data(config, ["mc"], {})
+
+        assert error.value.status_code == 400
@@ -0,0 +1,41 @@
+import numpy as np
+import pandas as pd
+
+from utils import FactorModel
+
+
+def signal_panel(coverage: dict, periods: int = 6) -> pd.DataFrame:
+    """Weekly rows per asset, with a signal on the period indexes each asset covers"""
+    dates = pd.date_range("2024-01-07", periods=periods, freq="W")
+    return pd.DataFrame(
+        [
+            {"date": date, "asset": asset, "signal": 1.0 if i in covered else np.nan}
+            for asset, covered in coverage.items()
+            for i, date in enumerate(dates)
+        ]
+    )
+
+
+def test_minimum_valid_periods_excludes_intermittently_covered_asset():
+    model = FactorModel(signal_panel({"steady": range(6), "flicker": {0, 3}}), "momentum")
+
+    model.minimum_valid_periods("signal", 3)
+
+    assert set(model.df["asset"]) == {"steady"}
+
+
+def test_minimum_valid_periods_excludes_the_asset_from_every_date():
+    model = FactorModel(signal_panel({"steady": range(6), "flicker": {1, 2}}), "momentum")
+
+    model.minimum_valid_periods("signal", 3)
+
+    # flicker's covered dates go too, not just its NaN ones
+    assert (model.df["asset"] == "steady").sum() == 6
+
+
+def test_minimum_valid_periods_keeps_asset_at_the_boundary():
+    model = FactorModel(signal_panel({"steady": range(6), "late": {3, 4, 5}}), "momentum")
+
+    model.minimum_valid_periods("signal", 3)
+
+    assert set(model.df["asset"]) == {"steady", "late"}
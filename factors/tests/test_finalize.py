@@ -0,0 +1,84 @@
+import numpy as np
+import pandas as pd
+import pytest
+
+import api
+
+from .test_transaction_costs import config, two_rebalances
+
+
+@pytest.fixture
+def finalized(logs_dir, no_benchmark):
+    """An SMB run finalized with a route parameter and a factor-specific log column"""
+    return api.finalize_factor_run(
+        api.Factor.SMB,
+        config(),
+        two_rebalances(0),
+        {},
+        config_extra={"lookback_periods": 3},
+        log_extra={"top_n": 5},
+    )
+
+
+def test_the_result_echoes_the_config_with_its_extras(finalized):
+    assert finalized["factor"] == api.Factor.SMB
+    assert finalized["config"]["lookback_periods"] == 3
+    assert finalized["config"]["start_date"] == "2024-01-01"
+    assert "start_date" not in finalized["performance"]
+    assert finalized["data_quality"]["nan_signal_assets"] == 0
+
+
+def test_the_log_row_has_the_shared_metrics_and_the_extras(finalized):
+    row = api.load_factor_logs("smb").iloc[-1]
+
+    assert row["run_id"] == finalized["run_id"]
+    assert row["top_n"] == 5
+    assert row["cumulative_returns"] == pytest.approx(finalized["performance"]["cumulative_returns"])
+    for column in ("long_only_returns", "short_only_returns", "rebalance_frequency", "transaction_cost_bps"):
+        assert column in row.index
+
+
+def test_the_run_is_saved_for_the_run_routes(finalized, logs_dir):
+    run_id = finalized["run_id"]
+
+    assert api.time_series_path("smb", run_id) is not None
+    assert (logs_dir / f"smb_{run_id}_compositions.json").exists()
+    assert api.load_run_config("smb", run_id)[0]["lookback_periods"] == 3
+
+
+def test_a_run_without_returns_fails_before_logging(logs_dir):
+    from utils import FactorModel
+
+    empty = FactorModel(pd.DataFrame(), "smb", breakpoint=0.5, min_assets=4, weighting_method="equal")
+
+    with pytest.raises(api.HTTPException) as error:
+        api.finalize_factor_run(api.Factor.SMB, config(), empty, {})
+
+    assert error.value.status_code == 400
+    assert not (logs_dir / "smb.csv").exists()
+
+
+BENCHMARK = {"alpha": 0.001, "beta": 0.5, "tracking_error": 0.2, "information_ratio": 1.5}
+
+
+def logged_performance() -> api.FactorPerformance:
+    return api.performance_from_log_row(api.load_factor_logs("smb").iloc[-1].replace({np.nan: None}), "smb")
+
+
+def test_the_run_logs_its_alpha_and_beta(logs_dir, monkeypatch):
+    monkeypatch.setattr(api, "benchmark_metrics", lambda *_: BENCHMARK)
+
+    result = api.finalize_factor_run(api.Factor.SMB, config(), two_rebalances(0), {})
+    performance = logged_performance()
+
+    assert (result["performance"]["alpha"], result["performance"]["beta"]) == (0.001, 0.5)
+    assert (performance.alpha, performance.beta) == (0.001, 0.5)
+
+
+def test_the_run_logs_its_tracking_error_and_information_ratio(logs_dir, monkeypatch):
+    monkeypatch.setattr(api, "benchmark_metrics", lambda *_: BENCHMARK)
+
+    api.finalize_factor_run(api.Factor.SMB, config(), two_rebalances(0), {})
+    performance = logged_performance()
+
+    assert (performance.tracking_error, performance.information_ratio) == (0.2, 1.5)
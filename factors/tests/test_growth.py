@@ -0,0 +1,173 @@
+import numpy as np
+import pandas as pd
+import pytest
+from fastapi import HTTPException
+
+import api
+import stats
+from utils import FactorModel, winsorize_series
+
+
+def config(factor: str = "growth", **fields) -> api.FactorConfig:
+    return api.FactorConfig(factor=factor, start_date="2024-01-01", end_date="2024-06-30", **fields)
+
+
+def fees_model(fees: list, mc: list) -> FactorModel:
+    df = pd.DataFrame(
+        {
+            "date": pd.date_range("2024-01-07", periods=len(fees), freq="7D"),
+            "asset": "a",
+            "fees": fees,
+            "mc": mc,
+        }
+    )
+    return FactorModel(df, "growth")
+
+
+class TestNormalizeByMarketCap:
+    def test_raw_growth_measures_the_metric_itself(self):
+        # Fees double while market cap quadruples
+        factor_model = fees_model([10.0, 20.0, 20.0], [100.0, 400.0, 400.0])
+
+        api.add_growth_rates(factor_model, config(growth_weights={"fees": 1.0}), lookback_periods=1)
+
+        assert factor_model.df["fees_pct_change_t_minus_1"].iloc[2] == pytest.approx(1.0)
+
+    def test_normalized_growth_measures_the_metric_per_dollar_of_market_cap(self):
+        factor_model = fees_model([10.0, 20.0, 20.0], [100.0, 400.0, 400.0])
+
+        api.add_growth_rates(
+            factor_model, config(growth_weights={"fees": 1.0}, normalize_by_mc=True), lookback_periods=1
+        )
+
+        # Fees per dollar of market cap halve, 0.1 -> 0.05
+        assert factor_model.df["fees_pct_change_t_minus_1"].iloc[2] == pytest.approx(-0.5)
+
+    def test_rows_without_a_positive_market_cap_are_dropped(self):
+        factor_model = fees_model([10.0, 20.0, 30.0, 40.0], [100.0, 0.0, np.nan, 200.0])
+
+        factor_model.normalize_by_market_cap(["fees"])
+
+        assert factor_model.df["fees"].tolist() == pytest.approx([0.1, 0.2])
+
+
+class TestNormalizeByMarketCapSupport:
+    @pytest.mark.parametrize("factor", ["smb", "value", "momentum"])
+    def test_factors_that_ignore_it_reject_it(self, factor):
+        with pytest.raises(HTTPException) as error:
+            api.ensure_endpoint_factor(config(factor, normalize_by_mc=True), api.Factor(factor))
+
+        assert error.value.status_code == 400
+
+    def test_growth_accepts_it(self):
+        api.ensure_endpoint_factor(config(normalize_by_mc=True), api.Factor.GROWTH)
+
+    def test_a_composite_without_growth_rejects_it(self):
+        with pytest.raises(HTTPException) as error:
+            api.run_composite_factor(config("composite", normalize_by_mc=True), {"smb": 1.0}, lookback_periods=2)
+
+        assert error.value.status_code == 400
+        assert "growth" in error.value.detail
+
+
+class TestWinsorizeSeries:
+    def test_clamps_known_outliers_to_the_percentiles(self):
+        series = pd.Series([-500.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 1000.0])
+
+        winsorized = winsorize_series(series, 0.1, 0.1)
+
+        assert (winsorized.iloc[0], winsorized.iloc[-1]) == (2.0, 10.0)
+        assert winsorized.iloc[1:-1].tolist() == series.iloc[1:-1].tolist()
+
+    def test_half_percentiles_clamp_to_the_median(self):
+        # Callers have always been able to pass 0.5, collapsing the series to its median
+        winsorized = winsorize_series(pd.Series([1.0, 2.0, 3.0, 100.0, 5.0]), 0.5, 0.5)
+
+        assert winsorized.tolist() == [3.0] * 5
+
+
+class TestPartialMetricPolicy:
+    # full has every component, partial lacks dau, empty has none
+    ZSCORES = pd.DataFrame(
+        {"fees": [1.0, 2.0, np.nan], "dau": [0.5, np.nan, np.nan], "revenue": [-0.5, 1.0, np.nan]},
+        index=["full", "partial", "empty"],
+    )
+    WEIGHTS = {"fees": 0.5, "dau": 0.25, "revenue": 0.25}
+
+    def combined(self, policy: str) -> dict:
+        return stats.combine_zscores(self.ZSCORES, self.WEIGHTS, policy).to_dict()
+
+    def test_require_all_drops_partial_assets(self):
+        combined = self.combined("require_all")
+
+        assert combined["full"] == pytest.approx(0.5 * 1.0 + 0.25 * 0.5 - 0.25 * 0.5)
+        assert np.isnan(combined["partial"]) and np.isnan(combined["empty"])
+
+    def test_average_available_renormalizes_over_the_components_present(self):
+        combined = self.combined("average_available")
+
+        assert combined["full"] == pytest.approx(0.5)
+        assert combined["partial"] == pytest.approx((0.5 * 2.0 + 0.25 * 1.0) / 0.75)
+        assert np.isnan(combined["empty"])
+
+    def test_impute_mean_counts_missing_components_as_zero(self):
+        combined = self.combined("impute_mean")
+
+        assert combined["full"] == pytest.approx(0.5)
+        assert combined["partial"] == pytest.approx(0.5 * 2.0 + 0.25 * 1.0)
+        assert np.isnan(combined["empty"])
+
+    def test_an_unknown_policy_is_an_error(self):
+        with pytest.raises(ValueError, match="Unknown partial metric policy"):
+            self.combined("drop_some")
+
+
+class TestGrowthSignalPolicies:
+    """combine_growth_signal on one date's universe: four assets with every growth rate, one without dau"""
+
+    def universe(self) -> FactorModel:
+        df = pd.DataFrame(
+            {
+                "date": pd.Timestamp("2024-01-07"),
+                "asset": ["a", "b", "c", "d", "partial"],
+                "fees_pct_change_t_minus_1": [0.1, 0.2, 0.3, 0.4, 0.5],
+                "dau_pct_change_t_minus_1": [0.4, 0.1, 0.3, 0.2, np.nan],
+                "revenue_pct_change_t_minus_1": [0.0, 0.1, 0.2, 0.3, 0.4],
+            }
+        )
+        return FactorModel(df, "growth")
+
+    def signals(self, policy: str) -> tuple[FactorModel, pd.Series]:
+        factor_model = self.universe()
+        signal_col = api.combine_growth_signal(factor_model, config(), policy)
+        return factor_model, factor_model.df.set_index("asset")[signal_col]
+
+    def present_zscores(self, factor_model: FactorModel) -> tuple[float, float]:
+        """The partial asset's fees and revenue growth z-scores"""
+        partial = factor_model.df.set_index("asset").loc["partial"]
+        return partial["fees_growth_zscore"], partial["revenue_growth_zscore"]
+
+    def test_require_all_leaves_the_partial_asset_unrankable(self):
+        _, signal = self.signals("require_all")
+
+        assert np.isnan(signal["partial"])
+        assert signal.drop("partial").notna().all()
+
+    def test_average_available_scores_the_partial_asset_on_fees_and_revenue(self):
+        factor_model, signal = self.signals("average_available")
+
+        fees, revenue = self.present_zscores(factor_model)
+        assert signal["partial"] == pytest.approx((fees + revenue) / 2)
+
+    def test_impute_mean_scores_the_missing_dau_as_average(self):
+        factor_model, signal = self.signals("impute_mean")
+
+        fees, revenue = self.present_zscores(factor_model)
+        assert signal["partial"] == pytest.approx((fees + revenue) / 3)
+
+    @pytest.mark.parametrize("policy", ["require_all", "average_available", "impute_mean"])
+    def test_full_assets_score_the_same_under_every_policy(self, policy):
+        _, signal = self.signals(policy)
+        _, reference = self.signals("require_all")
+
+        assert signal.drop("partial").tolist() == pytest.approx(reference.drop("partial").tolist())
@@ -0,0 +1,47 @@
+import pytest
+
+import stats
+
+from .helpers import daily_returns, weekly_returns
+
+VALUES = [0.01 * (i % 7 - 3) + 0.002 * (i % 3) for i in range(40)]
+MARKET_VALUES = [0.01 * (i % 5 - 2) for i in range(40)]
+
+
+@pytest.fixture
+def daily_run(seed_run):
+    """A daily smb run, a daily market run and a weekly market run logged after it"""
+    seed_run("smb", "smb_daily", daily_returns(VALUES), rebalance_frequency="daily")
+    seed_run("market", "market_daily", daily_returns(MARKET_VALUES), rebalance_frequency="daily")
+    seed_run("market", "market_weekly", weekly_returns(MARKET_VALUES), rebalance_frequency="weekly")
+
+
+@pytest.mark.usefixtures("daily_run")
+class TestHedgingFrequency:
+    def test_defaults_to_the_latest_market_run_at_the_same_frequency(self, client):
+        response = client.get("/factors/smb/hedged", params={"window": 14})
+
+        assert response.status_code == 200
+        assert response.json()["market_run_id"] == "market_daily"
+
+    def test_the_window_and_sharpe_ratios_follow_the_runs_frequency(self, client):
+        hedged = client.get("/factors/smb/hedged", params={"window": 14}).json()
+
+        assert hedged["window_periods"] == 14
+        assert hedged["num_hedged_periods"] == len(VALUES) - 14
+        assert hedged["sharpe_ratio"] == pytest.approx(stats.sharpe_ratio(VALUES[14:], periods_per_year=365))
+        hedged_returns = [r for r in hedged["hedged_returns"] if r is not None]
+        assert hedged["hedged_sharpe_ratio"] == pytest.approx(stats.sharpe_ratio(hedged_returns, periods_per_year=365))
+
+    def test_a_market_run_at_another_frequency_is_rejected(self, client):
+        response = client.get("/factors/smb/hedged", params={"window": 14, "market_run_id": "market_weekly"})
+
+        assert response.status_code == 400
+        assert "weekly" in response.json()["detail"]
+
+    def test_no_market_run_at_the_runs_frequency_is_a_404(self, client, seed_run):
+        seed_run("smb", "smb_monthly", weekly_returns(VALUES), rebalance_frequency="monthly")
+
+        response = client.get("/factors/smb/hedged", params={"window": 90, "run_id": "smb_monthly"})
+
+        assert response.status_code == 404
@@ -0,0 +1,152 @@
+import threading
+
+import pandas as pd
+import pytest
+
+import api
+from utils import FactorModel, Logger
+
+from .helpers import weekly_returns
+
+
+def run_logger(logs_dir, run_id: str, factor: str = "smb") -> Logger:
+    model = FactorModel(pd.DataFrame(), factor, breakpoint=0.3, min_assets=5, weighting_method="equal", run_id=run_id)
+    return Logger(logs_dir, model)
+
+
+def log_run(logs_dir, run_id: str):
+    logger = run_logger(logs_dir, run_id)
+    logger.log_results({"sharpe_ratio": 1.0})
+    logger.save_time_series(weekly_returns([0.01, -0.02, 0.03]))
+    logger.save_config({"factor": "smb", "run_id": run_id}, 1)
+
+
+def test_two_runs_both_land_in_the_manifest(logs_dir):
+    log_run(logs_dir, "run1")
+    log_run(logs_dir, "run2")
+
+    runs = run_logger(logs_dir, "run3").load_manifest()["runs"]
+
+    assert set(runs) == {"run1", "run2"}
+    assert runs["run1"]["files"] == {"returns": "smb_run1_returns.csv"}
+    assert runs["run2"]["config"] == {"factor": "smb", "run_id": "run2"}
+
+
+def test_concurrent_runs_dont_lose_each_others_entries(logs_dir):
+    run_ids = [f"run{i}" for i in range(8)]
+    errors = []
+
+    def log(run_id):
+        try:
+            log_run(logs_dir, run_id)
+        except Exception as e:
+            errors.append(e)
+
+    threads = [threading.Thread(target=log, args=(run_id,)) for run_id in run_ids]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+
+    assert errors == []
+    assert set(run_logger(logs_dir, "other").load_manifest()["runs"]) == set(run_ids)
+    assert sorted(pd.read_csv(logs_dir / "smb.csv")["run_id"]) == run_ids
+    assert list(logs_dir.glob("*.tmp")) == []
+
+
+def test_a_duplicate_run_id_is_refused_before_its_row_is_appended(logs_dir):
+    log_run(logs_dir, "run1")
+
+    with pytest.raises(FileExistsError):
+        run_logger(logs_dir, "run1").log_results({"sharpe_ratio": 2.0})
+
+    assert pd.read_csv(logs_dir / "smb.csv")["sharpe_ratio"].tolist() == [1.0]
+
+
+def test_a_run_id_with_saved_files_is_refused(logs_dir):
+    (logs_dir / "smb_run1_compositions.json").write_text("[]")
+
+    with pytest.raises(FileExistsError):
+        run_logger(logs_dir, "run1").log_results({"sharpe_ratio": 1.0})
+
+    assert not (logs_dir / "smb.csv").exists()
+
+
+def test_widening_the_header_leaves_no_temp_file(logs_dir):
+    run_logger(logs_dir, "run1").log_results({"sharpe_ratio": 1.0})
+    run_logger(logs_dir, "run2").log_results({"sharpe_ratio": 2.0, "calmar_ratio": 0.5})
+
+    logs = pd.read_csv(logs_dir / "smb.csv")
+
+    assert logs["calmar_ratio"].isna().tolist() == [True, False]
+    assert [path.name for path in logs_dir.iterdir() if path.name.endswith(".tmp")] == []
+
+
+def test_a_wider_row_appended_to_an_old_log_reloads_aligned(logs_dir):
+    (logs_dir / "smb.csv").write_text(
+        "run_id,factor,breakpoint,min_assets,weighting_method,sharpe_ratio\n"
+        "old1,smb,0.3,5,equal,0.8\n"
+        "old2,smb,0.2,10,equal,1.2\n"
+    )
+
+    run_logger(logs_dir, "new").log_results(
+        {"sharpe_ratio": 1.5, "calmar_ratio": 0.7, "start_date": "2024-01-07", "end_date": "2024-06-30"}
+    )
+    logs = api.load_factor_logs("smb")
+
+    assert list(logs.columns) == [
+        "run_id", "factor", "breakpoint", "min_assets", "weighting_method",
+        "sharpe_ratio", "calmar_ratio", "start_date", "end_date",
+    ]
+    assert logs["run_id"].tolist() == ["old1", "old2", "new"]
+    assert logs["breakpoint"].tolist() == [0.3, 0.2, 0.3]
+    assert logs["sharpe_ratio"].tolist() == [0.8, 1.2, 1.5]
+    assert logs["calmar_ratio"].isna().tolist() == [True, True, False]
+    assert logs["calmar_ratio"].iloc[2] == 0.7
+    assert logs["start_date"].tolist() == ["", "", "2024-01-07"]
+    assert logs["end_date"].tolist() == ["", "", "2024-06-30"]
+
+
+def run_concurrently(target, args_list: list) -> list:
+    """Run target once per args in its own thread, released together, returning the errors raised"""
+    barrier = threading.Barrier(len(args_list))
+    errors = []
+
+    def run(*args):
+        barrier.wait()
+        try:
+            target(*args)
+        except Exception as e:
+            errors.append(e)
+
+    threads = [threading.Thread(target=run, args=args) for args in args_list]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+    return errors
+
+
+def test_concurrent_runs_recreate_a_removed_logs_dir_without_errors(tmp_path):
+    # Created at startup, then removed while the server runs
+    log_dir = tmp_path / "nested" / "factor_logs"
+    run_ids = [f"run{i}" for i in range(8)]
+
+    errors = run_concurrently(lambda run_id: log_run(log_dir, run_id), [(run_id,) for run_id in run_ids])
+
+    assert errors == []
+    assert sorted(pd.read_csv(log_dir / "smb.csv")["run_id"]) == run_ids
+    assert set(run_logger(log_dir, "other").load_manifest()["runs"]) == set(run_ids)
+
+
+def test_concurrent_calls_to_every_saver_tolerate_a_missing_logs_dir(tmp_path):
+    log_dir = tmp_path / "factor_logs"
+    savers = [
+        lambda: run_logger(log_dir, "a").log_results({"sharpe_ratio": 1.0}),
+        lambda: run_logger(log_dir, "b").save_time_series(weekly_returns([0.01])),
+        lambda: run_logger(log_dir, "c").save_compositions({}),
+        lambda: run_logger(log_dir, "d").save_config({"factor": "smb"}, 1),
+    ]
+
+    assert run_concurrently(lambda save: save(), [(save,) for save in savers]) == []
+    assert log_dir.is_dir()
@@ -0,0 +1,71 @@
+import pytest
+
+RUN_IDS = [f"run{i}" for i in range(7)]
+
+
+@pytest.fixture
+def logged(seed_run):
+    for run_id in RUN_IDS:
+        seed_run("smb", run_id, sharpe_ratio=1.0)
+
+
+def page(client, **params) -> list:
+    response = client.get("/factors/smb/logs", params=params)
+    assert response.status_code == 200
+    return [run["run_id"] for run in response.json()]
+
+
+@pytest.mark.usefixtures("logged")
+class TestLogPagination:
+    def test_the_default_page_is_the_most_recent_runs(self, client):
+        assert page(client) == RUN_IDS
+
+    @pytest.mark.parametrize(
+        "offset, limit, expected",
+        [
+            (0, 3, ["run4", "run5", "run6"]),
+            (3, 3, ["run1", "run2", "run3"]),
+            (5, 3, ["run0", "run1"]),
+            (1, 1, ["run5"]),
+        ],
+    )
+    def test_offset_counts_back_from_the_most_recent_run(self, client, offset, limit, expected):
+        assert page(client, offset=offset, limit=limit) == expected
+
+    def test_pages_cover_every_run_once(self, client):
+        pages = [page(client, offset=offset, limit=3) for offset in (0, 3, 6)]
+
+        assert sorted(run_id for runs in pages for run_id in runs) == RUN_IDS
+
+    @pytest.mark.parametrize("offset", [7, 100])
+    def test_an_offset_past_the_oldest_run_is_an_empty_page(self, client, offset):
+        assert page(client, offset=offset, limit=3) == []
+
+    def test_a_negative_offset_fails_validation(self, client):
+        assert client.get("/factors/smb/logs", params={"offset": -1}).status_code == 422
+
+
+@pytest.fixture
+def with_partial_run(seed_run):
+    seed_run("smb", "full1", sharpe_ratio=1.0, partial=False)
+    seed_run("smb", "stopped", sharpe_ratio=0.2, partial=True)
+    seed_run("smb", "full2", sharpe_ratio=1.1, partial=False)
+
+
+@pytest.mark.usefixtures("with_partial_run")
+class TestPartialRuns:
+    def test_are_left_out_like_every_other_listing(self, client):
+        assert page(client) == ["full1", "full2"]
+        assert [run["run_id"] for run in client.get("/factors/smb/runs").json()["runs"]] == ["full2", "full1"]
+
+    def test_arent_counted_when_paging(self, client):
+        assert page(client, offset=1, limit=1) == ["full1"]
+
+    def test_are_listed_and_marked_with_include_partial(self, client):
+        runs = client.get("/factors/smb/logs", params={"include_partial": True}).json()
+
+        assert [(run["run_id"], run["partial"]) for run in runs] == [
+            ("full1", False),
+            ("stopped", True),
+            ("full2", False),
+        ]
@@ -0,0 +1,72 @@
+import pandas as pd
+import pytest
+from fastapi import HTTPException
+
+import api
+from utils import FactorModel
+
+MARKET_CAPS = [500.0, 100.0, 300.0, 50.0, 200.0]
+RETURNS = [0.01, 0.05, 0.02, -0.03, 0.04]
+
+
+def market_model(top_n: int, dates=("2024-01-07",)) -> FactorModel:
+    df = pd.concat(
+        pd.DataFrame(
+            {
+                "date": pd.Timestamp(date),
+                "asset": [f"asset{i}" for i in range(len(MARKET_CAPS))],
+                "mc_t_minus_1": MARKET_CAPS,
+                "price_pct_change_p1": RETURNS,
+            }
+        )
+        for date in dates
+    )
+    factor_model = FactorModel(df, "market", breakpoint=0.5, min_assets=5, weighting_method="market_cap")
+    factor_model.form_long_only_portfolio("mc_t_minus_1", top_n, weight_col="mc_t_minus_1")
+    return factor_model
+
+
+def config(**fields) -> api.FactorConfig:
+    return api.FactorConfig(factor="market", start_date="2024-01-01", end_date="2024-06-30", **fields)
+
+
+class TestTopN:
+    def test_holds_the_largest_assets_by_market_cap(self):
+        (holdings,) = market_model(3).factor_assets.values()
+
+        assert set(holdings["long_portfolio"]) == {"asset0", "asset2", "asset4"}
+        assert holdings["short_portfolio"] == {}
+
+    def test_weights_are_proportional_to_market_cap_and_sum_to_one(self):
+        (holdings,) = market_model(3).factor_assets.values()
+        weights = {asset: holding["weighting"] for asset, holding in holdings["long_portfolio"].items()}
+
+        assert sum(weights.values()) == pytest.approx(1.0)
+        assert weights == pytest.approx({"asset0": 0.5, "asset2": 0.3, "asset4": 0.2})
+
+    def test_the_factor_return_is_the_weighted_return_of_the_holdings(self):
+        factor_model = market_model(3)
+
+        (factor_return,) = factor_model.factor_returns.values()
+        assert factor_return == pytest.approx(0.5 * 0.01 + 0.3 * 0.02 + 0.2 * 0.04)
+        assert factor_model.long_portfolio_returns == factor_model.factor_returns
+
+    def test_a_period_with_fewer_than_top_n_assets_is_skipped(self):
+        assert market_model(6, dates=("2024-01-07", "2024-01-14")).factor_returns == {}
+
+
+class TestWeightingMethod:
+    def test_defaults_to_market_cap_for_the_market_factor_only(self):
+        assert config().weighting_method == "market_cap"
+        assert api.FactorConfig(factor="smb", start_date="2024-01-01", end_date="2024-06-30").weighting_method == "equal"
+
+    @pytest.mark.parametrize("weighting_method", ["equal", "inverse_variance"])
+    def test_another_weighting_is_a_400(self, weighting_method):
+        with pytest.raises(HTTPException) as error:
+            api.run_market_factor(config(weighting_method=weighting_method))
+
+        assert error.value.status_code == 400
+        assert weighting_method in error.value.detail
+
+    def test_an_explicit_market_cap_weighting_is_accepted(self):
+        assert config(weighting_method="market_cap").model_dump() == config().model_dump()
@@ -0,0 +1,320 @@
+from types import SimpleNamespace
+
+import pandas as pd
+import pytest
+
+import api
+import utils
+
+
+class FakeSources:
+    """Artemis and Coinbase stand-ins serving fixed frames, as fetch_merged_crypto_data receives them"""
+
+    def __init__(self):
+        self.metrics = pd.DataFrame(columns=["date", "asset", "mc"])
+        self.candles = pd.DataFrame(columns=["date", "asset", "price", "24h_volume"])
+        self.listed = 0  # times the full Artemis universe was listed
+        self.metric_symbols = []  # symbols of each Artemis metric fetch
+        self.price_symbols = []  # symbols of each Coinbase price fetch
+
+    def artemis(self, api_key=None):
+        return FakeArtemis(self)
+
+    def coinbase(self):
+        return FakeCoinbase(self)
+
+
+class FakeArtemis:
+    def __init__(self, sources: FakeSources):
+        self.sources = sources
+
+    def with_cache(self, cache=None):
+        return self
+
+    def list_crypto_symbols(self):
+        self.sources.listed += 1
+        return sorted(self.sources.metrics["asset"].unique())
+
+    def get_metric_for_all_symbols(self, metrics, start_date, end_date, symbols, progress=None):
+        self.sources.metric_symbols.append(list(symbols))
+        return self.sources.metrics[self.sources.metrics["asset"].isin(symbols)].reset_index(drop=True)
+
+
+class FakeCoinbase:
+    def __init__(self, sources: FakeSources):
+        self.sources = sources
+
+    def get_price_volume_for_symbols(self, symbols, start_date, end_date, progress=None):
+        """The candles of the requested symbols, and the symbols without any (as CoinbaseData skips them)"""
+        self.sources.price_symbols.append(list(symbols))
+        candles = self.sources.candles[self.sources.candles["asset"].isin(symbols)]
+        return candles, [symbol for symbol in symbols if symbol not in set(candles["asset"])]
+
+
+@pytest.fixture
+def sources(monkeypatch) -> FakeSources:
+    fake = FakeSources()
+    monkeypatch.setattr(utils, "ApiData", fake.artemis)
+    monkeypatch.setattr(utils, "CoinbaseData", fake.coinbase)
+    return fake
+
+
+def merge(data_quality=None, **kwargs) -> pd.DataFrame:
+    return utils.fetch_merged_crypto_data(
+        start_date="2024-01-01", end_date="2024-01-03", api_key="key", data_quality=data_quality, **kwargs
+    )
+
+
+def metric_rows(asset: str, dates: list) -> pd.DataFrame:
+    return pd.DataFrame({"date": pd.to_datetime(dates), "asset": asset, "mc": 1e9})
+
+
+def candle_rows(asset: str, timestamps: list) -> pd.DataFrame:
+    return pd.DataFrame(
+        {"date": pd.to_datetime(timestamps, utc=True), "asset": asset, "price": 100.0, "24h_volume": 5e6}
+    )
+
+
+def test_timestamps_hours_apart_on_the_same_utc_day_join(sources):
+    sources.metrics = metric_rows("bitcoin", ["2024-01-01", "2024-01-02", "2024-01-03"])
+    sources.candles = candle_rows(
+        "bitcoin", ["2024-01-01T05:00:00Z", "2024-01-02T06:00:00Z", "2024-01-03T23:59:00Z"]
+    )
+
+    merged = merge()
+
+    assert merged.index.tolist() == list(pd.to_datetime(["2024-01-01", "2024-01-02", "2024-01-03"]))
+    assert merged["price"].notna().all()
+
+
+def test_a_candle_on_another_utc_day_does_not_join(sources):
+    sources.metrics = metric_rows("bitcoin", ["2024-01-01", "2024-01-02"])
+    # 20:00 in New York on Jan 1 is already Jan 2 in UTC, so Jan 1 has no candle
+    sources.candles = candle_rows("bitcoin", [pd.Timestamp("2024-01-01T20:00:00", tz="America/New_York")])
+
+    data_quality = {}
+    merged = merge(data_quality)
+
+    assert merged.index.tolist() == [pd.Timestamp("2024-01-02")]
+    assert data_quality["unmatched_metric_rows"] == 1
+
+
+def test_a_left_join_keeps_metric_days_without_a_candle(sources):
+    sources.metrics = metric_rows("bitcoin", ["2024-01-01", "2024-01-02"])
+    sources.candles = candle_rows("bitcoin", ["2024-01-02T12:00:00Z"])
+
+    merged = merge(join="left")
+
+    assert merged.index.tolist() == list(pd.to_datetime(["2024-01-01", "2024-01-02"]))
+    assert merged["price"].isna().tolist() == [True, False]
+
+
+def test_two_candles_on_one_day_keep_the_later(sources):
+    sources.metrics = metric_rows("bitcoin", ["2024-01-01"])
+    sources.candles = pd.concat(
+        [candle_rows("bitcoin", ["2024-01-01T01:00:00Z"]), candle_rows("bitcoin", ["2024-01-01T02:00:00Z"])]
+    ).assign(price=[100.0, 101.0])
+
+    assert merge()["price"].tolist() == [101.0]
+
+
+def test_a_symbol_without_candles_is_dropped_as_missing_prices(sources):
+    sources.metrics = pd.concat(
+        [metric_rows("bitcoin", ["2024-01-01", "2024-01-02"]), metric_rows("ethereum", ["2024-01-01", "2024-01-02"])]
+    )
+    sources.candles = candle_rows("bitcoin", ["2024-01-01T12:00:00Z", "2024-01-02T12:00:00Z"])
+
+    data_quality = {}
+    merged = merge(data_quality)
+
+    assert data_quality["dropped_assets"] == {"ethereum": "missing_prices"}
+    assert set(merged["asset"]) == {"bitcoin"}
+
+
+@pytest.mark.parametrize(
+    "timestamp, day",
+    [
+        ("2024-01-01T23:30:00-05:00", "2024-01-02"),
+        ("2024-01-01T23:30:00+00:00", "2024-01-01"),
+        ("2024-01-01T23:30:00", "2024-01-01"),
+    ],
+)
+def test_dates_are_floored_to_their_utc_day(timestamp, day):
+    assert utils.normalize_dates(pd.Series([timestamp])).tolist() == [pd.Timestamp(day)]
+
+
+def test_a_priced_symbol_without_metrics_is_dropped_as_missing_metrics(sources):
+    sources.metrics = metric_rows("bitcoin", ["2024-01-01", "2024-01-02"])
+    sources.candles = pd.concat(
+        [
+            candle_rows("bitcoin", ["2024-01-01T12:00:00Z", "2024-01-02T12:00:00Z"]),
+            candle_rows("ethereum", ["2024-01-01T12:00:00Z", "2024-01-02T12:00:00Z"]),
+        ]
+    )
+
+    data_quality = {}
+    merged = merge(data_quality, universe=["bitcoin", "ethereum"])
+
+    assert data_quality["dropped_assets"] == {"ethereum": "missing_metrics: mc"}
+    assert set(merged["asset"]) == {"bitcoin"}
+
+
+def test_the_drop_reason_names_only_the_required_metrics_missing(sources):
+    sources.metrics = pd.concat(
+        [
+            metric_rows("bitcoin", ["2024-01-01"]).assign(fees=1e6),
+            metric_rows("ethereum", ["2024-01-01"]).assign(mc=float("nan"), fees=float("nan")),
+        ]
+    )
+    sources.candles = pd.concat(
+        [candle_rows("bitcoin", ["2024-01-01T12:00:00Z"]), candle_rows("ethereum", ["2024-01-01T12:00:00Z"])]
+    )
+
+    data_quality = {}
+    merge(data_quality, artemis_metrics=["mc", "fees"], required_metrics=["fees"])
+
+    assert data_quality["dropped_assets"] == {"ethereum": "missing_metrics: fees"}
+
+
+class TestAllNullMetrics:
+    DAYS = ["2024-01-01", "2024-01-02"]
+
+    @pytest.fixture(autouse=True)
+    def priced(self, sources):
+        sources.candles = pd.concat(
+            [candle_rows(asset, ["2024-01-01T12:00:00Z", "2024-01-02T12:00:00Z"]) for asset in ("bitcoin", "ethereum")]
+        )
+
+    def test_an_all_null_required_metric_drops_the_asset(self, sources):
+        # ethereum has rows, but every market cap is null
+        sources.metrics = pd.concat(
+            [metric_rows("bitcoin", self.DAYS), metric_rows("ethereum", self.DAYS).assign(mc=float("nan"))]
+        )
+
+        data_quality = {}
+        merged = merge(data_quality)
+
+        assert data_quality["dropped_assets"] == {"ethereum": "missing_metrics: mc"}
+        assert data_quality["null_metric_series"] == {}
+        assert set(merged["asset"]) == {"bitcoin"}
+
+    def test_an_all_null_optional_metric_keeps_the_asset_and_reports_it(self, sources):
+        sources.metrics = pd.concat(
+            [
+                metric_rows("bitcoin", self.DAYS).assign(fees=1e6),
+                metric_rows("ethereum", self.DAYS).assign(fees=float("nan")),
+            ]
+        )
+
+        data_quality = {}
+        merged = merge(data_quality, artemis_metrics=["mc", "fees"], required_metrics=["mc"])
+
+        assert data_quality["dropped_assets"] == {}
+        assert data_quality["null_metric_series"] == {"ethereum": ["fees"]}
+        assert merged.loc[merged["asset"] == "ethereum", "fees"].isna().all()
+
+    def test_a_partly_null_metric_is_not_missing(self, sources):
+        sources.metrics = pd.concat(
+            [metric_rows("bitcoin", self.DAYS), metric_rows("ethereum", self.DAYS).assign(mc=[1e9, float("nan")])]
+        )
+
+        data_quality = {}
+        merged = merge(data_quality)
+
+        assert data_quality["dropped_assets"] == {}
+        assert data_quality["null_metric_series"] == {}
+        assert set(merged["asset"]) == {"bitcoin", "ethereum"}
+
+
+class TestPivotMetricBatch:
+    def test_null_and_nan_values_are_skipped(self):
+        pivoted = utils.ApiData._pivot_metric_batch(
+            {
+                "bitcoin": {
+                    "mc": [
+                        {"date": "2024-01-01", "val": 1e9},
+                        {"date": "2024-01-02", "val": None},
+                        {"date": "2024-01-03", "val": float("nan")},
+                    ]
+                }
+            }
+        )
+
+        assert pivoted["date"].tolist() == [pd.Timestamp("2024-01-01")]
+        assert pivoted["mc"].tolist() == [1e9]
+
+    def test_object_items_are_read_like_dicts(self):
+        items = [SimpleNamespace(date="2024-01-01", val=1e9), SimpleNamespace(date="2024-01-02", val=float("nan"))]
+
+        pivoted = utils.ApiData._pivot_metric_batch({"bitcoin": {"mc": items}})
+
+        assert pivoted["mc"].tolist() == [1e9]
+
+    def test_a_batch_of_only_null_series_is_empty(self):
+        symbols_data = {
+            "bitcoin": {"mc": [{"date": "2024-01-01", "val": float("nan")}]},
+            "ethereum": {"mc": [{"date": "2024-01-01", "val": None}], "fees": []},
+        }
+
+        assert utils.ApiData._pivot_metric_batch(symbols_data).empty
+
+    def test_an_asset_whose_series_is_all_null_gets_no_rows(self):
+        pivoted = utils.ApiData._pivot_metric_batch(
+            {
+                "bitcoin": {"mc": [{"date": "2024-01-01", "val": 1e9}]},
+                "ethereum": {"mc": [{"date": "2024-01-01", "val": float("nan")}]},
+            }
+        )
+
+        assert pivoted["asset"].tolist() == ["bitcoin"]
+
+
+class TestUniverseAllowlist:
+    @pytest.fixture(autouse=True)
+    def listed(self, sources):
+        """Three listed, priced assets with metrics"""
+        assets = ("bitcoin", "ethereum", "solana")
+        sources.metrics = pd.concat([metric_rows(asset, ["2024-01-01"]) for asset in assets])
+        sources.candles = pd.concat([candle_rows(asset, ["2024-01-01T12:00:00Z"]) for asset in assets])
+
+    def test_without_an_allowlist_every_listed_asset_is_fetched(self, sources):
+        merged = merge()
+
+        assert sources.listed == 1
+        assert sources.metric_symbols == [["bitcoin", "ethereum", "solana"]]
+        assert set(merged["asset"]) == {"bitcoin", "ethereum", "solana"}
+
+    def test_only_allowlisted_assets_are_fetched(self, sources):
+        merged = merge(universe=["bitcoin", "ethereum"])
+
+        assert sources.listed == 0
+        assert sources.metric_symbols == [["bitcoin", "ethereum"]]
+        assert sources.price_symbols == [["bitcoin", "ethereum"]]
+        assert set(merged["asset"]) == {"bitcoin", "ethereum"}
+
+    def test_tickers_and_product_ids_resolve_to_one_symbol_each(self, sources):
+        merge(universe=["BTC", "ETH-USD", "bitcoin"])
+
+        assert sources.metric_symbols == [["bitcoin", "ethereum"]]
+
+    def test_unmapped_names_are_dropped_under_the_name_given(self, sources):
+        data_quality = {}
+        merged = merge(data_quality, universe=["bitcoin", "Not A Coin"])
+
+        assert sources.metric_symbols == [["bitcoin"]]
+        assert data_quality["dropped_assets"] == {"Not A Coin": "no_coinbase_mapping"}
+        assert set(merged["asset"]) == {"bitcoin"}
+
+    def test_compute_requests_pass_their_universe_through(self, monkeypatch):
+        api._load_utils()
+        passed = []
+        monkeypatch.setattr(
+            api, "_fetch_merged_crypto_data", lambda **kwargs: passed.append(kwargs["universe"]) or pd.DataFrame()
+        )
+        config = api.FactorConfig(
+            factor="smb", start_date="2024-01-01", end_date="2024-06-30", universe=["bitcoin", "ethereum"]
+        )
+
+        api.fetch_factor_data(config, ["mc"], {})
+
+        assert passed == [["bitcoin", "ethereum"]]
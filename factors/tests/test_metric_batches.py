@@ -0,0 +1,96 @@
+import gc
+import weakref
+from types import SimpleNamespace
+
+import pandas as pd
+import pytest
+
+import utils
+from utils import ApiData
+
+# Twelve symbols fetch in three batches of at most five
+SYMBOLS = [f"asset{i:02d}" for i in range(12)]
+DAYS = ["2024-01-01", "2024-01-02", "2024-01-03"]
+
+
+class BatchResponse:
+    """An Artemis fetch_metrics response: {asset: {metric: [{date, val}]}} under data.symbols"""
+
+    def __init__(self, symbols_data: dict):
+        self.data = SimpleNamespace(symbols=symbols_data)
+
+
+class BatchedArtemis:
+    """An Artemis client answering each batch, recording how many earlier responses are still alive at each request"""
+
+    def __init__(self):
+        self.responses = []
+        self.alive_at_request = []
+
+    def fetch_metrics(self, api_key, metric_names, symbols, start_date, end_date):
+        gc.collect()
+        self.alive_at_request.append(sum(response() is not None for response in self.responses))
+        response = BatchResponse(
+            {
+                symbol: {
+                    # The last batch has no fees at all
+                    metric: [{"date": day, "val": float(SYMBOLS.index(symbol))} for day in DAYS]
+                    if metric == "mc" or symbol < "asset10"
+                    else []
+                    for metric in metric_names.split(",")
+                }
+                for symbol in symbols
+            }
+        )
+        self.responses.append(weakref.ref(response))
+        return response
+
+
+@pytest.fixture
+def artemis(monkeypatch) -> ApiData:
+    """An ApiData fetching from a BatchedArtemis client, without the pause between batches"""
+    monkeypatch.setattr(utils.time, "sleep", lambda seconds: None)
+    api_data = ApiData("key")
+    api_data.client = BatchedArtemis()
+    return api_data
+
+
+@pytest.fixture
+def pivoted_batches(monkeypatch) -> list:
+    """The number of assets in each batch handed to _pivot_metric_batch"""
+    sizes = []
+    pivot = ApiData._pivot_metric_batch
+
+    def spy(symbols_data):
+        sizes.append(len(symbols_data))
+        return pivot(symbols_data)
+
+    monkeypatch.setattr(ApiData, "_pivot_metric_batch", staticmethod(spy))
+    return sizes
+
+
+def fetch(api_data: ApiData) -> pd.DataFrame:
+    return api_data.get_metric_for_all_symbols(["mc", "fees"], DAYS[0], DAYS[-1], symbols=SYMBOLS)
+
+
+def test_each_batch_is_pivoted_as_it_arrives(artemis, pivoted_batches):
+    fetch(artemis)
+
+    assert pivoted_batches == [5, 5, 2]
+
+
+def test_only_the_latest_response_is_held_while_fetching_the_next_batch(artemis):
+    fetch(artemis)
+
+    assert artemis.client.alive_at_request == [0, 1, 1]
+
+
+def test_the_batches_combine_into_one_wide_row_per_date_and_asset(artemis):
+    df = fetch(artemis)
+
+    assert len(df) == len(SYMBOLS) * len(DAYS)
+    assert not df.reset_index().duplicated(["date", "asset"]).any()
+    assert df.loc[df["asset"] == "asset07", "mc"].tolist() == [7.0, 7.0, 7.0]
+    # A metric missing from a whole batch is null for its assets, not dropped
+    assert df.loc[df["asset"].isin(["asset10", "asset11"]), "fees"].isna().all()
+    assert df.loc[df["asset"] == "asset09", "fees"].notna().all()
@@ -0,0 +1,130 @@
+import threading
+import time
+
+import pandas as pd
+import pytest
+
+from utils import ApiData, MetricCache
+
+SYMBOLS = ["eth", "sol"]
+
+
+def metrics_frame() -> pd.DataFrame:
+    return pd.DataFrame({"date": pd.to_datetime(["2024-01-01"]), "asset": ["eth"], "mc": [1.0]})
+
+
+@pytest.fixture
+def fetches(monkeypatch):
+    """Calls to the uncached Artemis metric fetch, each returning a one-row frame"""
+    calls = []
+
+    def fetch(self, metrics, start_date, end_date, symbols, progress):
+        calls.append((tuple(symbols), tuple(metrics), start_date, end_date))
+        return metrics_frame()
+
+    monkeypatch.setattr(ApiData, "_fetch_metric_for_all_symbols", fetch)
+    return calls
+
+
+def get_metrics(api_data: ApiData, start_date: str = "2024-01-01") -> pd.DataFrame:
+    return api_data.get_metric_for_all_symbols(["mc"], start_date, "2024-06-30", symbols=SYMBOLS)
+
+
+def test_a_zero_ttl_fetches_every_time(fetches):
+    api_data = ApiData("key").with_cache(MetricCache(0))
+
+    get_metrics(api_data)
+    get_metrics(api_data)
+
+    assert len(fetches) == 2
+
+
+def test_identical_requests_within_the_ttl_fetch_once(fetches):
+    api_data = ApiData("key").with_cache(MetricCache(60))
+
+    first = get_metrics(api_data)
+    second = get_metrics(api_data)
+
+    assert len(fetches) == 1
+    pd.testing.assert_frame_equal(first, second)
+
+
+def test_a_different_date_range_is_fetched_separately(fetches):
+    api_data = ApiData("key").with_cache(MetricCache(60))
+
+    get_metrics(api_data)
+    get_metrics(api_data, start_date="2024-02-01")
+
+    assert len(fetches) == 2
+
+
+def test_callers_get_copies_of_the_cached_frame(fetches):
+    api_data = ApiData("key").with_cache(MetricCache(60))
+
+    get_metrics(api_data)["mc"] = 99.0
+
+    assert get_metrics(api_data)["mc"].tolist() == [1.0]
+
+
+def test_clear_drops_the_frames_and_their_locks(fetches):
+    cache = MetricCache(60)
+    api_data = ApiData("key").with_cache(cache)
+    get_metrics(api_data)
+
+    cache.clear()
+    get_metrics(api_data)
+
+    assert len(fetches) == 2
+    cache.clear()
+    assert cache._key_locks == {}
+
+
+def test_evicting_a_key_keeps_the_lock_of_a_fetch_in_flight():
+    cache = MetricCache(60, max_entries=1)
+    fetching, release = threading.Event(), threading.Event()
+    fetch_counts = {"slow": 0}
+
+    def slow_fetch():
+        fetch_counts["slow"] += 1
+        fetching.set()
+        release.wait(5)
+        return metrics_frame()
+
+    cache.get_or_fetch("slow", metrics_frame)
+    cache._entries["slow"] = (0.0, metrics_frame())  # expired, so the next call refetches
+    refetch = threading.Thread(target=cache.get_or_fetch, args=("slow", slow_fetch))
+    refetch.start()
+    assert fetching.wait(5)
+
+    # Filling another key evicts "slow" while its refetch holds the key lock
+    cache.get_or_fetch("other", metrics_frame)
+    waiter = threading.Thread(target=cache.get_or_fetch, args=("slow", slow_fetch))
+    waiter.start()
+    deadline = time.monotonic() + 5
+    while cache._key_lock_users.get("slow", 0) < 2 and time.monotonic() < deadline:
+        time.sleep(0.01)
+    release.set()
+    refetch.join(5)
+    waiter.join(5)
+
+    assert fetch_counts["slow"] == 1
+
+
+def test_a_raising_progress_callback_doesnt_abandon_the_cached_fetch(monkeypatch):
+    def fetch(self, metrics, start_date, end_date, symbols, progress):
+        progress("fetching_metrics", 0.0)
+        return metrics_frame()
+
+    def cancelled(phase, percent):
+        raise RuntimeError("client gone")
+
+    monkeypatch.setattr(ApiData, "_fetch_metric_for_all_symbols", fetch)
+    cache = MetricCache(60)
+    api_data = ApiData("key").with_cache(cache)
+
+    # This caller is abandoned once the fetch is done, but the frame is cached for the others
+    with pytest.raises(RuntimeError):
+        api_data.get_metric_for_all_symbols(["mc"], "2024-01-01", "2024-06-30", symbols=SYMBOLS, progress=cancelled)
+
+    assert get_metrics(api_data)["mc"].tolist() == [1.0]
+    assert len(cache._entries) == 1
@@ -0,0 +1,47 @@
+import pytest
+from fastapi import HTTPException
+
+import api
+
+BODY = {"factor": "momentum", "start_date": "2024-01-01", "end_date": "2024-06-30"}
+
+
+def config(**fields) -> api.FactorConfig:
+    return api.FactorConfig(**{**BODY, **fields})
+
+
+class TestDeprecatedLookbackPeriods:
+    def test_is_applied_as_the_same_length_in_days(self):
+        assert api.momentum_lookback_alias(config(), 3).lookback_days == 21
+
+    def test_counts_periods_of_the_runs_frequency(self):
+        assert api.momentum_lookback_alias(config(rebalance_frequency="monthly"), 2).lookback_days == 61
+
+    def test_is_ignored_when_not_given(self):
+        original = config(lookback_days=60)
+
+        assert api.momentum_lookback_alias(original, None) is original
+
+    def test_agreeing_with_lookback_days_is_accepted(self):
+        assert api.momentum_lookback_alias(config(lookback_days=28), 4).lookback_days == 28
+
+    def test_disagreeing_with_lookback_days_is_a_400(self):
+        with pytest.raises(HTTPException) as error:
+            api.momentum_lookback_alias(config(lookback_days=60), 4)
+
+        assert error.value.status_code == 400
+
+
+def test_the_momentum_route_still_accepts_lookback_periods(client, monkeypatch):
+    dispatched = []
+
+    async def dispatch(factor, config, **params):
+        dispatched.append(config)
+        return {"factor": factor}
+
+    monkeypatch.setattr(api, "dispatch_compute", dispatch)
+
+    response = client.post("/compute/momentum", params={"lookback_periods": 4}, json=BODY)
+
+    assert response.status_code == 200
+    assert dispatched[0].lookback_days == 28
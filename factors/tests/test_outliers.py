@@ -0,0 +1,60 @@
+import numpy as np
+import pandas as pd
+import pytest
+
+from utils import FactorModel, Logger
+
+DATES = pd.date_range("2024-01-07", periods=11, freq="W")
+
+
+@pytest.fixture
+def asset_returns(logs_dir):
+    """Save a run's per-asset returns: a steady asset with one planted 50% week, and a noisy one without"""
+
+    def save(steady_returns):
+        df = pd.DataFrame(
+            {
+                "date": list(DATES) * 2,
+                "asset": ["steady"] * 11 + ["noisy"] * 11,
+                "price_pct_change_p1": [*steady_returns, *[0.2 * (-1) ** i for i in range(11)]],
+            }
+        )
+        Logger(logs_dir, FactorModel(pd.DataFrame(), "smb", run_id="run1")).save_asset_returns(df)
+
+    return save
+
+
+def get_outliers(client, **params) -> dict:
+    response = client.get("/factors/smb/runs/run1/outliers", params=params)
+    assert response.status_code == 200
+    return response.json()
+
+
+def test_a_planted_outlier_is_flagged_with_the_default_threshold(client, asset_returns):
+    # Ten weeks alternating +-1%, then +50% in week 6: its own return can't mask it
+    asset_returns([0.01 * (-1) ** i for i in range(5)] + [0.5] + [0.01 * (-1) ** i for i in range(5)])
+
+    outliers = get_outliers(client)
+
+    assert outliers["num_assets"] == 2
+    assert outliers["num_outliers"] == 1
+    (outlier,) = outliers["outliers"]
+    assert (outlier["asset"], outlier["date"], outlier["return"]) == ("steady", "2024-02-11", 0.5)
+    # The other weeks: six +1% and four -1%, mean 0.2%
+    others = [0.01] * 6 + [-0.01] * 4
+    assert outlier["zscore"] == pytest.approx((0.5 - np.mean(others)) / np.std(others, ddof=1))
+
+
+def test_returns_within_the_assets_range_are_not_flagged(client, asset_returns):
+    asset_returns([0.01 * (-1) ** i for i in range(11)])
+
+    assert get_outliers(client)["num_outliers"] == 0
+
+
+def test_assets_with_too_few_returns_are_skipped(client, asset_returns):
+    asset_returns([0.01 * (-1) ** i for i in range(5)] + [0.5] + [0.01 * (-1) ** i for i in range(5)])
+
+    outliers = get_outliers(client, min_observations=12)
+
+    assert outliers["num_assets"] == 0
+    assert outliers["outliers"] == []
@@ -0,0 +1,49 @@
+import pandas as pd
+import pytest
+
+import api
+from utils import FactorModel, Logger
+
+from .helpers import weekly_returns
+
+pytest.importorskip("pyarrow")
+
+RETURNS = weekly_returns([0.0123456789, -0.02, 0.031, 0.0])
+ICS = dict(zip(RETURNS, [0.1, None, -0.25, 0.05]))
+
+
+def logger(logs_dir, run_id: str) -> Logger:
+    return Logger(logs_dir, FactorModel(pd.DataFrame(), "smb", run_id=run_id))
+
+
+def test_a_parquet_series_reads_back_identical_values(logs_dir):
+    logger(logs_dir, "run1").save_time_series(RETURNS, ICS, log_format="parquet")
+
+    loaded = Logger.load_time_series_parquet(logs_dir / "smb_run1_returns.parquet")
+
+    assert loaded["date"].tolist() == list(RETURNS)
+    assert loaded["return"].tolist() == list(RETURNS.values())
+    assert loaded["ic"].tolist()[::2] == [0.1, -0.25]
+    assert pd.isna(loaded["ic"].tolist()[1])
+    assert not (logs_dir / "smb_run1_returns.csv").exists()
+
+
+def test_parquet_and_csv_hold_the_same_series(logs_dir):
+    logger(logs_dir, "as_csv").save_time_series(RETURNS)
+    logger(logs_dir, "as_parquet").save_time_series(RETURNS, log_format="parquet")
+
+    from_csv = api.load_time_series("smb", "as_csv")
+    from_parquet = api.load_time_series("smb", "as_parquet")
+
+    pd.testing.assert_frame_equal(from_csv, from_parquet, check_dtype=False)
+
+
+def test_the_time_series_route_reads_parquet_runs(client, seed_run, logs_dir):
+    seed_run("smb", "run1", sharpe_ratio=1.0)
+    logger(logs_dir, "run1").save_time_series(RETURNS, log_format="parquet")
+
+    response = client.get("/factors/smb/runs/run1/series")
+
+    assert response.status_code == 200
+    assert response.json()["dates"] == ["2024-01-07", "2024-01-14", "2024-01-21", "2024-01-28"]
+    assert response.json()["returns"] == pytest.approx(list(RETURNS.values()))
@@ -0,0 +1,143 @@
+import threading
+import time
+
+import pandas as pd
+import pytest
+from fastapi import HTTPException
+
+import api
+from utils import FactorModel
+
+from .helpers import weekly_returns
+
+DATES = pd.date_range("2024-01-07", periods=4, freq="W")
+RETURNS = weekly_returns([0.01, -0.02, 0.03])
+
+
+class StopAfter(threading.Event):
+    """Reads as set from the check after the first `checks`, as if a shutdown arrived mid-run"""
+
+    def __init__(self, checks: int):
+        super().__init__()
+        self.checks = checks
+
+    def is_set(self) -> bool:
+        self.checks -= 1
+        return self.checks < 0
+
+
+def panel() -> pd.DataFrame:
+    """Four weekly rebalances of four assets: signal i, return 0.01 * i"""
+    return pd.DataFrame(
+        [
+            {"date": date, "asset": f"asset{i}", "signal": float(i), "price_pct_change_p1": 0.01 * i}
+            for date in DATES
+            for i in range(4)
+        ]
+    )
+
+
+def test_a_run_stopped_midway_keeps_the_completed_periods():
+    factor_model = FactorModel(
+        panel(), "smb", breakpoint=0.5, min_assets=2, weighting_method="equal", stop_event=StopAfter(2)
+    )
+
+    factor_model.form_long_short_portfolios("signal")
+
+    assert factor_model.partial is True
+    assert list(factor_model.factor_returns) == list(DATES[:2])
+    assert factor_model.stopped_at == DATES[2]
+
+    data_quality = {}
+    api.record_portfolio_quality(factor_model, data_quality)
+    assert data_quality["stopped_at"] == "2024-01-21"
+
+
+def test_a_completed_run_is_not_partial():
+    factor_model = FactorModel(panel(), "smb", breakpoint=0.5, min_assets=2, weighting_method="equal")
+
+    factor_model.form_long_short_portfolios("signal")
+
+    assert factor_model.partial is False
+    assert list(factor_model.factor_returns) == list(DATES)
+
+
+def test_a_run_stopped_before_any_period_is_a_504():
+    factor_model = FactorModel(
+        panel(), "smb", breakpoint=0.5, min_assets=2, weighting_method="equal", deadline=time.monotonic() - 1
+    )
+    factor_model.form_long_short_portfolios("signal")
+
+    with pytest.raises(HTTPException) as error:
+        api.ensure_factor_returns(factor_model)
+
+    assert error.value.status_code == 504
+
+
+class TestFetchDeadline:
+    @pytest.fixture
+    def fetched(self, monkeypatch):
+        """Replace the merged fetch with one that reports progress once and returns an empty frame"""
+        api._load_utils()
+        calls = []
+
+        def fetch(**kwargs):
+            kwargs["progress"]("fetching_metrics", 0.0)
+            calls.append(kwargs)
+            return pd.DataFrame()
+
+        monkeypatch.setattr(api, "_fetch_merged_crypto_data", fetch)
+        return calls
+
+    def config(self):
+        return api.FactorConfig(factor="smb", start_date="2024-01-01", end_date="2024-06-30")
+
+    def test_a_fetch_past_the_deadline_is_a_504(self, fetched):
+        with pytest.raises(HTTPException) as error:
+            api.fetch_factor_data(self.config(), ["mc"], {}, deadline=time.monotonic() - 1)
+
+        assert error.value.status_code == 504
+        assert fetched == []
+
+    def test_a_fetch_within_the_deadline_completes(self, fetched):
+        api.fetch_factor_data(self.config(), ["mc"], {}, deadline=time.monotonic() + 60)
+
+        assert len(fetched) == 1
+
+    def test_a_fetch_without_a_timeout_completes(self, fetched):
+        api.fetch_factor_data(self.config(), ["mc"], {})
+
+        assert len(fetched) == 1
+
+
+class TestPartialRunsAreLeftOut:
+    @pytest.fixture(autouse=True)
+    def runs(self, seed_run):
+        seed_run("smb", "complete", RETURNS, sharpe_ratio=1.0, annualized_return=0.1, partial=False)
+        seed_run("smb", "truncated", RETURNS, sharpe_ratio=9.0, annualized_return=0.9, partial=True)
+
+    def test_latest_is_the_last_complete_run(self, client):
+        assert client.get("/factors/smb/latest").json()["run_id"] == "complete"
+
+    def test_the_factor_run_listing_skips_partial_runs(self, client):
+        runs = client.get("/factors/smb/runs").json()["runs"]
+
+        assert [run["run_id"] for run in runs] == ["complete"]
+
+    def test_the_cross_factor_listing_skips_partial_runs(self, client):
+        runs = client.get("/runs", params={"sort_by": "sharpe_ratio"}).json()["runs"]
+
+        assert [run["run_id"] for run in runs] == ["complete"]
+
+    def test_compare_ranks_the_latest_complete_run(self, client):
+        (smb,) = [row for row in client.get("/factors/compare").json()["comparison"] if row["factor"] == "smb"]
+
+        assert smb["sharpe_ratio"] == 1.0
+
+    def test_the_latest_time_series_is_the_complete_runs(self):
+        run_id, _ = api.load_latest_time_series("smb")
+
+        assert run_id == "complete"
+
+    def test_the_partial_run_is_still_readable_by_id(self, client):
+        assert client.get("/factors/smb/runs/truncated/series").status_code == 200
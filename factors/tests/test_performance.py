@@ -0,0 +1,123 @@
+import numpy as np
+import pandas as pd
+import pytest
+
+import api
+from .helpers import fake_factor_model, weekly_returns
+
+
+def config(**fields) -> api.FactorConfig:
+    return api.FactorConfig(factor="smb", start_date="2024-01-01", end_date="2024-06-30", **fields)
+
+
+@pytest.mark.usefixtures("no_benchmark")
+class TestShortWindowAnnualization:
+    def test_runs_shorter_than_the_minimum_are_flagged(self):
+        # 5 weekly dates span 28 days
+        performance = api.summarize_performance(fake_factor_model(weekly_returns([0.05] * 5)), config())
+
+        assert performance["annualized_return_reliable"] is False
+        assert performance["annualized_return"] is not None
+
+    def test_suppress_nulls_the_annualized_return(self):
+        performance = api.summarize_performance(
+            fake_factor_model(weekly_returns([0.05] * 5)), config(short_window_annualization="suppress")
+        )
+
+        assert performance["annualized_return_reliable"] is False
+        assert performance["annualized_return"] is None
+        assert performance["cumulative_returns"] == pytest.approx(1.05**5 - 1)
+
+    def test_a_run_exactly_at_the_minimum_is_reliable(self):
+        performance = api.summarize_performance(
+            fake_factor_model(weekly_returns([0.05] * 5)),
+            config(min_annualization_days=28, short_window_annualization="suppress"),
+        )
+
+        assert performance["annualized_return_reliable"] is True
+        assert performance["annualized_return"] is not None
+
+    def test_longer_runs_are_annualized(self):
+        # 6 weekly dates span 35 days
+        performance = api.summarize_performance(fake_factor_model(weekly_returns([0.01] * 6)), config())
+
+        assert performance["annualized_return_reliable"] is True
+        assert performance["annualized_return"] == pytest.approx(api.annualize_return(1.01**6 - 1, 35))
+
+
+@pytest.mark.usefixtures("no_benchmark")
+class TestLegAttribution:
+    # The long leg gains 8% then 14%; the shorted assets gain 2% then lose 4%
+    LONG = weekly_returns([0.08, 0.14])
+    SHORT = weekly_returns([0.02, -0.04])
+
+    def summarize(self) -> dict:
+        factor_returns = {date: self.LONG[date] - self.SHORT[date] for date in self.LONG}
+        return api.summarize_performance(fake_factor_model(factor_returns, self.LONG, self.SHORT), config())
+
+    def test_spread_return_compounds_long_minus_shorted_assets_each_period(self):
+        performance = self.summarize()
+
+        assert performance["spread_return"] == pytest.approx(1.06 * 1.18 - 1)
+        assert performance["spread_return"] == pytest.approx(performance["cumulative_returns"])
+
+    def test_short_sharpe_is_for_the_short_position(self):
+        performance = self.summarize()
+
+        # Shorting the assets earned -2% then +4%
+        expected = api.sharpe_ratio([-0.02, 0.04], periods_per_year=52)
+        assert performance["short_sharpe"] == pytest.approx(expected)
+        assert performance["short_sharpe"] > 0
+
+    def test_leg_returns_are_cumulative_returns_of_the_assets_held(self):
+        performance = self.summarize()
+
+        assert performance["long_only_returns"] == pytest.approx(1.08 * 1.14 - 1)
+        assert performance["short_only_returns"] == pytest.approx(1.02 * 0.96 - 1)
+        assert performance["long_sharpe"] == pytest.approx(api.sharpe_ratio([0.08, 0.14], periods_per_year=52))
+
+    def test_long_only_runs_have_no_short_leg_metrics(self):
+        performance = api.summarize_performance(
+            fake_factor_model(self.LONG, self.LONG), config(portfolio_mode="long_only")
+        )
+
+        assert performance["short_only_returns"] is None
+        assert performance["short_sharpe"] is None
+        assert performance["spread_return"] is None
+
+
+@pytest.mark.parametrize(
+    "factor, frequency, expected",
+    [
+        ("smb", "daily", 365),
+        ("smb", "weekly", 52),
+        ("smb", "monthly", 12),
+        ("equity", "daily", 252),
+        ("equity", "weekly", 52),
+    ],
+)
+def test_periods_per_year_follow_the_runs_trading_calendar(factor, frequency, expected):
+    run_config = api.FactorConfig(
+        factor=factor, start_date="2024-01-01", end_date="2024-06-30", rebalance_frequency=frequency
+    )
+
+    assert api.periods_per_year(run_config) == expected
+
+
+@pytest.mark.usefixtures("no_benchmark")
+def test_daily_equity_runs_annualize_over_a_trading_year():
+    returns = [0.01, -0.005, 0.02, 0.0, -0.01, 0.015]
+    dates = pd.bdate_range("2024-06-03", periods=len(returns))
+    run_config = api.FactorConfig(
+        factor="equity", start_date="2024-01-01", end_date="2024-06-30", rebalance_frequency="daily"
+    )
+
+    performance = api.summarize_performance(fake_factor_model(dict(zip(dates, returns))), run_config)
+
+    assert performance["sharpe_ratio"] == pytest.approx(np.mean(returns) / np.std(returns, ddof=1) * np.sqrt(252))
+
+
+def test_a_logged_daily_equity_run_is_read_back_at_252_periods(seed_run):
+    seed_run("equity", "run1", rebalance_frequency="daily")
+
+    assert api.run_periods_per_year("equity", "run1") == 252
@@ -0,0 +1,92 @@
+import pandas as pd
+import pytest
+
+import api
+from utils import FactorModel
+
+# Longs are asset2 and asset3, shorts asset0 and asset1; the second week's returns are doubled
+WEEKS = {"2024-01-07": [0.01, 0.02, 0.03, 0.04], "2024-01-14": [0.02, 0.04, 0.06, 0.08]}
+LONG = [0.035, 0.07]
+SHORTED = [0.015, 0.03]
+
+
+def config(**fields) -> api.FactorConfig:
+    return api.FactorConfig(factor="smb", start_date="2024-01-01", end_date="2024-06-30", **fields)
+
+
+def formed(portfolio_mode: str) -> FactorModel:
+    df = pd.concat(
+        pd.DataFrame(
+            {
+                "date": pd.Timestamp(date),
+                "asset": [f"asset{i}" for i in range(4)],
+                "signal": [0.0, 1.0, 2.0, 3.0],
+                "price_pct_change_p1": returns,
+            }
+        )
+        for date, returns in WEEKS.items()
+    )
+    factor_model = FactorModel(
+        df, "smb", breakpoint=0.5, min_assets=4, weighting_method="equal", portfolio_mode=portfolio_mode
+    )
+    factor_model.form_long_short_portfolios("signal")
+    return factor_model
+
+
+def performance(portfolio_mode: str) -> dict:
+    return api.summarize_performance(formed(portfolio_mode), config(portfolio_mode=portfolio_mode))
+
+
+@pytest.mark.parametrize(
+    "portfolio_mode, factor_returns",
+    [
+        ("long_short", [long - shorted for long, shorted in zip(LONG, SHORTED)]),
+        ("long_only", LONG),
+        ("short_only", [-shorted for shorted in SHORTED]),
+    ],
+)
+def test_the_factor_return_combines_only_the_held_legs(portfolio_mode, factor_returns):
+    assert list(formed(portfolio_mode).factor_returns.values()) == pytest.approx(factor_returns)
+
+
+def test_long_only_holds_no_short_leg():
+    factor_model = formed("long_only")
+
+    assert factor_model.short_portfolio_returns == {}
+    assert all(assets["short_portfolio"] == {} for assets in factor_model.factor_assets.values())
+
+
+def test_short_only_holds_no_long_leg():
+    factor_model = formed("short_only")
+
+    assert factor_model.long_portfolio_returns == {}
+    assert all(assets["long_portfolio"] == {} for assets in factor_model.factor_assets.values())
+
+
+@pytest.mark.usefixtures("no_benchmark")
+class TestLegMetrics:
+    def test_long_short_populates_both_legs(self):
+        metrics = performance("long_short")
+
+        assert metrics["long_only_returns"] == pytest.approx(1.035 * 1.07 - 1)
+        assert metrics["short_only_returns"] == pytest.approx(1.015 * 1.03 - 1)
+        assert metrics["long_sharpe"] is not None and metrics["short_sharpe"] is not None
+        assert metrics["spread_return"] == pytest.approx(metrics["cumulative_returns"])
+
+    def test_long_only_populates_only_the_long_leg(self):
+        metrics = performance("long_only")
+
+        assert metrics["long_only_returns"] == pytest.approx(1.035 * 1.07 - 1)
+        assert metrics["long_sharpe"] is not None
+        assert metrics["short_only_returns"] is None
+        assert metrics["short_sharpe"] is None
+        assert metrics["spread_return"] is None
+
+    def test_short_only_populates_only_the_short_leg(self):
+        metrics = performance("short_only")
+
+        assert metrics["short_only_returns"] == pytest.approx(1.015 * 1.03 - 1)
+        assert metrics["short_sharpe"] is not None
+        assert metrics["long_only_returns"] is None
+        assert metrics["long_sharpe"] is None
+        assert metrics["spread_return"] is None
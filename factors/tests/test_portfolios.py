@@ -0,0 +1,332 @@
+import numpy as np
+import pandas as pd
+import pytest
+from fastapi import HTTPException
+
+import api
+from utils import EmptyLegError, FactorModel, OverlappingLegsError
+
+
+def period(signals, returns, date="2024-01-07") -> pd.DataFrame:
+    """One rebalance date's rows: asset i has signals[i] and returns[i]"""
+    return pd.DataFrame(
+        {
+            "date": pd.Timestamp(date),
+            "asset": [f"asset{i}" for i in range(len(signals))],
+            "signal": signals,
+            "price_pct_change_p1": returns,
+        }
+    )
+
+
+def model(df: pd.DataFrame, **kwargs) -> FactorModel:
+    return FactorModel(df, "smb", **{"breakpoint": 0.5, "min_assets": 5, "weighting_method": "equal", **kwargs})
+
+
+def test_min_assets_counts_only_rankable_assets_by_default():
+    factor_model = model(period([1.0, 2.0, 3.0, 4.0, np.nan], [0.01, 0.02, 0.03, 0.04, 0.05]))
+
+    factor_model.form_long_short_portfolios("signal")
+
+    assert factor_model.factor_returns == {}
+
+
+def test_count_unrankable_checks_min_assets_before_dropping_missing_signals():
+    factor_model = model(period([1.0, 2.0, 3.0, 4.0, np.nan], [0.01, 0.02, 0.03, 0.04, 0.05]))
+
+    factor_model.form_long_short_portfolios("signal", count_unrankable=True)
+
+    # The missing signal still isn't ranked: the legs split the four others
+    (date,) = factor_model.factor_returns
+    assert factor_model.factor_returns[date] == pytest.approx(0.035 - 0.015)
+    assert factor_model.nan_signal_counts == {date: 1}
+
+
+def holdings(returns, mc=None, variance=None) -> pd.DataFrame:
+    rows = {"price_pct_change_p1": returns}
+    if mc is not None:
+        rows["mc_t_minus_1"] = mc
+    if variance is not None:
+        rows["variance"] = variance
+    return pd.DataFrame(rows)
+
+
+class TestWeightedReturn:
+    def test_equal_weighting_averages_the_returns(self):
+        factor_model = model(pd.DataFrame(), weighting_method="equal")
+
+        assert factor_model.get_weighted_return(holdings([0.01, 0.03])) == pytest.approx(0.02)
+
+    def test_market_cap_weighting_is_proportional_to_prior_market_cap(self):
+        factor_model = model(pd.DataFrame(), weighting_method="market_cap")
+
+        weighted = factor_model.get_weighted_return(holdings([0.01, 0.04], mc=[300.0, 100.0]))
+
+        assert weighted == pytest.approx(0.75 * 0.01 + 0.25 * 0.04)
+
+    def test_market_cap_weighting_leaves_out_holdings_without_a_market_cap(self):
+        factor_model = model(pd.DataFrame(), weighting_method="market_cap")
+
+        weighted = factor_model.get_weighted_return(holdings([0.01, 0.04, 0.5], mc=[300.0, 100.0, np.nan]))
+
+        assert weighted == pytest.approx(0.75 * 0.01 + 0.25 * 0.04)
+
+    def test_inverse_variance_weighting_favors_the_steadier_holding(self):
+        factor_model = model(pd.DataFrame(), weighting_method="inverse_variance")
+
+        weighted = factor_model.get_weighted_return(holdings([0.01, 0.04], variance=[1.0, 3.0]))
+
+        assert weighted == pytest.approx(0.75 * 0.01 + 0.25 * 0.04)
+
+    @pytest.mark.parametrize("weighting_method", ["equal", "market_cap", "inverse_variance"])
+    def test_an_empty_leg_returns_nan(self, weighting_method):
+        factor_model = model(pd.DataFrame(), weighting_method=weighting_method)
+
+        assert np.isnan(factor_model.get_weighted_return(holdings([], mc=[], variance=[])))
+
+    def test_a_leg_without_any_market_cap_returns_nan(self):
+        factor_model = model(pd.DataFrame(), weighting_method="market_cap")
+
+        assert np.isnan(factor_model.get_weighted_return(holdings([0.01, 0.02], mc=[np.nan, np.nan])))
+
+
+def test_a_missing_market_cap_drops_the_asset_not_the_period():
+    df = period([1.0, 2.0, 3.0, 4.0, 5.0, 6.0], [0.01, 0.02, 0.03, 0.04, 0.05, 0.06]).assign(
+        mc_t_minus_1=[100.0, 100.0, 100.0, 100.0, 100.0, np.nan]
+    )
+    factor_model = model(df, weighting_method="market_cap")
+
+    factor_model.form_long_short_portfolios("signal")
+
+    # asset5 can't be weighted, so the five others are ranked: two per leg
+    (date,) = factor_model.factor_returns
+    assert factor_model.factor_returns[date] == pytest.approx(0.045 - 0.015)
+    assert "asset5" not in factor_model.factor_assets[date]["long_portfolio"]
+
+
+class TestUnrankableSignals:
+    # asset0, asset7 and asset8 have no finite signal; the six others split 3/3
+    SIGNALS = [np.nan, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, np.inf, -np.inf]
+    RETURNS = [0.01 * i for i in range(9)]
+
+    def legs(self, factor_model: FactorModel) -> tuple[set, set]:
+        (holdings,) = factor_model.factor_assets.values()
+        return set(holdings["long_portfolio"]), set(holdings["short_portfolio"])
+
+    @pytest.mark.parametrize(
+        "long_high, long_leg, short_leg",
+        [
+            (True, {"asset4", "asset5", "asset6"}, {"asset1", "asset2", "asset3"}),
+            (False, {"asset1", "asset2", "asset3"}, {"asset4", "asset5", "asset6"}),
+        ],
+    )
+    def test_are_left_out_of_both_legs(self, long_high, long_leg, short_leg):
+        factor_model = model(period(self.SIGNALS, self.RETURNS))
+
+        factor_model.form_long_short_portfolios("signal", long_high=long_high)
+
+        assert self.legs(factor_model) == (long_leg, short_leg)
+
+    def test_are_counted_per_period(self):
+        two_periods = [period(self.SIGNALS, self.RETURNS, date) for date in ("2024-01-07", "2024-01-14")]
+        factor_model = model(pd.concat(two_periods))
+
+        factor_model.form_long_short_portfolios("signal")
+
+        assert list(factor_model.nan_signal_counts.values()) == [3, 3]
+
+    def test_dont_change_the_return(self):
+        factor_model = model(period(self.SIGNALS, self.RETURNS))
+
+        factor_model.form_long_short_portfolios("signal")
+
+        (factor_return,) = factor_model.factor_returns.values()
+        assert factor_return == pytest.approx(0.05 - 0.02)
+
+    def test_are_left_out_of_a_long_only_portfolio(self):
+        factor_model = model(period(self.SIGNALS, self.RETURNS))
+
+        factor_model.form_long_only_portfolio("signal", top_n=6)
+
+        long_leg, _ = self.legs(factor_model)
+        assert long_leg == {f"asset{i}" for i in range(1, 7)}
+        assert list(factor_model.nan_signal_counts.values()) == [3]
+
+
+class TestEmptyLegPolicy:
+    def rebalances(self) -> pd.DataFrame:
+        """Five assets split 1/1 at a 0.2 breakpoint, then four (asset2 unlisted) that select no leg"""
+        first = period([1.0, 2.0, 3.0, 4.0, 5.0], [0.01, 0.02, 0.03, 0.04, 0.05], "2024-01-07")
+        second = period([1.0, 2.0, 3.0, 4.0, 5.0], [0.10, 0.20, 0.30, 0.40, 0.50], "2024-01-14")
+        return pd.concat([first, second.drop(index=2)], ignore_index=True)
+
+    def model(self, **kwargs) -> FactorModel:
+        return model(self.rebalances(), breakpoint=0.2, min_assets=4, **kwargs)
+
+    def test_skip_holds_the_prior_legs_through_the_empty_rebalance(self):
+        factor_model = self.model(empty_leg_policy="skip")
+
+        factor_model.form_long_short_portfolios("signal")
+
+        first, second = sorted(factor_model.factor_returns)
+        assert factor_model.empty_leg_dates == [pd.Timestamp("2024-01-14")]
+        assert factor_model.factor_returns[first] == pytest.approx(0.05 - 0.01)
+        assert factor_model.factor_returns[second] == pytest.approx(0.50 - 0.10)
+        assert factor_model.factor_assets[second] == {
+            "long_portfolio": {"asset4": {"weighting": 1.0, "price_pct_change": 0.50}},
+            "short_portfolio": {"asset0": {"weighting": 1.0, "price_pct_change": 0.10}},
+        }
+
+    def test_skip_drops_an_empty_first_rebalance_with_nothing_to_hold(self):
+        factor_model = model(period([1.0, 2.0, 3.0, 4.0], [0.01, 0.02, 0.03, 0.04]), breakpoint=0.2, min_assets=4)
+
+        factor_model.form_long_short_portfolios("signal")
+
+        assert factor_model.empty_leg_dates == [pd.Timestamp("2024-01-07")]
+        assert factor_model.factor_returns == {}
+
+    def test_error_raises_on_the_empty_rebalance(self):
+        factor_model = self.model(empty_leg_policy="error")
+
+        with pytest.raises(EmptyLegError, match="Breakpoint 0.2 selects no assets for a leg on 2024-01-14"):
+            factor_model.form_long_short_portfolios("signal")
+
+        assert factor_model.empty_leg_dates == [pd.Timestamp("2024-01-14")]
+
+    def test_error_is_a_400_from_the_api(self):
+        api._load_utils()
+        factor_model = self.model(empty_leg_policy="error")
+
+        with pytest.raises(HTTPException) as error:
+            api.form_long_short_portfolios(factor_model, "signal", long_high=True)
+
+        assert error.value.status_code == 400
+        assert error.value.detail.startswith("Empty portfolio leg: Breakpoint 0.2")
+
+    def test_skipped_rebalances_are_reported_in_data_quality(self):
+        factor_model = self.model(empty_leg_policy="skip")
+        factor_model.form_long_short_portfolios("signal")
+        data_quality = {}
+
+        api.record_portfolio_quality(factor_model, data_quality)
+
+        assert data_quality["empty_leg_rebalances"] == ["2024-01-14"]
+
+
+class TestLegNetting:
+    DATE = pd.Timestamp("2024-01-07")
+
+    def legs(self, long_assets, long_weights, short_assets, short_weights, returns=None):
+        """Weighted legs as net_legs receives them; every asset returns 0.01 unless given"""
+        returns = returns or {}
+
+        def leg(assets, weights, start):
+            index = range(start, start + len(assets))
+            rows = pd.DataFrame(
+                {"asset": assets, "price_pct_change_p1": [returns.get(asset, 0.01) for asset in assets]}, index=index
+            )
+            return rows, pd.Series(weights, index=index)
+
+        return (*leg(long_assets, long_weights, 0), *leg(short_assets, short_weights, len(long_assets)))
+
+    def netted(self, factor_model, *legs) -> tuple[dict, dict]:
+        """Each netted leg as {asset: weight}"""
+        long_portfolio, long_weights, short_portfolio, short_weights = factor_model.net_legs(self.DATE, *legs)
+        return dict(zip(long_portfolio["asset"], long_weights)), dict(zip(short_portfolio["asset"], short_weights))
+
+    def test_disjoint_legs_are_returned_unchanged(self):
+        factor_model = model(pd.DataFrame())
+        legs = self.legs(["a", "b"], [0.5, 0.5], ["c", "d"], [0.5, 0.5])
+
+        assert all(netted is leg for netted, leg in zip(factor_model.net_legs(self.DATE, *legs), legs))
+        assert factor_model.overlapping_assets == {}
+
+    def test_an_asset_in_both_legs_at_equal_weight_is_dropped(self):
+        factor_model = model(pd.DataFrame())
+
+        long_leg, short_leg = self.netted(factor_model, *self.legs(["a", "b"], [0.5, 0.5], ["b", "c"], [0.5, 0.5]))
+
+        assert long_leg == pytest.approx({"a": 0.5})
+        assert short_leg == pytest.approx({"c": 0.5})
+        assert factor_model.overlapping_assets == {self.DATE: ["b"]}
+
+    @pytest.mark.parametrize(
+        "long_b, short_b, long_leg, short_leg",
+        [
+            (0.75, 0.25, {"a": 0.25, "b": 0.5}, {"c": 0.75}),
+            (0.25, 0.75, {"a": 0.75}, {"c": 0.25, "b": 0.5}),
+        ],
+    )
+    def test_an_asset_in_both_legs_keeps_its_net_weight_on_the_larger_side(self, long_b, short_b, long_leg, short_leg):
+        factor_model = model(pd.DataFrame())
+        legs = self.legs(["a", "b"], [1 - long_b, long_b], ["b", "c"], [short_b, 1 - short_b])
+
+        netted_long, netted_short = self.netted(factor_model, *legs)
+
+        assert netted_long == pytest.approx(long_leg)
+        assert netted_short == pytest.approx(short_leg)
+
+    def test_netting_leaves_long_minus_short_unchanged(self):
+        factor_model = model(pd.DataFrame())
+        returns = {"a": 0.05, "b": 0.02, "c": -0.03}
+        legs = self.legs(["a", "b"], [0.25, 0.75], ["b", "c"], [0.25, 0.75], returns)
+
+        long_portfolio, long_weights, short_portfolio, short_weights = factor_model.net_legs(self.DATE, *legs)
+
+        netted = (long_weights * long_portfolio["price_pct_change_p1"]).sum() - (
+            short_weights * short_portfolio["price_pct_change_p1"]
+        ).sum()
+        assert netted == pytest.approx((0.25 * 0.05 + 0.75 * 0.02) - (0.25 * 0.02 + 0.75 * -0.03))
+
+    def test_an_asset_listed_twice_in_one_leg_is_combined(self):
+        factor_model = model(pd.DataFrame())
+
+        long_leg, short_leg = self.netted(factor_model, *self.legs(["a", "a"], [0.5, 0.5], ["c", "d"], [0.5, 0.5]))
+
+        assert long_leg == pytest.approx({"a": 1.0})
+        assert short_leg == pytest.approx({"c": 0.5, "d": 0.5})
+        assert factor_model.overlapping_assets == {}
+
+    def test_the_error_policy_raises_on_an_overlap(self):
+        factor_model = model(pd.DataFrame(), leg_overlap_policy="error")
+
+        with pytest.raises(OverlappingLegsError, match="^b selected for both legs on 2024-01-07$"):
+            factor_model.net_legs(self.DATE, *self.legs(["a", "b"], [0.5, 0.5], ["b", "c"], [0.5, 0.5]))
+
+        assert factor_model.overlapping_assets == {self.DATE: ["b"]}
+
+    def ranked_with_a_duplicate(self) -> pd.DataFrame:
+        """Six rows where "dup" ranks both first and last, as a ticker listed twice can"""
+        return period([6.0, 5.0, 4.0, 3.0, 2.0, 1.0], [0.10, 0.05, 0.04, 0.03, 0.02, 0.10]).assign(
+            asset=["dup", "a", "b", "c", "d", "dup"]
+        )
+
+    def test_formation_nets_a_duplicate_out_of_both_legs(self):
+        factor_model = model(self.ranked_with_a_duplicate())
+
+        factor_model.form_long_short_portfolios("signal")
+
+        ((date, holdings),) = factor_model.factor_assets.items()
+        assert set(holdings["long_portfolio"]) == {"a", "b"}
+        assert set(holdings["short_portfolio"]) == {"c", "d"}
+        assert factor_model.factor_returns[date] == pytest.approx((0.05 + 0.04 - 0.03 - 0.02) / 3)
+        assert factor_model.overlapping_assets == {date: ["dup"]}
+
+    def test_an_overlap_under_the_error_policy_is_a_400_from_the_api(self):
+        api._load_utils()
+        factor_model = model(self.ranked_with_a_duplicate(), leg_overlap_policy="error")
+
+        with pytest.raises(HTTPException) as error:
+            api.form_long_short_portfolios(factor_model, "signal", long_high=True)
+
+        assert error.value.status_code == 400
+        assert error.value.detail == "Overlapping portfolio legs: dup selected for both legs on 2024-01-07"
+
+    def test_netted_assets_are_reported_in_data_quality(self):
+        factor_model = model(self.ranked_with_a_duplicate())
+        factor_model.form_long_short_portfolios("signal")
+        data_quality = {}
+
+        api.record_portfolio_quality(factor_model, data_quality)
+
+        assert data_quality["overlapping_assets"] == {"2024-01-07": ["dup"]}
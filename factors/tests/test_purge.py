@@ -0,0 +1,59 @@
+import json
+import threading
+from datetime import datetime
+
+import pandas as pd
+
+from utils import Logger, manifest_lock
+
+from .helpers import backdate, weekly_returns
+
+RETURNS = weekly_returns([0.01, -0.02, 0.03])
+
+
+def test_only_runs_older_than_the_window_are_purged(client, seed_run, logs_dir):
+    seed_run("smb", "old", RETURNS, sharpe_ratio=1.0)
+    seed_run("smb", "recent", RETURNS, sharpe_ratio=2.0)
+    backdate(logs_dir, "smb", "old", "2020-01-01T00:00:00")
+
+    response = client.post("/admin/purge", params={"older_than_days": 30})
+
+    assert response.status_code == 200
+    assert response.json()["purged_runs"] == 1
+    assert response.json()["by_factor"] == {"smb": ["old"]}
+    assert pd.read_csv(logs_dir / "smb.csv")["run_id"].tolist() == ["recent"]
+    assert not (logs_dir / "smb_old_returns.csv").exists()
+    assert (logs_dir / "smb_recent_returns.csv").exists()
+    assert set(json.loads((logs_dir / "smb_index.json").read_text())["runs"]) == {"recent"}
+
+
+def test_unindexed_runs_are_aged_by_their_generated_id(client, seed_run, logs_dir):
+    seed_run("smb", "20200101_120000_000", sharpe_ratio=1.0)
+    seed_run("smb", datetime.now().strftime("%Y%m%d_%H%M%S_000"), sharpe_ratio=2.0)
+
+    response = client.post("/admin/purge", params={"older_than_days": 30})
+
+    assert response.json()["by_factor"] == {"smb": ["20200101_120000_000"]}
+    assert pd.read_csv(logs_dir / "smb.csv")["sharpe_ratio"].tolist() == [2.0]
+
+
+def test_purging_again_purges_nothing(client, seed_run, logs_dir):
+    seed_run("smb", "old", RETURNS, sharpe_ratio=1.0)
+    backdate(logs_dir, "smb", "old", "2020-01-01T00:00:00")
+    client.post("/admin/purge", params={"older_than_days": 30})
+
+    response = client.post("/admin/purge", params={"older_than_days": 30})
+
+    assert response.json()["purged_runs"] == 0
+
+
+def test_purge_waits_for_the_manifest_lock(logs_dir):
+    purged = []
+    with manifest_lock(logs_dir, "smb"):
+        purge = threading.Thread(target=lambda: purged.append(Logger.purge(logs_dir, "smb", datetime.now())))
+        purge.start()
+        purge.join(timeout=0.2)
+        assert purge.is_alive()
+    purge.join(timeout=5)
+
+    assert purged == [[]]
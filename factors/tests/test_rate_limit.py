@@ -0,0 +1,64 @@
+import time
+
+import pytest
+
+import api
+
+BODY = {"factor": "smb", "start_date": "2024-01-01", "end_date": "2024-06-30"}
+PER_MINUTE = 3
+
+
+@pytest.fixture
+def rate_limited(monkeypatch):
+    monkeypatch.setattr(api, "API_AUTH_TOKEN", None)
+    monkeypatch.setattr(api, "COMPUTE_RATE_LIMIT_PER_MINUTE", PER_MINUTE)
+    monkeypatch.setattr(api, "COMPUTE_RATE_LIMITER", api.RateLimiter(PER_MINUTE))
+
+
+@pytest.mark.usefixtures("rate_limited")
+class TestComputeRateLimit:
+    def test_the_request_past_the_limit_is_a_429_with_retry_after(self, client, dispatched):
+        responses = [client.post("/compute/smb", json=BODY) for _ in range(PER_MINUTE + 1)]
+
+        assert [response.status_code for response in responses] == [200] * PER_MINUTE + [429]
+        assert len(dispatched) == PER_MINUTE
+        # One token refills every 60 / PER_MINUTE seconds
+        assert 1 <= int(responses[-1].headers["Retry-After"]) <= 60 / PER_MINUTE
+
+    def test_read_routes_are_not_limited(self, client, seed_run):
+        seed_run("smb", "run1", sharpe_ratio=1.0)
+
+        responses = [client.get("/factors/smb/logs") for _ in range(PER_MINUTE + 1)]
+
+        assert all(response.status_code == 200 for response in responses)
+
+    def test_read_routes_still_answer_once_computes_are_limited(self, client, dispatched, seed_run):
+        seed_run("smb", "run1", sharpe_ratio=1.0)
+        for _ in range(PER_MINUTE + 1):
+            client.post("/compute/smb", json=BODY)
+
+        assert client.get("/factors/smb/logs").status_code == 200
+
+
+class TestRateLimiter:
+    @pytest.fixture
+    def clock(self, monkeypatch):
+        now = {"t": 1000.0}
+        monkeypatch.setattr(time, "monotonic", lambda: now["t"])
+        return now
+
+    def test_a_token_refills_after_its_share_of_the_minute(self, clock):
+        limiter = api.RateLimiter(PER_MINUTE)
+        for _ in range(PER_MINUTE):
+            assert limiter.acquire("1.2.3.4") is None
+
+        assert limiter.acquire("1.2.3.4") == pytest.approx(60 / PER_MINUTE)
+        clock["t"] += 60 / PER_MINUTE
+        assert limiter.acquire("1.2.3.4") is None
+
+    def test_clients_have_separate_buckets(self, clock):
+        limiter = api.RateLimiter(1)
+
+        assert limiter.acquire("1.2.3.4") is None
+        assert limiter.acquire("1.2.3.4") is not None
+        assert limiter.acquire("5.6.7.8") is None
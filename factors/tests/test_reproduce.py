@@ -0,0 +1,51 @@
+import pytest
+
+import api
+
+SAVED = api.FactorConfig(factor="smb", start_date="2024-01-01", end_date="2024-06-30").model_dump()
+
+
+def without(*names) -> dict:
+    return {name: value for name, value in SAVED.items() if name not in names}
+
+
+def test_a_config_saved_under_the_current_schema_upgrades_without_warnings():
+    config, _, warnings = api.upgrade_run_config(SAVED, api.CONFIG_SCHEMA_VERSION)
+
+    assert warnings == []
+    assert config.start_date == "2024-01-01"
+
+
+def test_a_field_added_after_the_runs_schema_is_reported_with_its_version():
+    _, _, warnings = api.upgrade_run_config(without("portfolio_mode"), 2)
+
+    assert warnings == [
+        "'portfolio_mode' was added in config schema v3, after this run's v2; "
+        "using the current default 'long_short', which may change results"
+    ]
+
+
+def test_a_field_missing_from_its_own_schema_version_is_reported_as_not_saved():
+    _, _, warnings = api.upgrade_run_config(without("portfolio_mode"), 3)
+
+    assert warnings == [
+        "'portfolio_mode' wasn't saved (config schema v3); using the current default 'long_short', which may change results"
+    ]
+
+
+@pytest.mark.parametrize("name", sorted(api.RESULT_NEUTRAL_CONFIG_FIELDS))
+def test_result_neutral_fields_are_never_warned_about(name):
+    _, _, warnings = api.upgrade_run_config(without(name), 1)
+
+    assert warnings == []
+
+
+def test_a_saved_field_that_no_longer_exists_is_ignored_with_a_warning():
+    _, _, warnings = api.upgrade_run_config({**SAVED, "retired_option": True}, api.CONFIG_SCHEMA_VERSION)
+
+    assert warnings == ["'retired_option' is no longer a config field and was ignored"]
+
+
+def test_every_versioned_field_is_a_config_field_within_the_current_schema():
+    assert set(api.CONFIG_FIELDS_ADDED) <= set(api.FactorConfig.model_fields)
+    assert max(api.CONFIG_FIELDS_ADDED.values()) == api.CONFIG_SCHEMA_VERSION
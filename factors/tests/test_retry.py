@@ -0,0 +1,63 @@
+import pytest
+import requests
+
+from utils import ARTEMIS_MAX_ATTEMPTS, ApiData
+
+
+class StatusError(Exception):
+    """An SDK error carrying the HTTP status of the failed response"""
+
+    def __init__(self, status_code: int):
+        super().__init__(f"HTTP {status_code}")
+        self.status_code = status_code
+
+
+def failing(*errors):
+    """A request raising each error in turn, then returning "ok"; calls counts the attempts"""
+    remaining = list(errors)
+
+    def request():
+        request.calls += 1
+        if remaining:
+            raise remaining.pop(0)
+        return "ok"
+
+    request.calls = 0
+    return request
+
+
+@pytest.fixture
+def api_data() -> ApiData:
+    return ApiData("key", backoff_base=0)
+
+
+def test_two_network_failures_then_success(api_data):
+    request = failing(requests.ConnectionError("reset"), requests.Timeout("slow"))
+
+    assert api_data._with_retry("test", request) == "ok"
+    assert request.calls == 3
+
+
+@pytest.mark.parametrize("error", [TimeoutError(), StatusError(503), StatusError(429)])
+def test_timeouts_and_retryable_statuses_are_retried(api_data, error):
+    request = failing(error)
+
+    assert api_data._with_retry("test", request) == "ok"
+    assert request.calls == 2
+
+
+@pytest.mark.parametrize("error", [KeyError("metrics"), TypeError("bad argument"), StatusError(404)])
+def test_other_errors_are_raised_without_retrying(api_data, error):
+    request = failing(error)
+
+    with pytest.raises(type(error)):
+        api_data._with_retry("test", request)
+    assert request.calls == 1
+
+
+def test_the_last_attempts_error_is_raised(api_data):
+    request = failing(*[requests.ConnectionError("down")] * ARTEMIS_MAX_ATTEMPTS)
+
+    with pytest.raises(requests.ConnectionError):
+        api_data._with_retry("test", request)
+    assert request.calls == ARTEMIS_MAX_ATTEMPTS
@@ -0,0 +1,52 @@
+import json
+
+import pandas as pd
+import pytest
+
+import api
+
+from .helpers import weekly_returns
+from .test_transaction_costs import config, two_rebalances
+
+RETURNS = weekly_returns([0.01 * i for i in range(12)])
+# Logged in an arbitrary order, as a dict built from a merge or a groupby can be
+SHUFFLED = dict(sorted(RETURNS.items(), key=lambda item: (item[0].day % 3, item[0])))
+CHRONOLOGICAL = [date.strftime("%Y-%m-%d") for date in RETURNS]
+
+
+class TestFormatRecentReturns:
+    def test_ordered_pairs_are_chronological(self):
+        ordered = api.format_recent_returns(SHUFFLED, "ordered")
+
+        assert [date for date, _ in ordered] == CHRONOLOGICAL[-10:]
+        assert [value for _, value in ordered] == pytest.approx([0.01 * i for i in range(2, 12)])
+
+    def test_ordered_pairs_keep_their_order_through_json(self):
+        ordered = json.loads(json.dumps(api.format_recent_returns(SHUFFLED, "ordered")))
+
+        assert [date for date, _ in ordered] == CHRONOLOGICAL[-10:]
+
+    def test_the_map_is_keyed_by_iso_date_oldest_first(self):
+        returns_map = api.format_recent_returns(SHUFFLED, "map")
+
+        assert list(returns_map) == CHRONOLOGICAL[-10:]
+        assert returns_map["2024-03-24"] == pytest.approx(0.11)
+
+    def test_a_short_run_returns_every_period(self):
+        assert len(api.format_recent_returns(dict(list(RETURNS.items())[:3]), "ordered")) == 3
+
+    def test_timestamp_keys_with_a_time_are_emitted_as_dates(self):
+        assert api.format_recent_returns({pd.Timestamp("2024-01-07 00:00:00"): 0.5}, "map") == {"2024-01-07": 0.5}
+
+
+@pytest.mark.usefixtures("logs_dir", "no_benchmark")
+class TestComputeResult:
+    def test_the_ordered_variant_is_selected_by_returns_format(self):
+        result = api.finalize_factor_run(api.Factor.SMB, config(returns_format="ordered"), two_rebalances(0), {})
+
+        assert result["returns"] == [["2024-01-07", pytest.approx(0.02)], ["2024-01-14", pytest.approx(-0.02)]]
+
+    def test_the_map_is_the_default(self):
+        result = api.finalize_factor_run(api.Factor.SMB, config(), two_rebalances(0), {})
+
+        assert list(result["returns"]) == ["2024-01-07", "2024-01-14"]
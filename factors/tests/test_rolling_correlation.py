@@ -0,0 +1,74 @@
+import numpy as np
+import pandas as pd
+import pytest
+
+import api
+import stats
+
+from .helpers import daily_returns, weekly_returns
+
+VALUES = [0.01 * (i % 7 - 3) + 0.002 * (i % 3) for i in range(20)]
+
+
+class TestRollingCorrelation:
+    def test_a_known_window_correlation(self):
+        # Deviations (-1.5, -0.5, 0.5, 1.5) and (-1.5, 0.5, -0.5, 1.5): 4 / sqrt(5 * 5)
+        correlation = stats.rolling_correlation(pd.Series([1.0, 2.0, 3.0, 4.0]), pd.Series([1.0, 3.0, 2.0, 4.0]), 4)
+
+        assert correlation.iloc[:3].isna().all()
+        assert correlation.iloc[3] == pytest.approx(0.8)
+
+    def test_linear_series_correlate_perfectly_after_the_first_window(self):
+        x = pd.Series(VALUES)
+
+        assert stats.rolling_correlation(x, 2 * x + 0.01, 5).iloc[4:].tolist() == pytest.approx([1.0] * 16)
+        assert stats.rolling_correlation(x, -x, 5).iloc[4:].tolist() == pytest.approx([-1.0] * 16)
+
+    def test_leading_windows_and_constant_windows_are_nan(self):
+        correlation = stats.rolling_correlation(pd.Series([1.0, 2.0, 3.0, 4.0]), pd.Series([2.0, 2.0, 2.0, 5.0]), 3)
+
+        assert correlation.iloc[:3].isna().all()
+        assert correlation.iloc[3] == pytest.approx(np.corrcoef([2.0, 3.0, 4.0], [2.0, 2.0, 5.0])[0, 1])
+
+
+@pytest.fixture
+def daily_run(seed_run):
+    """A daily smb run, a daily market run with the negated returns and a weekly market run logged after it"""
+    seed_run("smb", "smb_daily", daily_returns(VALUES), rebalance_frequency="daily")
+    seed_run("market", "market_daily", daily_returns([-v for v in VALUES]), rebalance_frequency="daily")
+    seed_run("market", "market_weekly", weekly_returns(VALUES), rebalance_frequency="weekly")
+
+
+@pytest.mark.usefixtures("daily_run")
+class TestBenchmarkFrequency:
+    def test_a_factor_benchmark_is_its_latest_run_at_the_runs_frequency(self, client):
+        response = client.get("/factors/smb/rolling-correlation", params={"to": "market", "window": 5})
+
+        assert response.status_code == 200
+        correlation = response.json()["rolling_correlation"]
+        assert correlation[:4] == [None] * 4
+        assert correlation[4:] == pytest.approx([-1.0] * 16)
+
+    def test_no_benchmark_run_at_the_runs_frequency_is_a_404(self, client, seed_run):
+        seed_run("smb", "smb_monthly", weekly_returns(VALUES), rebalance_frequency="monthly")
+
+        response = client.get(
+            "/factors/smb/rolling-correlation", params={"to": "market", "window": 5, "run_id": "smb_monthly"}
+        )
+
+        assert response.status_code == 404
+        assert "monthly" in response.json()["detail"]
+
+    def test_an_asset_benchmark_is_fetched_at_the_runs_frequency(self, client, monkeypatch):
+        fetched = []
+
+        def fetch(asset, start_date, end_date, rebalance_frequency="weekly"):
+            fetched.append((asset, rebalance_frequency))
+            return pd.Series(daily_returns([2 * v for v in VALUES]))
+
+        monkeypatch.setattr(api, "fetch_asset_returns", fetch)
+
+        response = client.get("/factors/smb/rolling-correlation", params={"to": "bitcoin", "window": 5})
+
+        assert fetched == [("bitcoin", "daily")]
+        assert response.json()["rolling_correlation"][4:] == pytest.approx([1.0] * 16)
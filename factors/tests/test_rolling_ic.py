@@ -0,0 +1,62 @@
+import pytest
+
+import stats
+
+from .helpers import weekly_returns
+
+RETURNS = weekly_returns([0.01 * (i % 5 - 2) for i in range(12)])
+ICS = dict(zip(RETURNS, [0.05, 0.1, -0.02, 0.08, 0.03, 0.12, 0.0, 0.07, -0.04, 0.09, 0.06, 0.02]))
+
+
+@pytest.mark.parametrize("frequency, periods_per_year", [("daily", 365), ("weekly", 52), ("monthly", 12), (None, 52)])
+def test_ic_ir_is_annualized_at_the_runs_frequency(client, seed_run, frequency, periods_per_year):
+    results = {"rebalance_frequency": frequency} if frequency else {}
+    seed_run("smb", "run1", **results).save_time_series(RETURNS, ICS)
+
+    response = client.get("/factors/smb/rolling-ic", params={"window": 4})
+
+    assert response.status_code == 200
+    rolling_ic = response.json()
+    assert rolling_ic["ic_ir"] == pytest.approx(
+        stats.ic_information_ratio(list(ICS.values()), periods_per_year=periods_per_year)
+    )
+    assert rolling_ic["rolling_ic"] == pytest.approx(stats.rolling_mean(list(ICS.values()), 4))
+
+
+def rolling_ic(client, **params) -> dict:
+    response = client.get("/factors/smb/rolling-ic", params=params)
+    assert response.status_code == 200
+    return response.json()
+
+
+class TestRollingAverage:
+    @pytest.fixture(autouse=True)
+    def persisted(self, seed_run):
+        returns = dict(list(RETURNS.items())[:6])
+        seed_run("smb", "run1").save_time_series(returns, {date: ICS[date] for date in returns})
+
+    def test_averages_the_persisted_ics_over_each_trailing_window(self, client):
+        result = rolling_ic(client, window=3)
+
+        assert result["dates"] == ["2024-01-07", "2024-01-14", "2024-01-21", "2024-01-28", "2024-02-04", "2024-02-11"]
+        assert result["ic"] == pytest.approx([0.05, 0.1, -0.02, 0.08, 0.03, 0.12])
+        assert result["rolling_ic"][:2] == [None, None]
+        assert result["rolling_ic"][2:] == pytest.approx([0.13 / 3, 0.16 / 3, 0.09 / 3, 0.23 / 3])
+        assert result["mean_ic"] == pytest.approx(0.36 / 6)
+
+    def test_a_window_of_one_is_the_ics_themselves(self, client):
+        assert rolling_ic(client, window=1)["rolling_ic"] == pytest.approx([0.05, 0.1, -0.02, 0.08, 0.03, 0.12])
+
+    def test_a_window_longer_than_the_run_is_all_null(self, client):
+        assert rolling_ic(client, window=7)["rolling_ic"] == [None] * 6
+
+
+def test_windows_containing_a_missing_ic_are_null(client, seed_run):
+    returns = dict(list(RETURNS.items())[:5])
+    ics = dict(zip(returns, [0.05, 0.1, None, 0.08, 0.03]))
+    seed_run("smb", "run1").save_time_series(returns, ics)
+
+    result = rolling_ic(client, window=2)
+
+    assert result["ic"][2] is None
+    assert result["rolling_ic"] == [None, pytest.approx(0.075), None, None, pytest.approx(0.055)]
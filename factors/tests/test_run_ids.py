@@ -0,0 +1,94 @@
+import re
+import threading
+from datetime import datetime
+
+import pandas as pd
+import pytest
+
+import api
+from utils import FactorModel, Logger, generate_run_id, parse_run_id_time
+
+from .helpers import weekly_returns
+
+RUN_ID = re.compile(r"^\d{8}_\d{6}_\d{3}_[0-9a-f]{8}$")
+
+
+def test_ids_from_a_tight_loop_sort_in_creation_order():
+    run_ids = [generate_run_id() for _ in range(1000)]
+
+    assert sorted(run_ids) == run_ids
+    assert len(set(run_ids)) == len(run_ids)
+    assert all(RUN_ID.match(run_id) for run_id in run_ids)
+
+
+def test_ids_from_concurrent_threads_are_unique():
+    run_ids = []
+
+    def generate():
+        run_ids.extend(generate_run_id() for _ in range(200))
+
+    threads = [threading.Thread(target=generate) for _ in range(8)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+
+    assert len(set(run_ids)) == 1600
+    # The timestamps alone are unique too, so sorting is unambiguous
+    assert len({run_id.rsplit("_", 1)[0] for run_id in run_ids}) == 1600
+
+
+def test_the_creation_time_is_recovered_from_every_id_format():
+    assert parse_run_id_time("20240107_093015_250_1a2b3c4d") == datetime(2024, 1, 7, 9, 30, 15, 250000)
+    assert parse_run_id_time("20240107_093015_250") == datetime(2024, 1, 7, 9, 30, 15, 250000)
+    assert parse_run_id_time("20240107_093015") == datetime(2024, 1, 7, 9, 30, 15)
+    assert parse_run_id_time("20240107_093015_0123456789abcdef0123456789abcdef") == datetime(2024, 1, 7, 9, 30, 15)
+
+
+def test_a_generated_id_parses_to_about_now():
+    assert abs((parse_run_id_time(generate_run_id()) - datetime.now()).total_seconds()) < 5
+
+
+def test_custom_ids_have_no_creation_time():
+    assert parse_run_id_time("my-backtest") is None
+    assert parse_run_id_time("baseline_deadbeef") is None
+    assert parse_run_id_time(None) is None
+
+
+def compute(client, factor: str, run_id: str):
+    return client.post(
+        f"/compute/{factor}",
+        json={"factor": factor, "start_date": "2024-01-01", "end_date": "2024-06-30", "run_id": run_id},
+    )
+
+
+@pytest.fixture
+def unfetched(monkeypatch):
+    """Fail any data fetch, so a request that gets that far shows up as a 502 rather than a 400"""
+
+    def fetch(*args, **kwargs):
+        raise api.HTTPException(status_code=502, detail="fetched")
+
+    monkeypatch.setattr(api, "fetch_factor_data", fetch)
+
+
+@pytest.mark.usefixtures("unfetched")
+class TestDuplicateCustomRunId:
+    def test_an_id_already_logged_for_the_factor_is_a_400(self, client, seed_run):
+        seed_run("smb", "baseline", sharpe_ratio=1.0)
+
+        response = compute(client, "smb", "baseline")
+
+        assert response.status_code == 400
+        assert response.json()["detail"] == "Run id 'baseline' already exists for factor 'smb'"
+
+    def test_an_id_with_only_a_saved_time_series_is_a_400(self, client, logs_dir):
+        model = FactorModel(pd.DataFrame(), "smb", run_id="orphan")
+        Logger(logs_dir, model).save_time_series(weekly_returns([0.01, 0.02]))
+
+        assert compute(client, "smb", "orphan").status_code == 400
+
+    def test_the_same_id_is_free_for_another_factor(self, client, seed_run):
+        seed_run("smb", "baseline", sharpe_ratio=1.0)
+
+        assert compute(client, "market", "baseline").status_code == 502
@@ -0,0 +1,56 @@
+import pytest
+
+from .helpers import backdate, weekly_returns
+
+RETURNS = weekly_returns([0.01, -0.02, 0.03])
+
+
+@pytest.fixture(autouse=True)
+def runs(seed_run, logs_dir):
+    """Three smb and two momentum runs across 2023-2024, one without a Sharpe ratio"""
+    for factor, run_id, created_at, sharpe in [
+        ("smb", "smb_old", "2023-06-01T00:00:00", 2.5),
+        ("smb", "smb_weak", "2024-03-01T00:00:00", 0.4),
+        ("smb", "smb_strong", "2024-05-01T00:00:00", 1.8),
+        ("momentum", "mom_strong", "2024-04-01T00:00:00", 1.2),
+        ("momentum", "mom_unrated", "2024-06-01T00:00:00", None),
+    ]:
+        results = {} if sharpe is None else {"sharpe_ratio": sharpe}
+        seed_run(factor, run_id, RETURNS, annualized_return=0.1, **results)
+        backdate(logs_dir, factor, run_id, created_at)
+
+
+def run_ids(client, **params) -> list:
+    response = client.get("/runs", params=params)
+    assert response.status_code == 200
+    return [run["run_id"] for run in response.json()["runs"]]
+
+
+def test_combined_filters_return_the_matching_runs_of_both_factors(client):
+    assert run_ids(client, factor="smb,momentum", start_after="2024-01-01", min_sharpe=1.0) == [
+        "smb_strong",
+        "mom_strong",
+    ]
+
+
+def test_each_filter_narrows_the_listing(client):
+    assert len(run_ids(client)) == 5
+    assert run_ids(client, factor="momentum") == ["mom_unrated", "mom_strong"]
+    assert run_ids(client, start_after="2024-04-15") == ["mom_unrated", "smb_strong"]
+    assert set(run_ids(client, min_sharpe=1.5)) == {"smb_old", "smb_strong"}
+
+
+def test_sorting_by_a_metric_puts_runs_without_it_last(client):
+    assert run_ids(client, sort_by="sharpe_ratio") == ["smb_old", "smb_strong", "mom_strong", "smb_weak", "mom_unrated"]
+    assert run_ids(client, sort_by="sharpe_ratio", order="asc")[-1] == "mom_unrated"
+
+
+def test_pages_share_the_total(client):
+    page = client.get("/runs", params={"sort_by": "sharpe_ratio", "offset": 1, "limit": 2}).json()
+
+    assert page["total"] == 5
+    assert [run["run_id"] for run in page["runs"]] == ["smb_strong", "mom_strong"]
+
+
+def test_an_unknown_factor_is_a_404(client):
+    assert client.get("/runs", params={"factor": "smb,not_a_factor"}).status_code == 404
@@ -0,0 +1,60 @@
+import pandas as pd
+import pytest
+
+from .helpers import daily_returns
+
+# Two years of daily returns: January earns 2% a day, every other month loses 0.1%
+DATES = pd.date_range("2022-01-01", "2023-12-31", freq="D")
+MONTHLY_PATTERN = daily_returns([0.02 if date.month == 1 else -0.001 for date in DATES], start="2022-01-01")
+
+
+def seasonality(client, **params) -> dict:
+    response = client.get("/factors/smb/seasonality", params=params)
+    assert response.status_code == 200
+    return response.json()
+
+
+def test_the_elevated_month_shows_the_higher_average(client, seed_run):
+    seed_run("smb", "run1", MONTHLY_PATTERN, rebalance_frequency="daily")
+
+    buckets = {bucket["bucket"]: bucket for bucket in seasonality(client)["buckets"]}
+
+    assert list(buckets)[0] == "January"
+    assert len(buckets) == 12
+    assert buckets["January"]["mean_return"] == pytest.approx(0.02)
+    assert buckets["January"]["num_observations"] == 62
+    assert max(buckets, key=lambda name: buckets[name]["mean_return"]) == "January"
+    for name in set(buckets) - {"January"}:
+        assert buckets[name]["mean_return"] == pytest.approx(-0.001)
+        assert buckets[name]["std_return"] == pytest.approx(0.0)
+
+
+def test_day_of_week_buckets_surface_a_weekday_effect(client, seed_run):
+    mondays_up = daily_returns([0.01 if date.dayofweek == 0 else 0.0 for date in DATES], start="2022-01-01")
+    seed_run("smb", "run1", mondays_up, rebalance_frequency="daily")
+
+    buckets = seasonality(client, by="day_of_week")["buckets"]
+
+    assert [bucket["bucket"] for bucket in buckets] == [
+        "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday",
+    ]
+    assert [bucket["mean_return"] for bucket in buckets] == pytest.approx([0.01, 0, 0, 0, 0, 0, 0])
+
+
+def test_buckets_with_too_few_observations_are_flagged(client, seed_run):
+    # January 1 to February 9: 31 January days and 9 February ones
+    seed_run("smb", "run1", daily_returns([0.001] * 40, start="2024-01-01"), rebalance_frequency="daily")
+
+    buckets = seasonality(client, min_observations=10)["buckets"]
+
+    flagged = [(bucket["bucket"], bucket["num_observations"], bucket["sufficient_observations"]) for bucket in buckets]
+    assert flagged == [("January", 31, True), ("February", 9, False)]
+
+
+def test_a_single_observation_has_no_std(client, seed_run):
+    seed_run("smb", "run1", {pd.Timestamp("2024-01-07"): 0.01, pd.Timestamp("2024-02-04"): 0.02})
+
+    buckets = seasonality(client, min_observations=1)["buckets"]
+
+    assert [bucket["std_return"] for bucket in buckets] == [None, None]
+    assert all(bucket["sufficient_observations"] for bucket in buckets)
@@ -0,0 +1,89 @@
+import pandas as pd
+import pytest
+
+import api
+from utils import FactorModel
+
+WEEKS = pd.date_range("2024-01-07", periods=5, freq="W")
+# Equal market caps, so value ranks on fees alone: protocol_a is the cheapest, protocol_f the most expensive
+WEEKLY_FEES = {f"protocol_{letter}": fees for letter, fees in zip("abcdef", [6e6, 5e6, 4e6, 3e6, 2e6, 1e6])}
+CHEAP = {"protocol_a", "protocol_b", "protocol_c"}
+EXPENSIVE = {"protocol_d", "protocol_e", "protocol_f"}
+
+
+def value_panel() -> pd.DataFrame:
+    """The weekly rows fetch_factor_data would return for the value factor, indexed by date"""
+    rows = [
+        {"date": date, "asset": asset, "price": 100.0 + i, "mc": 1e9, "24h_volume": 1e8, "fees": fees}
+        for asset, fees in WEEKLY_FEES.items()
+        for i, date in enumerate(WEEKS)
+    ]
+    return pd.DataFrame(rows).set_index("date")
+
+
+@pytest.fixture
+def value_run(monkeypatch):
+    """Run the value factor on the panel, returning its FactorModel and logged extras instead of finalizing"""
+    monkeypatch.setattr(api, "fetch_factor_data", lambda config, metrics, data_quality, **kwargs: value_panel())
+    # The panel is already on the weekly grid
+    monkeypatch.setattr(FactorModel, "resample", lambda self, freq, metric_agg_methods: None)
+    monkeypatch.setattr(api, "ensure_run_id_available", lambda factor, run_id: None)
+    monkeypatch.setattr(
+        api,
+        "finalize_factor_run",
+        lambda factor, config, factor_model, data_quality, **kwargs: (factor_model, kwargs["log_extra"]),
+    )
+
+    def run(**fields):
+        config = api.FactorConfig(
+            factor="value",
+            start_date="2024-01-01",
+            end_date="2024-02-29",
+            min_assets=6,
+            market_cap_threshold=0,
+            liquidity_threshold=0,
+            min_lifetime_days=0,
+            **fields,
+        )
+        return api.run_value_factor(config)
+
+    return run
+
+
+def legs(factor_model) -> list[tuple[set, set]]:
+    """Each rebalance's (long, short) assets, oldest first"""
+    return [
+        (set(holdings["long_portfolio"]), set(holdings["short_portfolio"]))
+        for _, holdings in sorted(factor_model.factor_assets.items())
+    ]
+
+
+def test_value_longs_the_cheapest_protocols_by_default(value_run):
+    factor_model, log_extra = value_run()
+
+    assert legs(factor_model)
+    assert all(long_leg == CHEAP and short_leg == EXPENSIVE for long_leg, short_leg in legs(factor_model))
+    assert log_extra["signal_direction"] == "low"
+
+
+def test_an_explicit_low_direction_matches_the_default(value_run):
+    default, _ = value_run()
+    low, _ = value_run(signal_direction="low")
+
+    assert legs(low) == legs(default)
+
+
+def test_a_high_direction_swaps_the_legs(value_run):
+    low, _ = value_run(signal_direction="low")
+    high, log_extra = value_run(signal_direction="high")
+
+    assert legs(high) == [(short_leg, long_leg) for long_leg, short_leg in legs(low)]
+    assert log_extra["signal_direction"] == "high"
+
+
+def test_signal_long_high_falls_back_to_the_factor_convention():
+    config = api.FactorConfig(factor="value", start_date="2024-01-01", end_date="2024-02-29")
+
+    assert api.signal_long_high(config, api.Factor.VALUE) is False
+    assert api.signal_long_high(config, api.Factor.MOMENTUM) is True
+    assert api.signal_long_high(config.model_copy(update={"signal_direction": "high"}), api.Factor.VALUE) is True
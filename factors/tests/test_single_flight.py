@@ -0,0 +1,53 @@
+import asyncio
+import threading
+
+import api
+
+
+class CountingCompute:
+    """A blocking compute that counts its calls and holds until released, so requests overlap"""
+
+    def __init__(self):
+        self.calls = 0
+        self.release = threading.Event()
+
+    def __call__(self, value):
+        self.calls += 1
+        self.release.wait(timeout=5)
+        return {"value": value, "call": self.calls}
+
+
+async def run_concurrently(compute: CountingCompute, *params: dict) -> list:
+    tasks = [asyncio.create_task(api.run_single_flight("smb", p, compute, p["value"])) for p in params]
+    await asyncio.sleep(0.1)
+    compute.release.set()
+    return await asyncio.gather(*tasks)
+
+
+def test_identical_concurrent_requests_compute_once():
+    compute = CountingCompute()
+
+    first, second = asyncio.run(run_concurrently(compute, {"value": 1}, {"value": 1}))
+
+    assert compute.calls == 1
+    assert first == second == {"value": 1, "call": 1}
+
+
+def test_different_requests_compute_separately():
+    compute = CountingCompute()
+
+    first, second = asyncio.run(run_concurrently(compute, {"value": 1}, {"value": 2}))
+
+    assert compute.calls == 2
+    assert {first["value"], second["value"]} == {1, 2}
+
+
+def test_a_finished_computation_isnt_reused():
+    compute = CountingCompute()
+    compute.release.set()
+
+    asyncio.run(run_concurrently(compute, {"value": 1}))
+    asyncio.run(run_concurrently(compute, {"value": 1}))
+
+    assert compute.calls == 2
+    assert api._inflight_computations == {}
@@ -0,0 +1,71 @@
+import os
+import threading
+
+import pytest
+
+import api
+
+
+@pytest.fixture
+def read_only_dir(tmp_path):
+    directory = tmp_path / "logs"
+    directory.mkdir()
+    directory.chmod(0o555)
+    yield directory
+    directory.chmod(0o755)
+
+
+def test_a_writable_logs_dir_is_created(tmp_path):
+    log_dir = tmp_path / "nested" / "logs"
+
+    api.ensure_logs_dir_writable(log_dir)
+
+    assert log_dir.is_dir()
+    assert list(log_dir.iterdir()) == []
+
+
+@pytest.mark.skipif(hasattr(os, "geteuid") and os.geteuid() == 0, reason="root can write to a read-only directory")
+def test_a_read_only_logs_dir_fails_naming_the_directory(read_only_dir):
+    with pytest.raises(RuntimeError, match=f"FACTOR_LOGS_DIR '{read_only_dir}' is not writable"):
+        api.ensure_logs_dir_writable(read_only_dir)
+
+
+def test_a_logs_dir_that_cant_be_created_fails_naming_the_directory(tmp_path):
+    (tmp_path / "file").write_text("")
+    log_dir = tmp_path / "file" / "logs"
+
+    with pytest.raises(RuntimeError, match="point FACTOR_LOGS_DIR at a writable directory"):
+        api.ensure_logs_dir_writable(log_dir)
+
+
+def test_the_app_fails_at_startup_without_a_writable_logs_dir(tmp_path, monkeypatch):
+    from fastapi.testclient import TestClient
+
+    (tmp_path / "file").write_text("")
+    monkeypatch.setattr(api, "FACTOR_LOGS_DIR", tmp_path / "file" / "logs")
+
+    with pytest.raises(RuntimeError, match="is not writable"):
+        with TestClient(api.app):
+            pass
+
+
+def test_concurrent_checks_of_a_missing_logs_dir_all_succeed(tmp_path):
+    log_dir = tmp_path / "nested" / "logs"
+    barrier = threading.Barrier(8)
+    errors = []
+
+    def check():
+        barrier.wait()
+        try:
+            api.ensure_logs_dir_writable(log_dir)
+        except Exception as e:
+            errors.append(e)
+
+    threads = [threading.Thread(target=check) for _ in range(8)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+
+    assert errors == []
+    assert list(log_dir.iterdir()) == []
@@ -0,0 +1,274 @@
+import numpy as np
+import pandas as pd
+import pytest
+
+import stats
+
+# A known price path: +10%, -50%, +100%, then a missing price
+PRICES = [100.0, 110.0, 55.0, 110.0]
+
+
+class TestReturns:
+    def test_simple_returns_over_a_known_path(self):
+        returns = stats.simple_returns(PRICES)
+
+        assert np.isnan(returns[0])
+        assert returns[1:].tolist() == pytest.approx([0.1, -0.5, 1.0])
+
+    def test_simple_returns_over_several_periods(self):
+        returns = stats.simple_returns(PRICES, periods=2)
+
+        assert returns.isna().tolist() == [True, True, False, False]
+        assert returns[2:].tolist() == pytest.approx([-0.45, 0.0])
+
+    def test_a_missing_or_non_positive_prior_price_gives_nan(self):
+        returns = stats.simple_returns([100.0, np.nan, 50.0, 0.0, 10.0])
+
+        assert returns.isna().tolist() == [True, True, True, False, True]
+        assert returns[3] == -1.0
+
+    def test_log_returns_over_a_known_path(self):
+        returns = stats.log_returns(PRICES)
+
+        assert np.isnan(returns[0])
+        assert returns[1:].tolist() == pytest.approx([np.log(1.1), np.log(0.5), np.log(2.0)])
+
+    def test_log_returns_sum_to_the_log_of_the_total_return(self):
+        assert stats.log_returns(PRICES).sum() == pytest.approx(np.log(110.0 / 100.0))
+
+    def test_cumulative_returns_compound(self):
+        cumulative = stats.cumulative_returns(stats.simple_returns(PRICES))
+
+        assert np.isnan(cumulative[0])
+        assert cumulative[1:].tolist() == pytest.approx([0.1, -0.45, 0.1])
+
+    def test_a_missing_return_after_the_first_carries_the_cumulative_return(self):
+        cumulative = stats.cumulative_returns([0.1, np.nan, 0.1])
+
+        assert cumulative.tolist() == pytest.approx([0.1, 0.1, 0.21])
+
+    def test_the_index_is_preserved(self):
+        prices = pd.Series(PRICES, index=pd.date_range("2024-01-07", periods=4, freq="7D"))
+
+        assert stats.simple_returns(prices).index.equals(prices.index)
+
+
+class TestSortinoRatio:
+    def test_divides_the_mean_by_the_std_of_the_losing_periods(self):
+        returns = [0.04, -0.01, 0.02, -0.03]
+
+        # mean 0.005; losing periods -0.01, -0.03 have a sample std of sqrt(0.0002)
+        expected = 0.005 / np.sqrt(0.0002) * np.sqrt(52)
+        assert stats.sortino_ratio(returns) == pytest.approx(expected)
+
+    def test_gains_do_not_shrink_the_downside_deviation(self):
+        returns = [0.02, 0.02, 0.02, 0.02, -0.01, -0.03]
+
+        # Only the two losing periods enter the deviation, however many periods gained
+        expected = np.mean(returns) / np.std([-0.01, -0.03], ddof=1) * np.sqrt(52)
+        assert stats.sortino_ratio(returns) == pytest.approx(expected)
+
+    def test_risk_free_shifts_which_periods_lose(self):
+        returns = [0.03, 0.005, 0.0, 0.02]
+
+        # Against 1% per period, 0.005 and 0.0 are the losing periods
+        expected = (0.01375 - 0.01) / np.std([-0.005, -0.01], ddof=1) * np.sqrt(12)
+        assert stats.sortino_ratio(returns, periods_per_year=12, risk_free=0.01) == pytest.approx(expected)
+
+    def test_is_none_without_a_losing_period(self):
+        assert stats.sortino_ratio([0.01, 0.02, 0.0]) is None
+
+    def test_is_none_with_a_single_losing_period(self):
+        assert stats.sortino_ratio([0.01, -0.02, 0.03]) is None
+
+
+class TestRollingAlignment:
+    RETURNS = [0.01, 0.03, -0.02, 0.04]
+
+    def test_the_first_window_minus_one_entries_are_none(self):
+        means = stats.rolling_mean(self.RETURNS, 3)
+
+        assert means[:2] == [None, None]
+        assert means[2:] == pytest.approx([0.02 / 3, 0.05 / 3])
+
+    def test_the_output_is_as_long_as_the_input(self):
+        for rolled in (
+            stats.rolling_mean(self.RETURNS, 2),
+            stats.rolling_std(self.RETURNS, 2),
+            stats.rolling_volatility(self.RETURNS, 2),
+            stats.rolling_sharpe(self.RETURNS, 2),
+        ):
+            assert len(rolled) == len(self.RETURNS)
+            assert rolled[0] is None and rolled[1] is not None
+
+    def test_a_window_longer_than_the_series_is_all_none(self):
+        assert stats.rolling_mean(self.RETURNS, 10) == [None] * 4
+        assert stats.rolling_sharpe(self.RETURNS, 10) == [None] * 4
+
+    def test_a_window_of_one_is_accepted(self):
+        assert stats.rolling_mean(self.RETURNS, 1) == pytest.approx(self.RETURNS)
+        assert stats.rolling_std(self.RETURNS, 1) == [None] * 4
+        assert stats.rolling_sharpe(self.RETURNS, 1) == [None] * 4
+
+    def test_a_window_containing_a_missing_value_is_none(self):
+        assert stats.rolling_mean([0.01, np.nan, 0.03, 0.05], 2) == [None, None, None, pytest.approx(0.04)]
+
+    def test_a_window_below_one_is_rejected(self):
+        with pytest.raises(ValueError):
+            stats.rolling_mean(self.RETURNS, 0)
+
+
+class TestLeaveOneOutZscore:
+    def test_scores_each_value_against_the_others(self):
+        zscores = stats.leave_one_out_zscore([1.0, 2.0, 3.0, 10.0])
+
+        # 10 against 1, 2, 3 (mean 2, std 1); 1 against 2, 3, 10 (mean 5, std sqrt(19))
+        assert zscores[3] == pytest.approx(8.0)
+        assert zscores[0] == pytest.approx(-4 / np.sqrt(19))
+
+    def test_a_single_extreme_value_is_not_capped(self):
+        values = [0.01, -0.01] * 5 + [0.5]
+
+        assert abs(stats.zscore(values).iloc[-1]) < 10 / np.sqrt(11) + 1e-9
+        assert stats.leave_one_out_zscore(values).iloc[-1] > 40
+
+    def test_missing_values_stay_missing_and_are_left_out(self):
+        zscores = stats.leave_one_out_zscore([1.0, np.nan, 2.0, 3.0, 10.0])
+
+        assert np.isnan(zscores[1])
+        assert zscores[4] == pytest.approx(8.0)
+
+    def test_fewer_than_three_values_score_nan(self):
+        assert stats.leave_one_out_zscore([1.0, 5.0]).isna().all()
+
+    def test_a_value_whose_others_do_not_vary_scores_nan(self):
+        zscores = stats.leave_one_out_zscore([0.01, 0.01, 0.01, 0.3])
+
+        assert np.isnan(zscores[3])
+
+
+class TestInformationRatio:
+    ACTIVE = [0.01, -0.01, 0.03, 0.01]
+
+    def test_tracking_error_is_the_annualized_std_of_active_returns(self):
+        # mean 0.01; squared deviations 0, 0.0004, 0.0004, 0 over 3 degrees of freedom
+        assert stats.tracking_error(self.ACTIVE) == pytest.approx(np.sqrt(0.0008 / 3) * np.sqrt(52))
+
+    def test_divides_the_annualized_mean_by_the_tracking_error(self):
+        expected = 0.01 * 52 / (np.sqrt(0.0008 / 3) * np.sqrt(52))
+
+        assert stats.information_ratio(self.ACTIVE) == pytest.approx(expected)
+        assert stats.information_ratio(self.ACTIVE) == pytest.approx(4.4159, abs=1e-4)
+
+    def test_annualizes_for_the_given_periods(self):
+        expected = 0.01 * 12 / (np.sqrt(0.0008 / 3) * np.sqrt(12))
+
+        assert stats.information_ratio(self.ACTIVE, periods_per_year=12) == pytest.approx(expected)
+
+    def test_is_none_for_a_tracking_error_of_zero_up_to_rounding(self):
+        assert stats.information_ratio([0.01, 0.01, 0.01]) is None
+        assert stats.information_ratio([0.01, 0.01 + 1e-15, 0.01]) is None
+
+    def test_is_none_below_two_active_returns(self):
+        assert stats.tracking_error([0.01]) is None
+        assert stats.information_ratio([0.01, np.nan]) is None
+
+
+class TestWinsorize:
+    # Eleven values, so the 10% and 90% quantiles fall exactly on the second and tenth
+    VALUES = [-500.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 1000.0]
+
+    def test_clamps_known_outliers_to_the_percentiles(self):
+        winsorized = stats.winsorize(self.VALUES, 0.1, 0.1)
+
+        assert winsorized.tolist() == [2.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 10.0]
+
+    def test_each_tail_has_its_own_percentile(self):
+        winsorized = stats.winsorize(self.VALUES, 0.0, 0.1)
+
+        assert winsorized.iloc[0] == -500.0
+        assert winsorized.iloc[-1] == 10.0
+
+    def test_missing_values_stay_missing(self):
+        winsorized = stats.winsorize([*self.VALUES, np.nan], 0.1, 0.1)
+
+        assert np.isnan(winsorized.iloc[-1])
+        assert winsorized.iloc[-2] == 10.0
+
+    @pytest.mark.parametrize("pct", [-0.1, 0.5, 0.7])
+    def test_a_percentile_outside_the_lower_half_is_rejected(self, pct):
+        with pytest.raises(ValueError):
+            stats.winsorize(self.VALUES, pct, 0.1)
+
+    def test_cross_sectional_winsorize_clamps_within_each_date(self):
+        df = pd.DataFrame(
+            {
+                "date": ["2024-01-07"] * 11 + ["2024-01-14"] * 11,
+                "signal": self.VALUES + [value * 10 for value in self.VALUES],
+            }
+        )
+
+        winsorized = stats.cross_sectional_winsorize(df, "signal", 0.1)
+
+        assert winsorized.iloc[:11].max() == 10.0
+        assert winsorized.iloc[11:].tolist()[-1] == 100.0
+        assert winsorized.iloc[11:].tolist()[0] == 20.0
+
+
+class TestZscore:
+    def test_standardizes_to_mean_zero_and_unit_sample_std(self):
+        zscores = stats.zscore([1.0, 2.0, 3.0, 4.0])
+
+        # mean 2.5, sample std sqrt(5 / 3)
+        assert zscores.tolist() == pytest.approx([(v - 2.5) / np.sqrt(5 / 3) for v in (1.0, 2.0, 3.0, 4.0)])
+
+    def test_constant_input_scores_zero(self):
+        assert stats.zscore([0.3, 0.3, 0.3]).tolist() == [0.0, 0.0, 0.0]
+
+    def test_missing_values_stay_missing(self):
+        zscores = stats.zscore([1.0, np.nan, 3.0])
+
+        assert np.isnan(zscores[1])
+        assert zscores[[0, 2]].tolist() == pytest.approx([-1 / np.sqrt(2), 1 / np.sqrt(2)])
+
+    def test_a_single_value_stays_nan(self):
+        assert stats.zscore([5.0]).isna().all()
+
+    def test_robust_zscore_scales_the_distance_from_the_median_by_the_mad(self):
+        # median 3, absolute deviations 2, 1, 0, 1, 97: MAD 1
+        zscores = stats.robust_zscore([1.0, 2.0, 3.0, 4.0, 100.0])
+
+        assert zscores.tolist() == pytest.approx([v / stats.MAD_TO_STD for v in (-2.0, -1.0, 0.0, 1.0, 97.0)])
+
+    def test_robust_zscore_of_constant_input_scores_zero(self):
+        assert stats.robust_zscore([2.0, 2.0, 2.0, np.nan]).tolist()[:3] == [0.0, 0.0, 0.0]
+
+
+class TestCrossSectionalZscore:
+    def frame(self, signals: dict) -> pd.DataFrame:
+        return pd.DataFrame(
+            [{"date": date, "signal": value} for date, values in signals.items() for value in values]
+        )
+
+    def test_matches_zscore_within_each_date(self):
+        df = self.frame({"2024-01-07": [1.0, 2.0, 3.0, 4.0], "2024-01-14": [10.0, -5.0, np.nan, 2.0]})
+
+        zscores = stats.cross_sectional_zscore(df, "signal")
+
+        for _, group in df.groupby("date"):
+            expected = stats.zscore(group["signal"])
+            assert zscores[group.index].tolist() == pytest.approx(expected.tolist(), nan_ok=True)
+
+    def test_a_constant_date_scores_zero_and_keeps_missing_values(self):
+        df = self.frame({"2024-01-07": [0.3, 0.3, np.nan], "2024-01-14": [1.0, 3.0, 5.0]})
+
+        zscores = stats.cross_sectional_zscore(df, "signal")
+
+        assert zscores.iloc[:2].tolist() == [0.0, 0.0]
+        assert np.isnan(zscores.iloc[2])
+        assert zscores.iloc[3:].tolist() == pytest.approx([-1.0, 0.0, 1.0])
+
+    def test_a_date_with_a_single_value_stays_nan(self):
+        df = self.frame({"2024-01-07": [1.0], "2024-01-14": [1.0, 3.0]})
+
+        assert np.isnan(stats.cross_sectional_zscore(df, "signal")[0])
@@ -0,0 +1,94 @@
+import asyncio
+import threading
+
+import pytest
+
+import api
+
+PARAMS = {"value": 1}
+
+
+class ReportingCompute:
+    """A blocking compute that counts its calls and reports progress until released or cancelled"""
+
+    def __init__(self):
+        self.calls = 0
+        self.release = threading.Event()
+
+    def __call__(self):
+        self.calls += 1
+        while not self.release.wait(timeout=0.01):
+            api.report_compute_progress("computing")
+        return {"call": self.calls}
+
+
+def test_a_stream_and_a_post_share_one_compute():
+    compute = ReportingCompute()
+
+    async def run():
+        stream = api.start_single_flight("smb", PARAMS, compute, stream=True)
+        queue = stream.progress.subscribe()
+        post = asyncio.create_task(api.run_single_flight("smb", PARAMS, compute))
+        event = await asyncio.wait_for(queue.get(), timeout=5)
+        compute.release.set()
+        return event, await stream.task, await post
+
+    event, streamed, posted = asyncio.run(run())
+
+    assert compute.calls == 1
+    assert event["phase"] == "computing"
+    assert streamed == posted == {"call": 1}
+    assert api._inflight_computations == {}
+
+
+def test_the_last_stream_leaving_cancels_the_run():
+    compute = ReportingCompute()
+
+    async def run():
+        stream = api.start_single_flight("smb", PARAMS, compute, stream=True)
+        first, second = stream.progress.subscribe(), stream.progress.subscribe()
+        stream.progress.unsubscribe(first)
+        assert not stream.progress.stop_event.disconnected()
+        stream.progress.unsubscribe(second)
+        with pytest.raises(api.ComputeCancelled):
+            await asyncio.wait_for(stream.task, timeout=5)
+
+    asyncio.run(run())
+
+    assert api._inflight_computations == {}
+
+
+def test_a_post_sharing_the_run_keeps_it_alive():
+    compute = ReportingCompute()
+
+    async def run():
+        stream = api.start_single_flight("smb", PARAMS, compute, stream=True)
+        queue = stream.progress.subscribe()
+        post = asyncio.create_task(api.run_single_flight("smb", PARAMS, compute))
+        await asyncio.sleep(0.05)
+        stream.progress.unsubscribe(queue)
+        compute.release.set()
+        return await post
+
+    assert asyncio.run(run()) == {"call": 1}
+    assert compute.calls == 1
+
+
+def test_a_cancelled_run_isnt_joined():
+    compute = ReportingCompute()
+
+    async def run():
+        abandoned = api.start_single_flight("smb", PARAMS, compute, stream=True)
+        abandoned.progress.unsubscribe(abandoned.progress.subscribe())
+        rejoined = api.start_single_flight("smb", PARAMS, compute, stream=True)
+        rejoined.progress.subscribe()
+        with pytest.raises(api.ComputeCancelled):
+            await asyncio.wait_for(abandoned.task, timeout=5)
+        compute.release.set()
+        return rejoined is abandoned, await rejoined.task
+
+    reused, result = asyncio.run(run())
+
+    assert not reused
+    assert result == {"call": 2}
+    assert api._inflight_computations == {}
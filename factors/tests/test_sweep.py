@@ -0,0 +1,51 @@
+import pytest
+
+import api
+
+CONFIG = {"start_date": "2024-01-01", "end_date": "2024-06-30"}
+
+
+@pytest.fixture
+def swept(monkeypatch):
+    """Stand in for run_sweep, recording each (factor, breakpoints, params) instead of running it"""
+    calls = []
+
+    def sweep(definition, configs, **params):
+        calls.append((definition.factor, [config.breakpoint for config in configs], params))
+        return {"factor": definition.factor, "runs": []}
+
+    monkeypatch.setattr(api, "run_sweep", sweep)
+    return calls
+
+
+def post_sweep(client, factor: str, params=None):
+    body = {"config": {**CONFIG, "factor": factor}, "breakpoints": [0.2, 0.3]}
+    return client.post(f"/compute/{factor}/sweep", json=body, params=params)
+
+
+def test_a_factor_with_a_lookback_gets_its_default(client, swept):
+    assert post_sweep(client, "momentum_v2").status_code == 200
+
+    assert swept == [(api.Factor.MOMENTUM_V2, [0.2, 0.3], {"lookback_periods": 3})]
+
+
+def test_an_explicit_lookback_is_passed_through(client, swept):
+    post_sweep(client, "growth", params={"lookback_periods": 4})
+
+    assert swept[0][2] == {"lookback_periods": 4}
+
+
+def test_a_lookback_for_a_factor_without_one_is_a_400(client, swept):
+    response = post_sweep(client, "smb", params={"lookback_periods": 4})
+
+    assert response.status_code == 400
+    assert response.json()["detail"] == "Factor 'smb' does not take lookback_periods"
+    assert swept == []
+
+
+def test_composite_is_a_400(client, swept):
+    response = post_sweep(client, "composite")
+
+    assert response.status_code == 400
+    assert "use /compute/composite" in response.json()["detail"]
+    assert swept == []
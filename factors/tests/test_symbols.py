@@ -0,0 +1,46 @@
+import pytest
+from fastapi import HTTPException
+
+import api
+from utils import resolve_artemis_symbol, resolve_symbol
+
+
+@pytest.mark.parametrize("name", ["BTC", "Bitcoin", "bitcoin", " bitcoin ", "btc", "BTC-USD", "btc-usdt", "BTCUSD"])
+def test_bitcoin_spellings_resolve_to_its_coinbase_product(name):
+    assert resolve_symbol(name) == "BTC-USD"
+
+
+@pytest.mark.parametrize(
+    "name, slug",
+    [
+        ("ETH", "ethereum"),
+        ("Shiba Inu", "shiba-inu"),
+        ("shiba_inu", "shiba-inu"),
+        ("shibainu", "shiba-inu"),
+        ("avalanche-2", "avalanche"),
+        ("MATIC", "polygon"),
+        ("Ripple", "xrp"),
+        ("toncoin", "ton"),
+    ],
+)
+def test_tickers_ids_and_aliases_resolve_to_the_artemis_slug(name, slug):
+    assert resolve_artemis_symbol(name) == slug
+
+
+@pytest.mark.parametrize("name", ["", "usd", "not-a-coin", "BTC-EUR"])
+def test_unknown_names_resolve_to_none(name):
+    assert resolve_artemis_symbol(name) is None
+    assert resolve_symbol(name) is None
+
+
+def test_a_benchmark_is_accepted_by_any_spelling():
+    for benchmark in ("BTC", "Bitcoin", "bitcoin"):
+        api.ensure_benchmark_mapped(api.FactorConfig(factor="smb", start_date="2024-01-01", benchmark=benchmark))
+
+
+def test_an_unmapped_benchmark_is_a_400():
+    with pytest.raises(HTTPException) as error:
+        api.ensure_benchmark_mapped(api.FactorConfig(factor="smb", start_date="2024-01-01", benchmark="not-a-coin"))
+
+    assert error.value.status_code == 400
+    assert error.value.detail == "Benchmark 'not-a-coin' has no Coinbase price mapping"
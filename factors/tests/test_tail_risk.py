@@ -0,0 +1,61 @@
+import pytest
+
+import stats
+
+from .helpers import weekly_returns
+
+# Eighteen small gains and two large losses: skewness -3.07, excess kurtosis 8.72
+SKEWED = [0.01] * 18 + [-0.08, -0.12]
+
+
+def tail_risk(client, **params):
+    return client.get("/factors/smb/tail-risk", params=params)
+
+
+class TestValueAtRisk:
+    def test_gaussian_is_mean_plus_normal_quantile_times_std(self):
+        # mean -0.001, std 0.034473, z at 5% -1.644854
+        assert stats.value_at_risk(SKEWED, 0.95, "gaussian") == pytest.approx(-0.0577038, rel=1e-5)
+
+    def test_cornish_fisher_is_worse_than_gaussian_for_a_fat_left_tail(self):
+        cornish_fisher = stats.value_at_risk(SKEWED, 0.95, "cornish_fisher")
+
+        assert cornish_fisher == pytest.approx(-0.0756452, rel=1e-5)
+        assert cornish_fisher < stats.value_at_risk(SKEWED, 0.95, "gaussian")
+
+    def test_an_unknown_method_raises(self):
+        with pytest.raises(ValueError, match="Unknown VaR method: historical"):
+            stats.value_at_risk(SKEWED, 0.95, "historical")
+
+
+class TestTailRiskEndpoint:
+    def test_reports_both_estimates_for_a_skewed_run(self, client, seed_run):
+        seed_run("smb", "run1", weekly_returns(SKEWED))
+
+        response = tail_risk(client)
+
+        assert response.status_code == 200
+        body = response.json()
+        assert body["run_id"] == "run1"
+        assert body["num_periods"] == 20
+        assert body["skewness"] == pytest.approx(-3.074981, rel=1e-5)
+        assert body["excess_kurtosis"] == pytest.approx(8.723531, rel=1e-5)
+        assert body["var_gaussian"] == pytest.approx(-0.0577038, rel=1e-5)
+        assert body["var_cornish_fisher"] == pytest.approx(-0.0756452, rel=1e-5)
+        assert [worst["return"] for worst in body["worst_returns"]][:2] == [-0.12, -0.08]
+
+    def test_a_higher_confidence_pushes_both_estimates_lower(self, client, seed_run):
+        seed_run("smb", "run1", weekly_returns(SKEWED))
+
+        at_95, at_99 = (tail_risk(client, confidence=confidence).json() for confidence in (0.95, 0.99))
+
+        assert at_99["var_gaussian"] < at_95["var_gaussian"]
+        assert at_99["var_cornish_fisher"] < at_95["var_cornish_fisher"]
+
+    def test_fewer_than_four_returns_is_a_400(self, client, seed_run):
+        seed_run("smb", "run1", weekly_returns([0.01, -0.02, 0.03]))
+
+        response = tail_risk(client)
+
+        assert response.status_code == 400
+        assert response.json()["detail"] == "Run 'run1' has 3 returns; tail risk needs at least 4"
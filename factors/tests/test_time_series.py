@@ -0,0 +1,125 @@
+import pandas as pd
+import pytest
+
+import api
+import stats
+
+from .helpers import weekly_returns
+
+RETURNS = weekly_returns([0.01 * (i % 5 - 2) for i in range(30)])
+DATES = [date.strftime("%Y-%m-%d") for date in RETURNS]
+
+
+def get_series(client, **params) -> dict:
+    response = client.get("/factors/time-series", params={"factors": "smb", **params})
+    assert response.status_code == 200
+    (series,) = response.json()["time_series"]
+    return series
+
+
+def test_pages_through_a_long_series(client, seed_run):
+    seed_run("smb", "run1", RETURNS)
+
+    pages = [get_series(client, offset=offset, limit=7) for offset in range(0, 30, 7)]
+
+    assert all(page["total"] == 30 for page in pages)
+    assert [len(page["dates"]) for page in pages] == [7, 7, 7, 7, 2]
+    assert [date for page in pages for date in page["dates"]] == DATES
+
+
+def test_offset_and_limit_window_the_series(client, seed_run):
+    seed_run("smb", "run1", RETURNS)
+
+    page = get_series(client, offset=10, limit=5)
+
+    assert page["dates"] == DATES[10:15]
+    assert page["returns"] == list(RETURNS.values())[10:15]
+
+
+def test_pagination_applies_after_date_filtering(client, seed_run):
+    seed_run("smb", "run1", RETURNS)
+
+    page = get_series(client, start_date=DATES[5], end_date=DATES[24], offset=10, limit=5)
+
+    assert page["total"] == 20
+    assert page["dates"] == DATES[15:20]
+
+
+def test_offset_past_the_end_returns_an_empty_page(client, seed_run):
+    seed_run("smb", "run1", RETURNS)
+
+    page = get_series(client, offset=40)
+
+    assert page["total"] == 30
+    assert page["dates"] == []
+
+
+def test_cumulative_returns_cover_the_whole_run_not_the_page(client, seed_run):
+    seed_run("smb", "run1", RETURNS)
+
+    page = get_series(client, offset=10, limit=1)
+
+    expected = pd.Series(list(RETURNS.values()))[:11].add(1).prod() - 1
+    assert abs(page["cumulative_returns"][0] - expected) < 1e-12
+
+
+class ChunkSpy:
+    """Wraps stream_csv to record the size of each chunk as the response body is generated"""
+
+    def __init__(self, stream_csv):
+        self.stream_csv = stream_csv
+        self.chunk_rows = []
+
+    def __call__(self, frames, columns, filename):
+        def recorded():
+            for frame in frames:
+                self.chunk_rows.append(len(frame))
+                yield frame
+
+        return self.stream_csv(recorded(), columns, filename)
+
+
+def test_large_csv_responses_stream_in_row_chunks(client, seed_run, monkeypatch):
+    seed_run("smb", "run1", RETURNS)
+    spy = ChunkSpy(api.stream_csv)
+    monkeypatch.setattr(api, "stream_csv", spy)
+    monkeypatch.setattr(api, "STREAM_MIN_ROWS", 10)
+    monkeypatch.setattr(api, "STREAM_CHUNK_ROWS", 8)
+
+    response = client.get("/factors/time-series", params={"factors": "smb"}, headers={"Accept": "text/csv"})
+
+    assert response.status_code == 200
+    assert response.headers["content-type"].startswith("text/csv")
+    assert spy.chunk_rows == [8, 8, 8, 6]
+    lines = response.text.strip().splitlines()
+    assert lines[0] == "factor,run_id,date,return,cumulative_return"
+    assert [line.split(",")[2] for line in lines[1:]] == DATES
+
+
+def test_large_json_responses_stream_the_same_document(client, seed_run, monkeypatch):
+    seed_run("smb", "run1", RETURNS)
+    buffered = get_series(client)
+    monkeypatch.setattr(api, "STREAM_MIN_ROWS", 10)
+
+    assert get_series(client) == buffered
+
+
+def test_small_responses_are_buffered(client, seed_run, monkeypatch):
+    seed_run("smb", "run1", RETURNS)
+    monkeypatch.setattr(api, "stream_csv", lambda *args: pytest.fail("a 30-row response was streamed"))
+
+    response = client.get("/factors/time-series", params={"factors": "smb"}, headers={"Accept": "text/csv"})
+
+    assert response.status_code == 200
+    assert len(response.text.strip().splitlines()) == 31
+
+
+@pytest.mark.parametrize("frequency, periods_per_year", [("daily", 365), ("weekly", 52), ("monthly", 12), (None, 52)])
+def test_rolling_sharpe_is_annualized_at_the_runs_frequency(client, seed_run, frequency, periods_per_year):
+    results = {"rebalance_frequency": frequency} if frequency else {}
+    seed_run("smb", "run1", RETURNS, **results)
+
+    series = get_series(client, rolling_sharpe_window=4)
+
+    expected = stats.rolling_sharpe(list(RETURNS.values()), 4, periods_per_year=periods_per_year)
+    assert series["rolling_sharpe"] == pytest.approx(expected)
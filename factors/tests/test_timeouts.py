@@ -0,0 +1,100 @@
+import os
+import subprocess
+import sys
+from pathlib import Path
+from types import SimpleNamespace
+
+import pytest
+
+import utils
+from utils import ApiData, CoinbaseData, YahooData
+
+
+class RecordingSession:
+    """A requests session answering every request with `payload`, recording each request's timeout"""
+
+    def __init__(self, payload: dict):
+        self.payload = payload
+        self.timeouts = []
+
+    def get(self, url, **kwargs):
+        self.timeouts.append(kwargs.get("timeout"))
+        return SimpleNamespace(status_code=200, raise_for_status=lambda: None, json=lambda: self.payload)
+
+
+@pytest.fixture(autouse=True)
+def no_delay(monkeypatch):
+    monkeypatch.setattr(CoinbaseData, "RATE_LIMIT_DELAY", 0)
+    monkeypatch.setattr(YahooData, "RATE_LIMIT_DELAY", 0)
+
+
+def imported_timeouts(**env) -> list[float]:
+    """Each client's default timeout as a fresh interpreter importing utils with `env` reads it"""
+    environment = {key: value for key, value in os.environ.items() if key != "API_TIMEOUT_SECONDS"}
+    script = (
+        "from utils import API_TIMEOUT_SECONDS, CoinbaseData, YahooData; "
+        "print(API_TIMEOUT_SECONDS, CoinbaseData().timeout, YahooData().timeout)"
+    )
+    result = subprocess.run(
+        [sys.executable, "-c", script],
+        cwd=Path(utils.__file__).parent,
+        env={**environment, **env},
+        capture_output=True,
+        text=True,
+        check=True,
+    )
+    return [float(value) for value in result.stdout.strip().splitlines()[-1].split()]
+
+
+def test_clients_wait_30_seconds_by_default():
+    assert imported_timeouts() == [30.0, 30.0, 30.0]
+
+
+def test_clients_take_their_default_from_api_timeout_seconds():
+    assert imported_timeouts(API_TIMEOUT_SECONDS="7.5") == [7.5, 7.5, 7.5]
+
+
+class TestCoinbaseTimeout:
+    def test_candle_requests_pass_the_timeout(self):
+        client = CoinbaseData(timeout=4.0)
+        client._session = RecordingSession({"candles": []})
+
+        client.get_candles("BTC-USD", "2024-01-01", "2024-03-31")
+
+        assert len(client._session.timeouts) > 1
+        assert set(client._session.timeouts) == {4.0}
+
+    def test_product_listing_passes_the_timeout(self):
+        client = CoinbaseData(timeout=4.0)
+        client._session = RecordingSession({"products": []})
+
+        client.list_products()
+
+        assert client._session.timeouts == [4.0]
+
+    def test_ping_passes_the_timeout(self):
+        client = CoinbaseData(timeout=4.0)
+        client._session = RecordingSession({"candles": [{"start": "1704067200"}]})
+
+        client.ping()
+
+        assert client._session.timeouts == [4.0]
+
+
+def test_yahoo_requests_pass_the_timeout():
+    client = YahooData(timeout=4.0)
+    client._session = RecordingSession({"chart": {"result": []}})
+
+    # Two requests: the range spans more than MAX_DAYS_PER_REQUEST days
+    client.get_candles("AAPL", "2020-01-01", "2023-12-31")
+
+    assert client._session.timeouts == [4.0, 4.0]
+
+
+def test_the_artemis_client_is_built_with_the_timeout(monkeypatch):
+    built = []
+    monkeypatch.setattr(utils, "Artemis", lambda **kwargs: built.append(kwargs))
+
+    ApiData("test-key", timeout=4.0)
+
+    assert built == [{"api_key": "test-key", "timeout": 4.0}]
@@ -0,0 +1,61 @@
+import pandas as pd
+import pytest
+
+import api
+from utils import FactorModel
+
+RETURNS = [0.01, 0.02, 0.03, 0.04]
+
+
+def config(**fields) -> api.FactorConfig:
+    return api.FactorConfig(factor="smb", start_date="2024-01-01", end_date="2024-06-30", **fields)
+
+
+def two_rebalances(transaction_cost_bps: float) -> FactorModel:
+    """
+    Four assets over two weekly rebalances with the ranking flipped between them, so
+    the book is built from cash (turnover 1 per leg) and then fully swapped (2 per leg)
+    """
+    df = pd.concat(
+        pd.DataFrame(
+            {
+                "date": pd.Timestamp(date),
+                "asset": [f"asset{i}" for i in range(4)],
+                "signal": [float(sign * i) for i in range(4)],
+                "price_pct_change_p1": RETURNS,
+            }
+        )
+        for date, sign in (("2024-01-07", 1), ("2024-01-14", -1))
+    )
+    factor_model = FactorModel(
+        df, "smb", breakpoint=0.5, min_assets=4, weighting_method="equal", transaction_cost_bps=transaction_cost_bps
+    )
+    factor_model.form_long_short_portfolios("signal")
+    return factor_model
+
+
+def test_turnover_is_the_sum_of_absolute_weight_changes():
+    assert list(two_rebalances(0).turnover.values()) == pytest.approx([2.0, 4.0])
+
+
+def test_costs_come_out_of_each_period_in_proportion_to_turnover():
+    factor_model = two_rebalances(10)
+
+    # Long 3.5% and short 1.5%, then the legs swap; 10bps is 0.2% on turnover 2 and 0.4% on 4
+    assert list(factor_model.gross_factor_returns.values()) == pytest.approx([0.02, -0.02])
+    assert list(factor_model.factor_returns.values()) == pytest.approx([0.018, -0.024])
+
+
+def test_without_costs_net_equals_gross():
+    factor_model = two_rebalances(0)
+
+    assert factor_model.factor_returns == factor_model.gross_factor_returns
+
+
+@pytest.mark.usefixtures("no_benchmark")
+def test_performance_compounds_net_and_gross_returns_separately():
+    performance = api.summarize_performance(two_rebalances(10), config(transaction_cost_bps=10))
+
+    assert performance["cumulative_returns"] == pytest.approx(1.018 * 0.976 - 1)
+    assert performance["gross_cumulative_returns"] == pytest.approx(1.02 * 0.98 - 1)
+    assert performance["mean_turnover"] == pytest.approx(3.0)
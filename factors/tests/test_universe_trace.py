@@ -0,0 +1,77 @@
+import numpy as np
+import pandas as pd
+import pytest
+
+import api
+from utils import FactorModel
+
+WEEKS = pd.date_range("2024-01-07", periods=8, freq="W")
+TRACE_DATE = WEEKS[-1].strftime("%Y-%m-%d")
+
+# (market cap, weekly volume, first week listed) per asset; the defaults are 100M, 35M and 30 days
+UNIVERSE = {
+    "bitcoin": (1e9, 1e8, 0),
+    "ethereum": (1e9, 1e8, 0),
+    "small_cap": (5e7, 1e8, 0),
+    "illiquid": (1e9, 1e7, 0),
+    "new_listing": (1e9, 1e8, 5),
+    "unpriced": (1e9, 1e8, 0),
+}
+
+
+def crafted_panel() -> pd.DataFrame:
+    """The weekly rows fetch_factor_data would return, indexed by date; unpriced has no price on the trace date"""
+    rows = []
+    for asset, (mc, volume, listed) in UNIVERSE.items():
+        for i, date in enumerate(WEEKS[listed:], start=listed):
+            price = np.nan if asset == "unpriced" and i == len(WEEKS) - 1 else 100.0 + i
+            rows.append({"date": date, "asset": asset, "price": price, "mc": mc, "24h_volume": volume})
+    return pd.DataFrame(rows).set_index("date")
+
+
+@pytest.fixture(autouse=True)
+def crafted_universe(monkeypatch):
+    monkeypatch.setattr(api, "fetch_factor_data", lambda config, metrics, data_quality, **kwargs: crafted_panel())
+    # The panel is already on the weekly grid
+    monkeypatch.setattr(FactorModel, "resample", lambda self, freq, metric_agg_methods: None)
+
+
+def trace(client, factor: str = "smb", **params):
+    return client.get(f"/compute/{factor}/universe-trace", params={"date": TRACE_DATE, **params})
+
+
+def test_each_stage_drops_the_assets_it_filters(client):
+    response = trace(client)
+
+    assert response.status_code == 200
+    assert response.json()["stages"] == [
+        {"stage": "initial", "num_assets": 6},
+        {"stage": "market_cap", "num_assets": 5},
+        {"stage": "liquidity", "num_assets": 4},
+        {"stage": "lifetime", "num_assets": 3},
+        {"stage": "data_availability", "num_assets": 2},
+    ]
+
+
+def test_counts_never_increase_from_stage_to_stage(client):
+    counts = [stage["num_assets"] for stage in trace(client).json()["stages"]]
+
+    assert counts == sorted(counts, reverse=True)
+
+
+def test_loosened_thresholds_keep_the_assets_they_would_drop(client):
+    stages = trace(client, market_cap_threshold=0, liquidity_threshold=0, min_lifetime_days=0).json()["stages"]
+
+    # Only the trace date's missing return still drops an asset
+    assert [stage["num_assets"] for stage in stages] == [6, 6, 6, 6, 5]
+
+
+def test_a_mid_week_date_is_traced_at_its_week_end(client):
+    response = trace(client, date=(WEEKS[-1] - pd.Timedelta(days=3)).strftime("%Y-%m-%d"))
+
+    assert response.json()["date"] == TRACE_DATE
+    assert response.json()["stages"][0]["num_assets"] == 6
+
+
+def test_an_unsupported_factor_is_a_400(client):
+    assert trace(client, factor="growth").status_code == 400
@@ -1,10 +1,19 @@
 # Imports
+import csv
+import fcntl
+import json
 import logging
 import os
+import re
+import tempfile
+import threading
 import time
-from datetime import datetime
+import uuid
+from contextlib import contextmanager
+from datetime import datetime, timedelta, timezone
+from email.utils import parsedate_to_datetime
 from pathlib import Path
-from typing import Optional
+from typing import Callable, Literal, Optional
 
 import dotenv
 import matplotlib.pyplot as plt
@@ -15,13 +24,54 @@ import statsmodels.api as sm
 import yfinance as yf
 
 from artemis import Artemis
+from stats import apply_weights, simple_returns
+from stats import sortino_ratio as compute_sortino_ratio
+from stats import cumulative_returns as compound_returns
+
+
+
+def positive_int_setting(name: str, default: int) -> int:
+    """Read an integer environment setting that must be at least 1, raising RuntimeError naming it otherwise"""
+    value = os.getenv(name, str(default))
+    try:
+        parsed = int(value)
+    except ValueError:
+        parsed = 0
+    if parsed < 1:
+        raise RuntimeError(f"{name} must be a whole number of at least 1, got {value!r}")
+    return parsed
+
 
 # Env Variables
 dotenv.load_dotenv("../../.env.local")
 API_KEY = os.getenv("ARTEMIS_API_KEY")
+# Seconds an Artemis metric response is reused for identical requests (0, the default, disables the cache)
+ARTEMIS_CACHE_TTL_SECONDS = float(os.getenv("ARTEMIS_CACHE_TTL_SECONDS", "0"))
+# Per-request timeout of the Artemis, Coinbase and Yahoo clients
+API_TIMEOUT_SECONDS = float(os.getenv("API_TIMEOUT_SECONDS", "30"))
+# Most Artemis requests in flight at once across the process, to stay under the API's rate limit.
+# Checked on import, which the API does at startup, so a bad value stops the server
+ARTEMIS_MAX_CONCURRENT_REQUESTS = positive_int_setting("ARTEMIS_MAX_CONCURRENT_REQUESTS", 4)
 
 logger = logging.getLogger(__name__)
 
+# Called with a fetch phase ("fetching_metrics", "fetching_prices") and its percent complete,
+# e.g. to stream a compute's progress; it may raise to abandon the fetch, except inside a
+# cached fetch other callers may be waiting on (see ApiData.get_metric_for_all_symbols)
+ProgressCallback = Callable[[str, float], None]
+
+
+def reporting_only(progress: ProgressCallback) -> ProgressCallback:
+    """Wrap a progress callback so it still reports but can't abandon the fetch calling it"""
+
+    def report(phase: str, percent: float):
+        try:
+            progress(phase, percent)
+        except Exception:
+            pass
+
+    return report
+
 
 # =============================================================================
 # Symbol Mappings
@@ -189,17 +239,104 @@ COINGECKO_TO_YFINANCE_MAP = {
     "mantle": "MNT-USD",
 }
 
+# Quote currency suffixes resolve_artemis_symbol strips, longest first ("BTC-USD", "BTC/USDT", "BTCUSD")
+SYMBOL_SUFFIXES = ("-usdc", "-usdt", "-usd", "/usdc", "/usdt", "/usd", "usdc", "usdt", "usd")
+
+# Spellings resolve_artemis_symbol accepts besides the ones derived from the maps above
+EXTRA_SYMBOL_ALIASES = {
+    "matic": "polygon",
+    "ripple": "xrp",
+    "toncoin": "ton",
+}
+
+
+def _build_symbol_aliases() -> dict:
+    """Helper to map tickers, CoinGecko ids and hyphen-less slugs to Artemis slugs"""
+    slug_by_product = {product_id: slug for slug, product_id in ARTEMIS_TO_COINBASE_MAP.items()}
+    aliases = {product_id.split("-")[0].lower(): slug for product_id, slug in slug_by_product.items()}
+    aliases.update(
+        {
+            gecko_id: slug_by_product[product_id]
+            for gecko_id, product_id in COINGECKO_TO_YFINANCE_MAP.items()
+            if product_id in slug_by_product
+        }
+    )
+    aliases.update({slug.replace("-", ""): slug for slug in ARTEMIS_TO_COINBASE_MAP})
+    aliases.update(EXTRA_SYMBOL_ALIASES)
+    return aliases
+
+
+SYMBOL_ALIASES = _build_symbol_aliases()
+
+
+def resolve_artemis_symbol(value: str) -> Optional[str]:
+    """
+    Resolve a user-supplied asset name to its Artemis slug, forgivingly.
+
+    Case, surrounding whitespace and spaces or underscores in place of hyphens are
+    ignored, and a trailing quote currency ("-USD", "USDT", ...) is stripped. Slugs
+    ("bitcoin") match first, then tickers ("BTC"), Coinbase product ids ("BTC-USD"),
+    CoinGecko ids and the other SYMBOL_ALIASES. Returns None when nothing matches;
+    look up ARTEMIS_TO_COINBASE_MAP directly where only exact slugs should do.
+    """
+    normalized = re.sub(r"[\s_]+", "-", value.strip().lower())
+    candidates = [normalized]
+    for suffix in SYMBOL_SUFFIXES:
+        if normalized.endswith(suffix) and len(normalized) > len(suffix):
+            candidates.append(normalized[: -len(suffix)].rstrip("-/"))
+            break
+    for candidate in candidates:
+        if candidate in ARTEMIS_TO_COINBASE_MAP:
+            return candidate
+        if candidate in SYMBOL_ALIASES:
+            return SYMBOL_ALIASES[candidate]
+    return None
+
+
+def resolve_symbol(value: str) -> Optional[str]:
+    """Coinbase product id of a forgivingly matched asset name (see resolve_artemis_symbol), or None"""
+    slug = resolve_artemis_symbol(value)
+    return ARTEMIS_TO_COINBASE_MAP[slug] if slug is not None else None
+
+
+# Default universe for the equity factor: liquid US large caps with Yahoo Finance tickers
+EQUITY_TICKERS = [
+    "AAPL", "MSFT", "NVDA", "AMZN", "GOOGL", "META", "TSLA", "BRK-B", "AVGO", "JPM",
+    "LLY", "V", "UNH", "XOM", "MA", "JNJ", "PG", "HD", "COST", "ABBV",
+    "MRK", "CVX", "KO", "PEP", "ADBE", "CRM", "WMT", "BAC", "NFLX", "AMD",
+    "TMO", "MCD", "CSCO", "ACN", "ABT", "LIN", "ORCL", "DIS", "WFC", "INTC",
+    "QCOM", "TXN", "INTU", "CAT", "IBM", "GE", "AMGN", "PFE", "NKE", "COIN",
+]
+
+
+class InvalidDateRangeError(ValueError):
+    """Raised when a price fetch is asked for a start date after its end date"""
+
+
+def parse_date_range(start_date: str, end_date: str) -> tuple[pd.Timestamp, pd.Timestamp]:
+    """Parse a YYYY-MM-DD fetch range, raising InvalidDateRangeError if it's inverted"""
+    start_dt = pd.Timestamp(start_date)
+    end_dt = pd.Timestamp(end_date)
+    if start_dt > end_dt:
+        raise InvalidDateRangeError(f"start_date {start_date} is after end_date {end_date}")
+    return start_dt, end_dt
+
+
+CandleGranularity = Literal["ONE_HOUR", "SIX_HOUR", "ONE_DAY"]
+
 
 class CoinbaseData:
     """Fetch crypto OHLCV data from Coinbase Advanced Trade public REST API."""
 
     BASE_URL = "https://api.coinbase.com/api/v3/brokerage/market"
     MAX_CANDLES_PER_REQUEST = 300
+    GRANULARITY_SECONDS = {"ONE_HOUR": 3600, "SIX_HOUR": 6 * 3600, "ONE_DAY": 24 * 3600}
     RATE_LIMIT_DELAY = 0.1  # 10 req/sec
 
-    def __init__(self):
+    def __init__(self, timeout: float = API_TIMEOUT_SECONDS):
         self._session = requests.Session()
         self._products_cache = None
+        self.timeout = timeout  # seconds per request
 
     def list_products(self, quote_currency: str = "USD") -> list[dict]:
         """List all spot trading pairs for a given quote currency, cached."""
@@ -213,7 +350,7 @@ class CoinbaseData:
 
         while True:
             params["offset"] = offset
-            resp = self._session.get(url, params=params, timeout=30)
+            resp = self._session.get(url, params=params, timeout=self.timeout)
             resp.raise_for_status()
             data = resp.json()
             products = data.get("products", [])
@@ -230,43 +367,66 @@ class CoinbaseData:
         ]
         return self._products_cache
 
+    @classmethod
+    def candle_step(cls, granularity: CandleGranularity) -> pd.Timedelta:
+        """Length of one candle, raising ValueError for an unsupported granularity"""
+        if granularity not in cls.GRANULARITY_SECONDS:
+            raise ValueError(
+                f"Unsupported granularity '{granularity}', expected one of "
+                f"{sorted(cls.GRANULARITY_SECONDS)}"
+            )
+        return pd.Timedelta(seconds=cls.GRANULARITY_SECONDS[granularity])
+
+    @classmethod
+    def request_window(cls, granularity: CandleGranularity) -> pd.Timedelta:
+        """Span covered by one candles request: ~300 days daily, ~75 days six-hourly, ~12.5 days hourly"""
+        return cls.candle_step(granularity) * (cls.MAX_CANDLES_PER_REQUEST - 1)
+
     def get_candles(
-        self, product_id: str, start_date: str, end_date: str
+        self,
+        product_id: str,
+        start_date: str,
+        end_date: str,
+        granularity: CandleGranularity = "ONE_DAY",
     ) -> pd.DataFrame:
         """
-        Fetch daily OHLCV candles with pagination (300 candles/request).
+        Fetch OHLCV candles with pagination (300 candles/request).
 
         Args:
             product_id: e.g. "BTC-USD"
             start_date: YYYY-MM-DD
             end_date: YYYY-MM-DD
+            granularity: "ONE_HOUR", "SIX_HOUR" or "ONE_DAY"
 
         Returns:
-            DataFrame with columns [date, open, high, low, close, volume]
+            DataFrame with columns [date, open, high, low, close, volume], where
+            date is the candle's start time
+
+        Raises:
+            InvalidDateRangeError: start_date is after end_date
+            ValueError: unsupported granularity
         """
         url = f"{self.BASE_URL}/products/{product_id}/candles"
-        start_dt = pd.Timestamp(start_date)
-        end_dt = pd.Timestamp(end_date)
+        start_dt, end_dt = parse_date_range(start_date, end_date)
+        step = self.candle_step(granularity)
+        window = self.request_window(granularity)
 
         all_candles = []
         current_start = start_dt
 
         while current_start < end_dt:
-            # Each request covers up to 300 days
-            current_end = min(
-                current_start + pd.Timedelta(days=self.MAX_CANDLES_PER_REQUEST - 1),
-                end_dt,
-            )
+            # Each request covers up to 300 candles at the chosen granularity
+            current_end = min(current_start + window, end_dt)
 
             params = {
                 "start": str(int(current_start.timestamp())),
                 "end": str(int(current_end.timestamp())),
-                "granularity": "ONE_DAY",
+                "granularity": granularity,
             }
 
             for attempt in range(3):
                 try:
-                    resp = self._session.get(url, params=params, timeout=30)
+                    resp = self._session.get(url, params=params, timeout=self.timeout)
                     resp.raise_for_status()
                     break
                 except requests.exceptions.RequestException as e:
@@ -282,14 +442,25 @@ class CoinbaseData:
                 candles = resp.json().get("candles", [])
                 all_candles.extend(candles)
 
-            current_start = current_end + pd.Timedelta(days=1)
+            current_start = current_end + step
             time.sleep(self.RATE_LIMIT_DELAY)
 
         if not all_candles:
             return pd.DataFrame(columns=["date", "open", "high", "low", "close", "volume"])
 
         df = pd.DataFrame(all_candles)
-        df["date"] = pd.to_datetime(df["start"].astype(int), unit="s")
+        df["date"] = self.parse_candle_timestamps(df["start"])
+
+        # Guard against a misdetected unit silently producing far-off dates
+        in_range = df["date"].between(
+            start_dt - pd.Timedelta(days=1), end_dt + pd.Timedelta(days=1)
+        )
+        if not in_range.all():
+            logger.warning(
+                f"Dropping {int((~in_range).sum())} candles for {product_id} "
+                f"outside {start_date} to {end_date}"
+            )
+            df = df[in_range]
         df = df.rename(columns={"close": "close", "open": "open", "high": "high", "low": "low"})
         df["volume"] = pd.to_numeric(df["volume"], errors="coerce")
         df["close"] = pd.to_numeric(df["close"], errors="coerce")
@@ -300,13 +471,64 @@ class CoinbaseData:
         df = df.sort_values("date").drop_duplicates(subset=["date"]).reset_index(drop=True)
         return df
 
+    def ping(self, product_id: str = "BTC-USD"):
+        """
+        Fetch the latest daily candle of a product in a single attempt, as a connectivity check.
+
+        Raises requests exceptions on failure, or ValueError when Coinbase returns no candle.
+        """
+        end = int(time.time())
+        params = {"start": str(end - 24 * 3600), "end": str(end), "granularity": "ONE_DAY"}
+        resp = self._session.get(
+            f"{self.BASE_URL}/products/{product_id}/candles", params=params, timeout=self.timeout
+        )
+        resp.raise_for_status()
+        if not resp.json().get("candles"):
+            raise ValueError(f"No candles returned for {product_id}")
+
+    @staticmethod
+    def daily_candles(candles: pd.DataFrame, end_date: str) -> pd.DataFrame:
+        """
+        Aggregate sub-daily candles to one per UTC day through end_date: the first open,
+        the high and low, the last close and the summed volume, so a day's close and
+        24h volume mean the same as with daily candles
+        """
+        if candles.empty:
+            return candles
+        candles = candles.sort_values("date").assign(date=lambda df: df["date"].dt.floor("D"))
+        candles = candles[candles["date"] <= pd.Timestamp(end_date)]
+        return candles.groupby("date", as_index=False).agg(
+            open=("open", "first"),
+            high=("high", "max"),
+            low=("low", "min"),
+            close=("close", "last"),
+            volume=("volume", "sum"),
+        )
+
+    @staticmethod
+    def parse_candle_timestamps(values: pd.Series) -> pd.Series:
+        """
+        Convert candle `start` values to datetimes, detecting the unit.
+
+        Coinbase documents UNIX seconds, but numeric values too large to be
+        seconds (beyond year ~5000) are treated as milliseconds, and
+        non-numeric values are parsed as ISO 8601 / RFC 3339 strings.
+        """
+        numeric = pd.to_numeric(values, errors="coerce")
+        if numeric.notna().all():
+            unit = "ms" if numeric.abs().max() >= 1e11 else "s"
+            return pd.to_datetime(numeric.astype("int64"), unit=unit)
+        return pd.to_datetime(values, utc=True).dt.tz_localize(None)
+
     def get_price_volume_for_symbols(
         self,
         symbols: list[str],
         start_date: str,
         end_date: str,
         symbol_map: dict | None = None,
-    ) -> pd.DataFrame:
+        granularity: CandleGranularity = "ONE_DAY",
+        progress: Optional[ProgressCallback] = None,
+    ) -> tuple[pd.DataFrame, list[str]]:
         """
         Batch fetch price + volume for multiple Artemis symbols.
 
@@ -316,24 +538,46 @@ class CoinbaseData:
             end_date: YYYY-MM-DD
             symbol_map: Mapping from Artemis slug to Coinbase product ID.
                         Defaults to ARTEMIS_TO_COINBASE_MAP.
+            granularity: Candle size fetched, "ONE_HOUR", "SIX_HOUR" or "ONE_DAY".
+                         Sub-daily candles are aggregated to daily ones (see daily_candles),
+                         so rows are always one per asset and UTC day.
+            progress: Called with "fetching_prices" and the percent of symbols fetched as each one starts
 
         Returns:
-            DataFrame with columns [date, asset, price, 24h_volume]
+            DataFrame with columns [date, asset, price, 24h_volume], and the
+            symbols left out of it: unmapped ones and ones with no candles in range
+
+        Raises:
+            InvalidDateRangeError: start_date is after end_date, checked before any request
+            ValueError: unsupported granularity, checked before any request
         """
+        parse_date_range(start_date, end_date)
+        self.candle_step(granularity)
         if symbol_map is None:
             symbol_map = ARTEMIS_TO_COINBASE_MAP
+        # Sub-daily candles run to the end date's last hours, so fetch into the next day
+        fetch_end = end_date
+        if granularity != "ONE_DAY":
+            fetch_end = (pd.Timestamp(end_date) + pd.Timedelta(days=1)).strftime("%Y-%m-%d")
 
         records = []
         unmapped = []
+        skipped = []
 
-        for symbol in symbols:
+        for n, symbol in enumerate(symbols):
+            if progress is not None:
+                progress("fetching_prices", 100 * n / len(symbols))
             product_id = symbol_map.get(symbol)
             if not product_id:
                 unmapped.append(symbol)
+                skipped.append(symbol)
                 continue
 
-            candles = self.get_candles(product_id, start_date, end_date)
+            candles = self.get_candles(product_id, start_date, fetch_end, granularity)
+            if granularity != "ONE_DAY":
+                candles = self.daily_candles(candles, end_date)
             if candles.empty:
+                skipped.append(symbol)
                 continue
 
             for _, row in candles.iterrows():
@@ -350,11 +594,11 @@ class CoinbaseData:
             logger.info(f"Unmapped Artemis symbols (no Coinbase pair): {unmapped}")
 
         if not records:
-            return pd.DataFrame(columns=["date", "asset", "price", "24h_volume"])
+            return pd.DataFrame(columns=["date", "asset", "price", "24h_volume"]), skipped
 
         df = pd.DataFrame(records)
         df["date"] = pd.to_datetime(df["date"])
-        return df
+        return df, skipped
 
 
 def build_artemis_to_coinbase_map(validate: bool = False) -> dict:
@@ -376,29 +620,336 @@ def build_artemis_to_coinbase_map(validate: bool = False) -> dict:
     return validated
 
 
+class YahooData:
+    """Fetch daily equity prices and volume from the Yahoo Finance chart API."""
+
+    BASE_URL = "https://query1.finance.yahoo.com/v8/finance/chart"
+    MAX_DAYS_PER_REQUEST = 1000
+    RATE_LIMIT_DELAY = 0.2  # Yahoo throttles bursts; stay around 5 req/sec
+    # The chart API rejects requests without a browser-like user agent
+    HEADERS = {"User-Agent": "Mozilla/5.0"}
+
+    def __init__(self, base_url: str | None = None, timeout: float = API_TIMEOUT_SECONDS):
+        self.base_url = (base_url or self.BASE_URL).rstrip("/")
+        self._session = requests.Session()
+        self._session.headers.update(self.HEADERS)
+        self.timeout = timeout  # seconds per request
+
+    def get_candles(self, ticker: str, start_date: str, end_date: str) -> pd.DataFrame:
+        """
+        Fetch daily candles with pagination (MAX_DAYS_PER_REQUEST days/request).
+
+        Args:
+            ticker: e.g. "AAPL"
+            start_date: YYYY-MM-DD
+            end_date: YYYY-MM-DD
+
+        Returns:
+            DataFrame with columns [date, close, volume]; close is split/dividend
+            adjusted when Yahoo provides it
+
+        Raises:
+            InvalidDateRangeError: start_date is after end_date
+        """
+        url = f"{self.base_url}/{ticker}"
+        start_dt, end_dt = parse_date_range(start_date, end_date)
+
+        frames = []
+        current_start = start_dt
+
+        while current_start < end_dt:
+            current_end = min(current_start + pd.Timedelta(days=self.MAX_DAYS_PER_REQUEST - 1), end_dt)
+
+            params = {
+                "period1": str(int(current_start.timestamp())),
+                # period2 is exclusive, so include the window's last day
+                "period2": str(int((current_end + pd.Timedelta(days=1)).timestamp())),
+                "interval": "1d",
+                "events": "div,splits",
+            }
+
+            resp = None
+            for attempt in range(3):
+                try:
+                    resp = self._session.get(url, params=params, timeout=self.timeout)
+                    resp.raise_for_status()
+                    break
+                except requests.exceptions.RequestException as e:
+                    if attempt == 2:
+                        logger.warning(
+                            f"Failed to fetch Yahoo prices for {ticker} "
+                            f"({current_start.date()} to {current_end.date()}): {e}"
+                        )
+                        resp = None
+                        break
+                    time.sleep(2 ** attempt)
+
+            if resp is not None and resp.status_code == 200:
+                frame = self.parse_chart(resp.json())
+                if not frame.empty:
+                    frames.append(frame)
+
+            current_start = current_end + pd.Timedelta(days=1)
+            time.sleep(self.RATE_LIMIT_DELAY)
+
+        if not frames:
+            return pd.DataFrame(columns=["date", "close", "volume"])
+
+        df = pd.concat(frames, ignore_index=True)
+        df = df[df["date"].between(start_dt, end_dt)]
+        return df.sort_values("date").drop_duplicates(subset=["date"]).reset_index(drop=True)
+
+    @staticmethod
+    def parse_chart(payload: dict) -> pd.DataFrame:
+        """
+        Convert a chart API response to [date, close, volume] rows.
+
+        Timestamps are UNIX seconds at the exchange open, so they're normalized to
+        the trading day. Days without a close (halts, partial data) are dropped.
+        """
+        results = (payload.get("chart") or {}).get("result") or []
+        if not results or not results[0].get("timestamp"):
+            return pd.DataFrame(columns=["date", "close", "volume"])
+
+        result = results[0]
+        indicators = result.get("indicators", {})
+        quote = (indicators.get("quote") or [{}])[0]
+        adjclose = (indicators.get("adjclose") or [{}])[0].get("adjclose")
+
+        df = pd.DataFrame(
+            {
+                "date": pd.to_datetime(result["timestamp"], unit="s").normalize(),
+                "close": pd.to_numeric(pd.Series(adjclose or quote.get("close")), errors="coerce"),
+                "volume": pd.to_numeric(pd.Series(quote.get("volume")), errors="coerce"),
+            }
+        )
+        return df.dropna(subset=["close"])
+
+    def get_price_volume_for_symbols(
+        self,
+        tickers: list[str],
+        start_date: str,
+        end_date: str,
+        progress: Optional[ProgressCallback] = None,
+    ) -> pd.DataFrame:
+        """
+        Batch fetch price + volume for multiple equity tickers.
+
+        Args:
+            tickers: List of tickers (e.g. ["AAPL", "MSFT"])
+            start_date: YYYY-MM-DD
+            end_date: YYYY-MM-DD
+            progress: Called with "fetching_prices" and the percent of tickers fetched as each one starts
+
+        Returns:
+            DataFrame with columns [date, asset, price, 24h_volume], matching
+            CoinbaseData; 24h_volume is the day's share volume
+
+        Raises:
+            InvalidDateRangeError: start_date is after end_date, checked before any request
+        """
+        parse_date_range(start_date, end_date)
+        records = []
+        missing = []
+
+        for n, ticker in enumerate(tickers):
+            if progress is not None:
+                progress("fetching_prices", 100 * n / len(tickers))
+            candles = self.get_candles(ticker, start_date, end_date)
+            if candles.empty:
+                missing.append(ticker)
+                continue
+
+            for _, row in candles.iterrows():
+                records.append(
+                    {
+                        "date": row["date"],
+                        "asset": ticker,
+                        "price": row["close"],
+                        "24h_volume": row["volume"],
+                    }
+                )
+
+        if missing:
+            logger.info(f"No Yahoo prices for tickers: {missing}")
+
+        if not records:
+            return pd.DataFrame(columns=["date", "asset", "price", "24h_volume"])
+
+        df = pd.DataFrame(records)
+        df["date"] = pd.to_datetime(df["date"])
+        return df
+
+
+class MetricCache:
+    """
+    Thread-safe TTL cache of Artemis metric frames, shared across ApiData instances.
+
+    Each key has its own lock, so concurrent requests for the same key wait for
+    the first fetch instead of repeating it, while different keys fetch in
+    parallel. Failed fetches are not cached. The oldest entries are evicted past
+    max_entries. A key's lock is only dropped (on eviction or clear) once no
+    caller holds or waits on it, so a key never has two fetches in flight.
+    """
+
+    def __init__(self, ttl_seconds: float, max_entries: int = 32):
+        self.ttl_seconds = ttl_seconds
+        self.max_entries = max_entries
+        self._entries = {}  # {key: (expires_at, DataFrame)}
+        self._key_locks = {}  # {key: Lock}
+        self._key_lock_users = {}  # {key: callers holding or waiting on its lock}
+        self._lock = threading.Lock()
+
+    def get_or_fetch(self, key: tuple, fetch) -> pd.DataFrame:
+        """Return a copy of the cached frame for key, calling fetch() to fill it when missing or expired"""
+        with self._lock:
+            key_lock = self._key_locks.setdefault(key, threading.Lock())
+            self._key_lock_users[key] = self._key_lock_users.get(key, 0) + 1
+        try:
+            with key_lock:
+                entry = self._entries.get(key)
+                if entry is not None and entry[0] > time.monotonic():
+                    return entry[1].copy()
+                df = fetch()
+                with self._lock:
+                    self._entries[key] = (time.monotonic() + self.ttl_seconds, df)
+                    while len(self._entries) > self.max_entries:
+                        oldest = min(self._entries, key=lambda cached_key: self._entries[cached_key][0])
+                        del self._entries[oldest]
+                        self._drop_unused_key_lock(oldest)
+                return df.copy()
+        finally:
+            with self._lock:
+                self._key_lock_users[key] -= 1
+                if key not in self._entries:
+                    self._drop_unused_key_lock(key)
+
+    def _drop_unused_key_lock(self, key: tuple):
+        """Forget key's lock unless a caller holds or waits on it (call with self._lock held)"""
+        if not self._key_lock_users.get(key):
+            self._key_locks.pop(key, None)
+            self._key_lock_users.pop(key, None)
+
+    def clear(self):
+        """Drop every cached frame, and the lock of every key no caller is using"""
+        with self._lock:
+            self._entries.clear()
+            for key in list(self._key_locks):
+                self._drop_unused_key_lock(key)
+
+
+# Process-wide cache used by ApiData.with_cache()
+ARTEMIS_METRIC_CACHE = MetricCache(ARTEMIS_CACHE_TTL_SECONDS)
+
+# Artemis requests are retried on these statuses, and on these errors when they carry no status
+ARTEMIS_RETRY_STATUSES = {429, 500, 502, 503, 504}
+ARTEMIS_RETRY_ERRORS = (requests.RequestException, TimeoutError, ConnectionError)
+ARTEMIS_MAX_ATTEMPTS = 3
+# Longest Retry-After wait honored before falling back to the backoff delay
+ARTEMIS_MAX_RETRY_AFTER_SECONDS = 60
+
+
+# Request slots shared by every ApiData with the same concurrency limit
+_artemis_request_slots = {}
+_artemis_request_slots_lock = threading.Lock()
+
+
+def artemis_request_slots(max_concurrent_requests: int) -> threading.BoundedSemaphore:
+    """The process-wide semaphore limiting Artemis requests to max_concurrent_requests in flight"""
+    if max_concurrent_requests < 1:
+        raise ValueError(f"Artemis max_concurrent_requests must be at least 1, got {max_concurrent_requests}")
+    with _artemis_request_slots_lock:
+        return _artemis_request_slots.setdefault(
+            max_concurrent_requests, threading.BoundedSemaphore(max_concurrent_requests)
+        )
+
+
+def retry_after_seconds(value: Optional[str]) -> Optional[float]:
+    """Parse a Retry-After header (delay seconds or an HTTP date) into seconds to wait, or None"""
+    if not value:
+        return None
+    try:
+        return max(float(value), 0.0)
+    except ValueError:
+        pass
+    try:
+        retry_at = parsedate_to_datetime(value)
+    except (TypeError, ValueError):
+        return None
+    if retry_at.tzinfo is None:
+        retry_at = retry_at.replace(tzinfo=timezone.utc)
+    return max((retry_at - datetime.now(timezone.utc)).total_seconds(), 0.0)
+
+
 class ApiData:
     """Class to pull and format data from Artemis API"""
 
-    def __init__(self, api_key):
+    def __init__(
+        self,
+        api_key,
+        backoff_base: float = 1.0,
+        timeout: float = API_TIMEOUT_SECONDS,
+        max_concurrent_requests: int = ARTEMIS_MAX_CONCURRENT_REQUESTS,
+    ):
         self.api_key = api_key
-        self.client = Artemis(api_key=api_key)
+        self.client = Artemis(api_key=api_key, timeout=timeout)
+        self.cache = None
+        self.backoff_base = backoff_base  # seconds before the first retry, doubling per attempt
+        # Shared with every other client using the same limit; raises ValueError below 1
+        self.request_slots = artemis_request_slots(max_concurrent_requests)
+
+    def _with_retry(self, description: str, request):
+        """
+        Helper to call request(), retrying transient Artemis failures with exponential backoff.
+
+        Up to ARTEMIS_MAX_ATTEMPTS attempts are made for errors with a status in
+        ARTEMIS_RETRY_STATUSES, and for network errors and timeouts without one
+        (ARTEMIS_RETRY_ERRORS), waiting backoff_base, then twice that, between them. A Retry-After header on the error's response
+        takes precedence over the backoff, up to ARTEMIS_MAX_RETRY_AFTER_SECONDS.
+        Other errors, e.g. a 4xx or a KeyError, and the last attempt's are raised at once.
+
+        Each attempt holds one of request_slots while in flight, not while backing off.
+        """
+        for attempt in range(ARTEMIS_MAX_ATTEMPTS):
+            try:
+                with self.request_slots:
+                    return request()
+            except Exception as e:
+                response = getattr(e, "response", None)
+                status = getattr(e, "status_code", None) or getattr(response, "status_code", None)
+                transient = status in ARTEMIS_RETRY_STATUSES if status is not None else isinstance(e, ARTEMIS_RETRY_ERRORS)
+                if attempt == ARTEMIS_MAX_ATTEMPTS - 1 or not transient:
+                    raise
+                delay = retry_after_seconds((getattr(response, "headers", None) or {}).get("Retry-After"))
+                if delay is None or delay > ARTEMIS_MAX_RETRY_AFTER_SECONDS:
+                    delay = self.backoff_base * 2 ** attempt
+                logger.warning(f"Artemis {description} failed ({e}); retrying in {delay:.1f}s")
+                time.sleep(delay)
+
+    def with_cache(self, cache: Optional[MetricCache] = None) -> "ApiData":
+        """
+        Reuse metric responses from `cache` (default: the process-wide ARTEMIS_METRIC_CACHE).
+
+        Identical get_metric_for_all_symbols calls within the TTL are served from
+        memory. Caching is skipped when the TTL is 0.
+        """
+        cache = cache or ARTEMIS_METRIC_CACHE
+        self.cache = cache if cache.ttl_seconds > 0 else None
+        return self
 
     def get_all_metrics_for_symbol(self, symbol: str) -> list:
         """Get all metrics for a symbol"""
         available_metrics = []
-        supported_metrics = self.client.asset.list_supported_metrics(
-            symbol=symbol
-        ).metrics
+        with self.request_slots:
+            supported_metrics = self.client.asset.list_supported_metrics(symbol=symbol).metrics
         for i in supported_metrics:
             for key in i.keys():
                 available_metrics.append(key)
         return available_metrics
 
-    def get_metric_for_all_symbols(
-        self, metrics: list, start_date: str, end_date: str
-    ) -> pd.DataFrame:
-        """Get all metrics for all symbols between start and end date"""
-        all_assets = self.client.asset.list_asset_symbols()
+    def list_crypto_symbols(self) -> list[str]:
+        """List Artemis asset symbols, excluding equities and stablecoins"""
+        all_assets = self._with_retry("symbol listing", self.client.asset.list_asset_symbols)
         symbols = [
             asset.get("symbol")
             for asset in all_assets["assets"]
@@ -406,7 +957,7 @@ class ApiData:
         ]
 
         # get rid of equity symbols (that contain eq-)
-        symbols = [
+        return [
             symbol
             for symbol in symbols
             if "eq-" not in symbol
@@ -414,33 +965,107 @@ class ApiData:
             and symbol != "M"
             and symbol != "eurc"
         ]  # filter out equities and stablecoins
+
+    def get_metric_for_all_symbols(
+        self,
+        metrics: list,
+        start_date: str,
+        end_date: str,
+        symbols: Optional[list[str]] = None,
+        progress: Optional[ProgressCallback] = None,
+    ) -> pd.DataFrame:
+        """
+        Get all metrics for the given symbols (default: all crypto symbols) between start and end date.
+
+        With a cache (see with_cache), results are keyed by the symbols, metrics and dates.
+        progress is called with "fetching_metrics" and the percent of symbols fetched as each batch
+        starts; a cached result reports nothing. Other callers may wait on a cached fetch, so
+        there progress can only report: it's called once more after the fetch, where raising
+        abandons this caller's request alone.
+        """
+        if symbols is None:
+            symbols = self.list_crypto_symbols()
+        if self.cache is None:
+            return self._fetch_metric_for_all_symbols(metrics, start_date, end_date, symbols, progress)
+        key = (tuple(sorted(symbols)), tuple(sorted(metrics)), start_date, end_date)
+        shared_progress = reporting_only(progress) if progress is not None else None
+        df = self.cache.get_or_fetch(
+            key, lambda: self._fetch_metric_for_all_symbols(metrics, start_date, end_date, symbols, shared_progress)
+        )
+        if progress is not None:
+            progress("fetching_metrics", 100.0)
+        return df
+
+    def _fetch_metric_for_all_symbols(
+        self,
+        metrics: list,
+        start_date: str,
+        end_date: str,
+        symbols: list[str],
+        progress: Optional[ProgressCallback] = None,
+    ) -> pd.DataFrame:
+        """
+        Helper to fetch and pivot the metrics from the Artemis API in symbol batches.
+
+        Each batch's response is pivoted to one row per (date, asset) as soon as it
+        arrives and the raw response dropped, so peak memory is one batch's long-format
+        values plus the wide rows so far, rather than every value of a large universe
+        held at once before pivoting.
+        """
         metrics = ",".join(metrics)
 
         symbol_batch_size = 5  # 250 asset limit per request
-        full_data_dict = {}
+        chunks = []
         failed_symbols = []
 
         for i in range(0, len(symbols), symbol_batch_size):
+            # Before the try, so a callback raising abandons the fetch rather than skipping a batch
+            if progress is not None:
+                progress("fetching_metrics", 100 * i / len(symbols))
             batch = symbols[i : i + symbol_batch_size]
             try:
-                metrics_for_asset = self.client.fetch_metrics(
-                    api_key=self.api_key,
-                    metric_names=metrics,
-                    symbols=batch,
-                    start_date=start_date,
-                    end_date=end_date,
+                metrics_for_asset = self._with_retry(
+                    f"metrics fetch for {batch}",
+                    lambda: self.client.fetch_metrics(
+                        api_key=self.api_key,
+                        metric_names=metrics,
+                        symbols=batch,
+                        start_date=start_date,
+                        end_date=end_date,
+                    ),
                 )
-                full_data_dict.update(metrics_for_asset.data.symbols)
                 time.sleep(1)
             except Exception as e:
                 print(f"Error getting metrics for {batch}: {e}")
                 failed_symbols.extend(batch)
                 continue
 
-        records = []
-        for asset, metrics_dict in full_data_dict.items():
-            for metric, values in metrics_dict.items():
-                for item in values:
+            chunk = self._pivot_metric_batch(metrics_for_asset.data.symbols)
+            if not chunk.empty:
+                chunks.append(chunk)
+
+        if not chunks:
+            raise ValueError(
+                "No data returned from Artemis API for the requested metrics. "
+                "Verify API key, metric names, and date range."
+            )
+
+        # Batches hold disjoint assets; a metric absent from a whole batch is null there
+        pivoted_df = pd.concat(chunks, ignore_index=True)
+        return pivoted_df.set_index("date")
+
+    @staticmethod
+    def _pivot_metric_batch(symbols_data: dict) -> pd.DataFrame:
+        """
+        Helper to turn one batch's {asset: {metric: [items]}} response into rows of (date, asset, metrics...).
+
+        Values are collected column-wise rather than as a dict per value, and null or
+        NaN values are skipped. Returns an empty frame when the batch has no values.
+        """
+        dates, assets, metric_names, values = [], [], [], []
+        for asset, metrics_dict in symbols_data.items():
+            for metric, items in metrics_dict.items():
+                for item in items:
                     # Handle both dict and object types
                     if isinstance(item, dict):
                         item_date = item.get("date") or item.get("timestamp")
@@ -449,54 +1074,83 @@ class ApiData:
                         # Handle DataSymbolsDataSymbolsItem objects
                         item_date = getattr(item, "date", None) or getattr(item, "timestamp", None)
                         item_value = getattr(item, "val", None)
-                    
-                    if item_date is None or item_value is None:
+
+                    # NaN placeholders count as missing, like nulls
+                    if item_date is None or item_value is None or (isinstance(item_value, float) and np.isnan(item_value)):
                         continue
-                    
+
                     # Convert date to string if it's a date object
-                    if hasattr(item_date, 'strftime'):
+                    if hasattr(item_date, "strftime"):
                         item_date = item_date.strftime("%Y-%m-%d")
-                    elif isinstance(item_date, str):
-                        item_date = item_date
-                    else:
+                    elif not isinstance(item_date, str):
                         item_date = str(item_date)
-                    
-                    records.append(
-                        {
-                            "date": item_date,
-                            "asset": asset,
-                            "metric": metric,
-                            "value": item_value,
-                        }
-                    )
 
-        df = pd.DataFrame(records)
+                    dates.append(item_date)
+                    assets.append(asset)
+                    metric_names.append(metric)
+                    values.append(item_value)
 
-        if df.empty:
-            raise ValueError(
-                "No data returned from Artemis API for the requested metrics. "
-                "Verify API key, metric names, and date range."
-            )
-
-        required_columns = {"date", "asset", "metric", "value"}
-        missing_columns = required_columns - set(df.columns)
-        if missing_columns:
-            raise ValueError(
-                f"Artemis API response is missing expected fields: {missing_columns}. "
-                "Unable to pivot data."
-            )
+        if not values:
+            return pd.DataFrame()
 
         # pivot df to get metrics as columns and perform light transformations
-        pivoted_df = df.pivot(
-            index=["date", "asset"], columns="metric", values="value"
-        ).reset_index()
-
+        long_df = pd.DataFrame({"date": dates, "asset": assets, "metric": metric_names, "value": values})
+        pivoted_df = long_df.pivot(index=["date", "asset"], columns="metric", values="value").reset_index()
         pivoted_df.columns.name = None
         pivoted_df["date"] = pd.to_datetime(pivoted_df["date"])
-        pivoted_df = pivoted_df.set_index("date")
         return pivoted_df
 
 
+# Generated run ids are YYYYMMDD_HHMMSS_mmm_<8 hex> (local time, millisecond resolution,
+# then a random suffix), so they sort lexicographically in creation order and workers
+# starting in the same millisecond still get distinct ids. Older logs hold ids without
+# the suffix or the milliseconds.
+RUN_ID_FORMATS = ("%Y%m%d_%H%M%S_%f", "%Y%m%d_%H%M%S")
+RUN_ID_SUFFIX = re.compile(r"_[0-9a-f]{8,32}$")
+_run_id_lock = threading.Lock()
+_last_run_id_time = None
+
+
+def generate_run_id() -> str:
+    """
+    Create a filesystem-safe run id from the current time at millisecond resolution.
+
+    Ids are strictly increasing within the process: a run created in the same
+    millisecond as the previous one (or after the clock stepped back) is bumped
+    1ms past it, so sorting ids matches creation order even under burst load. The
+    uuid suffix keeps ids from separate workers or processes unique.
+    """
+    global _last_run_id_time
+    with _run_id_lock:
+        now = datetime.now()
+        now = now.replace(microsecond=now.microsecond // 1000 * 1000)
+        if _last_run_id_time is not None and now <= _last_run_id_time:
+            now = _last_run_id_time + timedelta(milliseconds=1)
+        _last_run_id_time = now
+    return now.strftime("%Y%m%d_%H%M%S_") + f"{now.microsecond // 1000:03d}_{uuid.uuid4().hex[:8]}"
+
+
+def parse_run_id_time(run_id: str) -> Optional[datetime]:
+    """Recover the creation time of a generated run id (any of its formats), or None for custom ids"""
+    if not isinstance(run_id, str):
+        return None
+    timestamp = RUN_ID_SUFFIX.sub("", run_id)
+    for fmt in RUN_ID_FORMATS:
+        try:
+            return datetime.strptime(timestamp, fmt)
+        except (TypeError, ValueError):
+            continue
+    return None
+
+
+class EmptyLegError(ValueError):
+    """Raised when a rebalance selects no assets for a leg and empty_leg_policy is 'error'"""
+
+
+class OverlappingLegsError(ValueError):
+    """Raised when a rebalance selects an asset for both legs and leg_overlap_policy is 'error'"""
+
+
 class FactorModel:
     """ "Class to format and prep data for factor modeling and track factor constituents per period"""
 
@@ -507,17 +1161,40 @@ class FactorModel:
         breakpoint: Optional[float] = None,
         min_assets: Optional[int] = None,
         weighting_method: Optional[str] = None,
+        run_id: Optional[str] = None,
+        empty_leg_policy: str = "skip",
+        leg_overlap_policy: str = "net",
+        portfolio_mode: str = "long_short",
+        deadline: Optional[float] = None,
+        stop_event: Optional[threading.Event] = None,
+        transaction_cost_bps: float = 0.0,
     ):
         self.factor = factor
         self.df = df
         self.min_assets = min_assets
         self.breakpoint = breakpoint
         self.weighting_method = weighting_method
-        self.factor_returns = {}  # {date: return}}
+        self.empty_leg_policy = empty_leg_policy
+        self.leg_overlap_policy = leg_overlap_policy
+        self.portfolio_mode = portfolio_mode  # "long_short", "long_only" or "short_only"
+        self.transaction_cost_bps = transaction_cost_bps
+        self.period_days = 7  # calendar days per row after resampling (set by the caller for other cadences)
+        self.deadline = deadline  # time.monotonic() value after which portfolio formation stops
+        self.stop_event = stop_event  # set to stop portfolio formation early, e.g. on shutdown
+        self.partial = False  # True when formation stopped before the last rebalance date
+        self.stopped_at = None  # first rebalance date not formed when partial
+        self.factor_returns = {}  # {date: return}}, net of transaction costs
+        self.gross_factor_returns = {}  # {date: return} before transaction costs
+        self.turnover = {}  # {date: sum of absolute weight changes across both legs at that rebalance}
         self.long_portfolio_returns = {}  # {date: return}
         self.short_portfolio_returns = {}  # {date: return}
+        self.period_ics = {}  # {date: rank IC of the signal vs that period's returns}
+        self.nan_signal_counts = {}  # {date: number of assets dropped for a NaN/inf signal before ranking}
+        self.empty_leg_dates = []  # rebalance dates where the breakpoint selected no assets for a leg
+        self.overlapping_assets = {}  # {date: assets selected for both legs before netting}
+        self.covariance_fallback_assets = 0  # inverse_variance holdings equal-weighted for lack of a variance
         self.factor_assets = {}  # {date: {long_portfolio: {asset: {weighting: weighting, price_pct_change: price_pct_change}}, short_portfolio: {asset: {weighting: weighting, price_pct_change: price_pct_change}}}}
-        self.run_id = datetime.now().strftime("%Y%m%d_%H%M%S")
+        self.run_id = run_id or generate_run_id()
         self.results_dict = {}
 
     def resample(self, freq: str, metric_agg_methods: dict):
@@ -532,9 +1209,9 @@ class FactorModel:
 
     def calculate_price_pct_change(self, periods: int = 1):
         """Helper to calculate the price pct change for each asset"""
-        self.df[f"price_pct_change_p{periods}"] = self.df.groupby("asset")[
-            "price"
-        ].pct_change(periods=periods)
+        self.df[f"price_pct_change_p{periods}"] = self.df.groupby("asset")["price"].transform(
+            lambda prices: simple_returns(prices, periods=periods)
+        )
 
     def get_t_minus_1_metrics(self, metrics: list):
         """Helper to get the t-1 metrics for each asset. The t-1 metric is used for rebalancing."""
@@ -557,42 +1234,421 @@ class FactorModel:
             self.df["date"] - self.df["min_asset_date"] >= pd.Timedelta(days=days)
         ]
 
-    def calculate_variance(self, returns_col: str = "price_pct_change_p1"):
-        """Helper to calculate variance and inverse variance for each asset over entire history."""
-        self.df["variance"] = self.df.groupby("asset")[returns_col].transform("var")
+    def normalize_by_market_cap(self, metrics: list, mc_col: str = "mc"):
+        """
+        Helper to express flow metrics (fees, revenue, ...) per dollar of market cap.
+
+        Without this, absolute-dollar metrics let large caps dominate purely by size.
+        Rows without a positive market cap can't be normalized and are dropped.
+        """
+        self.df = self.df[self.df[mc_col] > 0].copy()
+        for metric in metrics:
+            if metric in self.df.columns:
+                self.df[metric] = self.df[metric] / self.df[mc_col]
+
+    def minimum_valid_periods(self, signal_col: str, periods: int):
+        """
+        Helper to drop assets with fewer than `periods` rebalance dates carrying a full signal window.
+
+        Unlike the per-date filters, this excludes the asset from the whole run so it doesn't
+        flicker in and out of the universe. Call after the threshold filters.
+        """
+        valid_counts = (
+            self.df.dropna(subset=[signal_col]).groupby("asset")["date"].nunique()
+        )
+        eligible_assets = valid_counts[valid_counts >= periods].index
+        self.df = self.df[self.df["asset"].isin(eligible_assets)]
+
+    def should_stop(self, date) -> bool:
+        """
+        Helper to checkpoint portfolio formation before the rebalance on `date`.
+
+        Returns True once the deadline has passed or stop_event is set, marking the run
+        partial. Periods formed so far are kept, so the results cover every completed
+        rebalance up to (not including) stopped_at.
+        """
+        timed_out = self.deadline is not None and time.monotonic() >= self.deadline
+        stopped = self.stop_event is not None and self.stop_event.is_set()
+        if timed_out or stopped:
+            self.partial = True
+            self.stopped_at = date
+        return self.partial
+
+    def form_long_short_portfolios(
+        self,
+        signal_col: str,
+        long_high: bool = True,
+        returns_col: str = "price_pct_change_p1",
+        count_unrankable: bool = False,
+    ):
+        """
+        Rank assets on a signal each period and record long/short leg returns.
+
+        The top `breakpoint` share of assets by signal goes long (bottom share if
+        long_high is False) and the opposite share goes short. Periods with fewer
+        than min_assets rankable assets, or an empty leg, are skipped. With
+        count_unrankable, min_assets counts every asset on the date instead, including
+        those without a finite signal or a return (SMB has always counted this way).
+
+        When the breakpoint selects no assets for a leg (a small universe with a small
+        breakpoint), the date goes in empty_leg_dates and empty_leg_policy decides:
+        "skip" skips the rebalance and holds the prior period's legs through it (the
+        period is skipped outright if there are none yet), "error" raises EmptyLegError.
+
+        Legs are weighted by weighting_method (see leg_weights); a period whose leg
+        weights can't be formed, e.g. an asset with zero variance, is skipped too.
+        Under market_cap weighting, assets without a prior market cap can't be weighted
+        and are left out of that period's ranking. The weighted legs are then netted into one signed position per asset (see net_legs).
+        portfolio_mode decides which legs are held and so the factor return (see _record_legs).
+
+        Assets whose signal is NaN or infinite are dropped before ranking, since
+        sorting would otherwise push them to one end of the ranking and into a leg.
+        The number dropped per period is kept in nan_signal_counts.
+
+        Formation stops early, leaving a partial run, once should_stop() trips.
+        """
+        if self.weighting_method == "inverse_variance" and "variance" not in self.df.columns:
+            raise ValueError("inverse_variance weighting needs a variance column. Call calculate_variance() first.")
+
+        for date in self.df["date"].unique():
+            if self.should_stop(date):
+                break
+            period_data = self.df[self.df["date"] == date]
+            num_assets = len(period_data)
+            valid_signal = np.isfinite(period_data[signal_col].astype(float))
+            if not valid_signal.all():
+                self.nan_signal_counts[date] = int((~valid_signal).sum())
+            period_data = period_data[valid_signal].dropna(subset=[returns_col, *self._weight_cols()])
+            if (num_assets if count_unrankable else len(period_data)) < self.min_assets:
+                continue
+
+            # Rank IC, oriented so a positive value means the signal ranked the long side correctly
+            ic = period_data[signal_col].rank().corr(period_data[returns_col].rank())
+            self.period_ics[date] = ic if long_high else -ic
+
+            period_data = period_data.sort_values(signal_col, ascending=not long_high)
+            n = len(period_data)
+            cutoff = int(n * self.breakpoint)
+
+            long_portfolio = period_data.head(cutoff)
+            short_portfolio = period_data.tail(cutoff)
+
+            if len(long_portfolio) == 0 or len(short_portfolio) == 0:
+                self.empty_leg_dates.append(date)
+                if self.empty_leg_policy == "error":
+                    raise EmptyLegError(
+                        f"Breakpoint {self.breakpoint} selects no assets for a leg on "
+                        f"{pd.Timestamp(date):%Y-%m-%d} ({n} rankable assets)"
+                    )
+                self._hold_prior_legs(date, returns_col)
+                continue
+
+            try:
+                long_weights = self.leg_weights(long_portfolio)
+                short_weights = self.leg_weights(short_portfolio)
+            except ValueError:
+                continue
+            if self.portfolio_mode == "long_short":
+                long_portfolio, long_weights, short_portfolio, short_weights = self.net_legs(
+                    date, long_portfolio, long_weights, short_portfolio, short_weights
+                )
+
+            self._record_legs(
+                date,
+                float((long_weights * long_portfolio[returns_col]).sum()),
+                float((short_weights * short_portfolio[returns_col]).sum()),
+                self._portfolio_holdings(long_portfolio, returns_col, long_weights),
+                self._portfolio_holdings(short_portfolio, returns_col, short_weights),
+            )
+
+        self._apply_transaction_costs()
+
+    def _record_legs(self, date, long_return: float, short_return: float, long_holdings: dict, short_holdings: dict):
+        """
+        Helper to record a period's legs and combine them into the factor return per portfolio_mode.
+
+        "long_short" returns the long leg minus the shorted assets, "long_only" just the
+        long leg and "short_only" the short position (the shorted assets' negated return).
+        A leg that isn't held gets no holdings and no entry in its leg returns.
+        """
+        if self._holds_leg("long_portfolio"):
+            self.long_portfolio_returns[date] = long_return
+        else:
+            long_return, long_holdings = 0.0, {}
+        if self._holds_leg("short_portfolio"):
+            self.short_portfolio_returns[date] = short_return
+        else:
+            short_return, short_holdings = 0.0, {}
+        self.factor_returns[date] = long_return - short_return
+        self.factor_assets[date] = {"long_portfolio": long_holdings, "short_portfolio": short_holdings}
+
+    def net_legs(
+        self,
+        date,
+        long_portfolio: pd.DataFrame,
+        long_weights: pd.Series,
+        short_portfolio: pd.DataFrame,
+        short_weights: pd.Series,
+    ) -> tuple[pd.DataFrame, pd.Series, pd.DataFrame, pd.Series]:
+        """
+        Net a rebalance's weighted legs into a single signed position per asset.
+
+        An asset selected more than once, in both legs or twice in one (duplicate
+        rows, e.g. a ticker listed twice), holds its long weight minus its short
+        weight: a positive net stays long, a negative net goes short at its absolute
+        weight and a zero net is dropped. Leg weights can then sum to less than 1,
+        but long minus short is unchanged. Assets selected for both legs are recorded
+        in overlapping_assets first; with leg_overlap_policy "error" they raise
+        OverlappingLegsError instead. Returns the netted legs and their weights.
+        """
+        overlap = sorted(set(long_portfolio["asset"]) & set(short_portfolio["asset"]))
+        duplicated = long_portfolio["asset"].duplicated().any() or short_portfolio["asset"].duplicated().any()
+        if not overlap and not duplicated:
+            return long_portfolio, long_weights, short_portfolio, short_weights
+        if overlap:
+            self.overlapping_assets[date] = overlap
+            if self.leg_overlap_policy == "error":
+                raise OverlappingLegsError(
+                    f"{', '.join(map(str, overlap))} selected for both legs on {pd.Timestamp(date):%Y-%m-%d}"
+                )
+
+        rows = pd.concat([long_portfolio, short_portfolio], ignore_index=True)
+        signed = pd.Series(
+            np.concatenate([
+                long_weights.loc[long_portfolio.index].to_numpy(),
+                -short_weights.loc[short_portfolio.index].to_numpy(),
+            ])
+        )
+        net = signed.groupby(rows["asset"]).sum()
+        net = net[~np.isclose(net, 0)]
+        # Each asset keeps its first row (and so its period return) once netted
+        first_rows = rows.drop_duplicates("asset").set_index("asset", drop=False)
+
+        netted = []
+        for leg_net in (net[net > 0], -net[net < 0]):
+            leg = first_rows.loc[leg_net.index].reset_index(drop=True)
+            netted.extend([leg, pd.Series(leg_net.to_numpy(), index=leg.index)])
+        return tuple(netted)
+
+    def _hold_prior_legs(self, date, returns_col: str):
+        """
+        Helper to carry the latest formed legs through `date` instead of rebalancing.
+
+        Each leg keeps its prior weights, renormalized over the holdings that have a
+        return this period. Nothing is recorded if there are no prior legs or a held
+        leg has no holdings left; a leg portfolio_mode doesn't hold stays empty.
+        """
+        prior_dates = [prior for prior in self.factor_assets if prior < date]
+        if not prior_dates:
+            return
+        prior_legs = self.factor_assets[max(prior_dates)]
+
+        period_returns = (
+            self.df[self.df["date"] == date].dropna(subset=[returns_col]).set_index("asset")[returns_col]
+        )
+        held_legs = {}
+        for leg, holdings in prior_legs.items():
+            if not holdings and not self._holds_leg(leg):
+                held_legs[leg] = {}
+                continue
+            held = {asset: holding["weighting"] for asset, holding in holdings.items() if asset in period_returns.index}
+            total_weight = sum(held.values())
+            if not held or total_weight <= 0:
+                return
+            held_legs[leg] = {
+                asset: {"weighting": weight / total_weight, "price_pct_change": float(period_returns[asset])}
+                for asset, weight in held.items()
+            }
+
+        long_return = sum(h["weighting"] * h["price_pct_change"] for h in held_legs["long_portfolio"].values())
+        short_return = sum(h["weighting"] * h["price_pct_change"] for h in held_legs["short_portfolio"].values())
+        self._record_legs(date, long_return, short_return, held_legs["long_portfolio"], held_legs["short_portfolio"])
+
+    def _holds_leg(self, leg: str) -> bool:
+        """Whether portfolio_mode holds the "long_portfolio" or "short_portfolio" leg"""
+        if leg == "long_portfolio":
+            return self.portfolio_mode != "short_only"
+        return self.portfolio_mode != "long_only"
+
+    def form_long_only_portfolio(
+        self,
+        signal_col: str,
+        top_n: int,
+        weight_col: Optional[str] = None,
+        returns_col: str = "price_pct_change_p1",
+    ):
+        """
+        Hold the top_n assets by signal each period in a single long portfolio.
+
+        Holdings are weighted by weight_col (normalized to sum to 1), or equally when
+        it's None. Periods with fewer than top_n rankable assets are skipped; NaN/inf
+        signals are dropped first, and formation stops early once should_stop() trips,
+        as for long/short portfolios.
+        """
+        for date in self.df["date"].unique():
+            if self.should_stop(date):
+                break
+            period_data = self.df[self.df["date"] == date]
+            valid_signal = np.isfinite(period_data[signal_col].astype(float))
+            if not valid_signal.all():
+                self.nan_signal_counts[date] = int((~valid_signal).sum())
+            period_data = period_data[valid_signal].dropna(subset=[returns_col])
+            if len(period_data) < top_n:
+                continue
+
+            portfolio = period_data.nlargest(top_n, signal_col)
+            if weight_col is None:
+                weights = pd.Series(1 / len(portfolio), index=portfolio.index)
+            else:
+                weights = portfolio[weight_col] / portfolio[weight_col].sum()
+                if not np.isfinite(weights).all():
+                    continue
+
+            long_return = float((weights * portfolio[returns_col]).sum())
+            self.factor_returns[date] = long_return
+            self.long_portfolio_returns[date] = long_return
+            self.factor_assets[date] = {
+                "long_portfolio": self._portfolio_holdings(portfolio, returns_col, weights),
+                "short_portfolio": {},
+            }
+
+        self._apply_transaction_costs()
+
+    def _apply_transaction_costs(self):
+        """
+        Helper to record each rebalance's turnover and take transaction costs out of the period returns.
+
+        Turnover is the sum over both legs of the absolute change in each asset's weight
+        from the previous rebalance's target weights (the first rebalance is measured
+        from cash, so building the book costs a turnover of 1 per leg). Each leg pays
+        transaction_cost_bps on its own turnover: the long leg's return is reduced and the
+        shorted assets' return raised, so the factor return (long minus short) is net of
+        both. gross_factor_returns keeps the returns before costs.
+        """
+        cost = self.transaction_cost_bps / 10_000
+        self.gross_factor_returns = dict(self.factor_returns)
+        previous = {"long_portfolio": {}, "short_portfolio": {}}
+        for date in sorted(self.factor_assets):
+            leg_turnover = {}
+            for leg, prior in previous.items():
+                current = {asset: holding["weighting"] for asset, holding in self.factor_assets[date][leg].items()}
+                leg_turnover[leg] = sum(
+                    abs(current.get(asset, 0.0) - prior.get(asset, 0.0)) for asset in set(current) | set(prior)
+                )
+                previous[leg] = current
+            self.turnover[date] = leg_turnover["long_portfolio"] + leg_turnover["short_portfolio"]
+
+            if cost:
+                if date in self.long_portfolio_returns:
+                    self.long_portfolio_returns[date] -= cost * leg_turnover["long_portfolio"]
+                if date in self.short_portfolio_returns:
+                    self.short_portfolio_returns[date] += cost * leg_turnover["short_portfolio"]
+                self.factor_returns[date] -= cost * self.turnover[date]
+
+    def _portfolio_holdings(
+        self, portfolio_df: pd.DataFrame, returns_col: str, weights: Optional[pd.Series] = None
+    ) -> dict:
+        """
+        Helper to build the {asset: {weighting, price_pct_change}} holdings of a leg.
+
+        Legs are equal-weighted unless per-row weights are given. When price and period
+        volume are available, each holding also records its average daily dollar volume
+        over the period (period_days long), used for capacity estimates.
+        """
+        if weights is None:
+            weights = pd.Series(1 / len(portfolio_df), index=portfolio_df.index)
+        holdings = {
+            asset: {"weighting": float(weighting), "price_pct_change": float(price_pct_change)}
+            for asset, weighting, price_pct_change in zip(
+                portfolio_df["asset"], weights.loc[portfolio_df.index], portfolio_df[returns_col]
+            )
+        }
+        if {"price", "24h_volume"} <= set(portfolio_df.columns):
+            # Period rows sum daily volume, which Coinbase reports in base units
+            daily_dollar_volume = portfolio_df["24h_volume"] * portfolio_df["price"] / self.period_days
+            for asset, dollar_volume in zip(portfolio_df["asset"], daily_dollar_volume):
+                if pd.notna(dollar_volume):
+                    holdings[asset]["daily_dollar_volume"] = float(dollar_volume)
+        return holdings
+
+    def calculate_variance(self, returns_col: str = "price_pct_change_p1", lookback_periods: Optional[int] = None):
+        """
+        Helper to calculate variance and inverse variance for each asset.
+
+        With lookback_periods, each period's variance is taken over the trailing
+        lookback_periods returns before it, so weights only use returns known at the
+        rebalance; periods without a full window get NaN. Otherwise it's the variance
+        over the asset's entire history.
+        """
+        if lookback_periods is None:
+            self.df["variance"] = self.df.groupby("asset")[returns_col].transform("var")
+        else:
+            self.df["variance"] = self.df.groupby("asset")[returns_col].transform(
+                lambda returns: returns.shift(1).rolling(lookback_periods, min_periods=lookback_periods).var()
+            )
         self.df["inverse_variance"] = 1 / self.df["variance"]
         # Replace inf with NaN for cleaner handling
         self.df["inverse_variance"] = self.df["inverse_variance"].replace(
             [np.inf, -np.inf], np.nan
         )
 
+    def leg_weights(self, portfolio_df: pd.DataFrame) -> pd.Series:
+        """
+        Weights for a leg's holdings under weighting_method, summing to 1.
+
+        market_cap uses mc_t_minus_1 and inverse_variance the variance column from
+        calculate_variance(); no method (or "equal") weights equally. Raises
+        ValueError when the weights can't be formed.
+
+        Under inverse_variance, holdings without a variance (no full covariance
+        window yet) get an equal 1/n share and the rest split the remainder by
+        inverse variance. They're counted in covariance_fallback_assets.
+        """
+        method = self.weighting_method or "equal"
+        if method == "inverse_variance":
+            missing = ~np.isfinite(portfolio_df["variance"].astype(float))
+            if missing.any():
+                self.covariance_fallback_assets += int(missing.sum())
+                weights = pd.Series(1 / len(portfolio_df), index=portfolio_df.index)
+                estimated = portfolio_df[~missing]
+                if not estimated.empty:
+                    weights[estimated.index] = apply_weights(
+                        len(estimated), method, variances=estimated["variance"]
+                    ) * (len(estimated) / len(portfolio_df))
+                return weights
+
+        weights = apply_weights(
+            len(portfolio_df),
+            method,
+            market_caps=portfolio_df["mc_t_minus_1"] if method == "market_cap" else None,
+            variances=portfolio_df["variance"] if "variance" in portfolio_df.columns else None,
+        )
+        return pd.Series(weights, index=portfolio_df.index)
+
+    def _weight_cols(self) -> list:
+        """Helper listing the columns a holding needs a value in for weighting_method to weight it"""
+        return ["mc_t_minus_1"] if self.weighting_method == "market_cap" else []
+
     def get_weighted_return(
         self, portfolio_df: pd.DataFrame, returns_col: str = "price_pct_change_p1"
     ) -> float:
         """
         Calculate weighted return for a portfolio based on weighting_method.
 
-        Supports: 'equal', 'market_cap', 'inverse_variance'
-        """
-        if self.weighting_method == "equal":
-            return portfolio_df[returns_col].mean()
-        elif self.weighting_method == "market_cap":
-            weights = portfolio_df["mc_t_minus_1"].astype(float)
-            numerator = (portfolio_df[returns_col].astype(float) * weights).sum()
-            denominator = weights.sum()
-            return numerator / denominator if denominator != 0 else np.nan
-        elif self.weighting_method == "inverse_variance":
-            if "inverse_variance" not in portfolio_df.columns:
-                raise ValueError(
-                    "inverse_variance column not found. Call calculate_variance() first."
-                )
-            weights = portfolio_df["inverse_variance"].astype(float)
-            numerator = (portfolio_df[returns_col].astype(float) * weights).sum()
-            denominator = weights.sum()
-            return numerator / denominator if denominator != 0 else np.nan
-        else:
-            # Default to equal weighting
-            return portfolio_df[returns_col].mean()
+        Supports: 'equal', 'market_cap', 'inverse_variance'. Holdings without a market
+        cap are left out under market_cap; a portfolio with nothing left to weight,
+        or whose weights can't be formed, returns NaN.
+        """
+        if self.weighting_method == "inverse_variance" and "variance" not in portfolio_df.columns:
+            raise ValueError("inverse_variance weighting needs a variance column. Call calculate_variance() first.")
+        portfolio_df = portfolio_df.dropna(subset=self._weight_cols())
+        if portfolio_df.empty:
+            return np.nan
+        try:
+            weights = self.leg_weights(portfolio_df)
+        except ValueError:
+            return np.nan
+        return float((weights * portfolio_df[returns_col].astype(float)).sum())
 
     def get_asset_beta(
         self, asset: str, min_observations: int = 52, asset_type: str = "crypto"
@@ -922,6 +1978,45 @@ class FactorModel:
             return {"error": str(e)}
 
 
+@contextmanager
+def manifest_lock(log_dir, factor: str):
+    """
+    Hold the exclusive lock on a factor's log csv and {factor}_index.json manifest.
+
+    Every read-modify-write of either (appending a log row, registering a run's files,
+    purging) runs under it, so concurrent runs and processes can't lose each other's
+    entries. The lock isn't reentrant: don't take it while already holding it.
+    """
+    log_dir = Path(log_dir)
+    log_dir.mkdir(parents=True, exist_ok=True)
+    with open(log_dir / f"{factor}_index.lock", "w") as lock_file:
+        fcntl.flock(lock_file, fcntl.LOCK_EX)
+        try:
+            yield
+        finally:
+            fcntl.flock(lock_file, fcntl.LOCK_UN)
+
+
+def replace_file(path: Path, write: Callable, newline: Optional[str] = None):
+    """
+    Write a file via a uniquely named temp file in its directory, then swap it into place.
+
+    Readers never see a partial file, and concurrent writers never share a temp file.
+    `write` is called with the open temp file.
+    """
+    with tempfile.NamedTemporaryFile(
+        "w", dir=path.parent, prefix=f".{path.name}.", suffix=".tmp", newline=newline, delete=False
+    ) as f:
+        tmp_path = Path(f.name)
+        try:
+            write(f)
+        except BaseException:
+            f.close()
+            tmp_path.unlink(missing_ok=True)
+            raise
+    os.replace(tmp_path, path)
+
+
 class Logger:
     """Class to log the results of the factor model to csv"""
 
@@ -933,8 +2028,26 @@ class Logger:
         self.weighting_method = factor_model.weighting_method
         self.run_id = factor_model.run_id
 
+    def _ensure_log_dir(self):
+        """
+        Helper to recreate the log directory if it was removed after startup.
+
+        The API creates it once at startup; this is a cheap, idempotent fallback
+        that is safe when concurrent requests race to create it.
+        """
+        self.log_dir.mkdir(parents=True, exist_ok=True)
+
+    def lock(self):
+        """Helper to hold the factor's manifest_lock"""
+        return manifest_lock(self.log_dir, self.factor)
+
     def log_results(self, results_dict: Optional[dict] = None):
-        """Helper to log the results to a csv"""
+        """
+        Helper to log the results to a csv.
+
+        Raises FileExistsError, without writing anything, if the run id is already
+        indexed or has saved files.
+        """
         base_results_dict = {
             "run_id": self.run_id,
             "factor": self.factor,
@@ -949,16 +2062,327 @@ class Logger:
 
         results_df = pd.DataFrame(results_dict, index=[0])
         file_path = self.log_dir / f"{self.factor}.csv"
-        # if directory doesn't exist, create it
-        if not self.log_dir.exists():
-            self.log_dir.mkdir(parents=True, exist_ok=True)
-        file_exists = file_path.exists()
-        results_df.to_csv(
-            file_path,
-            mode="a",  # Append mode
-            header=not file_exists,  # Only write header if file doesn't exist
-            index=False,
+        self._ensure_log_dir()
+        with self.lock():
+            self._ensure_run_not_logged()
+            file_exists = file_path.exists()
+            if file_exists:
+                header = self._migrate_log_header(file_path, list(results_df.columns))
+                # Write values in the file's column order, leaving columns this run lacks empty
+                results_df = results_df.reindex(columns=header)
+            results_df.to_csv(
+                file_path,
+                mode="a",  # Append mode
+                header=not file_exists,  # Only write header if file doesn't exist
+                index=False,
+            )
+
+    def _ensure_run_not_logged(self):
+        """Helper to refuse logging a run id that's already indexed or has saved files"""
+        if self.run_id in self.load_manifest()["runs"]:
+            raise FileExistsError(f"Run {self.run_id} is already indexed for {self.factor}")
+        for suffix in ("returns.csv", "returns.parquet", "compositions.json", "asset_returns.csv"):
+            if (self.log_dir / f"{self.factor}_{self.run_id}_{suffix}").exists():
+                raise FileExistsError(f"{self.factor}_{self.run_id}_{suffix} already exists for run {self.run_id}")
+
+    @staticmethod
+    def _migrate_log_header(file_path: Path, columns: list) -> list:
+        """
+        Helper to widen a log csv's header before appending a row with new columns.
+
+        Existing rows are rewritten under the superset header, backfilled with empty
+        values, so appended rows stay aligned. Rows that are already wider than their
+        header (appended before this migration existed) keep their extra values in
+        the following columns. Returns the header to write rows against.
+        """
+        with open(file_path, newline="") as f:
+            rows = list(csv.reader(f))
+        if not rows:
+            return columns
+
+        header = rows[0]
+        new_columns = [column for column in columns if column not in header]
+        if not new_columns:
+            return header
+
+        header = header + new_columns
+
+        def write(f):
+            writer = csv.writer(f)
+            writer.writerow(header)
+            for row in rows[1:]:
+                if row:
+                    writer.writerow(row + [""] * (len(header) - len(row)))
+
+        replace_file(file_path, write, newline="")
+        logger.info(f"Migrated {file_path.name} header with new columns: {new_columns}")
+        return header
+
+    def save_time_series(self, factor_returns: dict, period_ics: Optional[dict] = None, log_format: str = "csv"):
+        """
+        Helper to save the per-period returns of a run to {factor}_{run_id}_returns.csv.
+
+        Per-period information coefficients, when given, are stored in an `ic` column.
+        With log_format="parquet" the series goes to a .parquet file instead (see
+        save_time_series_parquet).
+        """
+        if log_format == "parquet":
+            self.save_time_series_parquet(factor_returns, period_ics)
+            return
+        returns_df = self._time_series_frame(factor_returns, period_ics)
+        returns_df["date"] = returns_df["date"].dt.strftime("%Y-%m-%d")
+        self._ensure_log_dir()
+        file_name = f"{self.factor}_{self.run_id}_returns.csv"
+        with self.lock():
+            self._ensure_not_indexed("returns", file_name)
+            returns_df.to_csv(self.log_dir / file_name, index=False)
+            self._register_run_file("returns", file_name)
+
+    def save_time_series_parquet(self, factor_returns: dict, period_ics: Optional[dict] = None):
+        """
+        Helper to save the per-period returns of a run to {factor}_{run_id}_returns.parquet.
+
+        Same columns as the CSV (date, return, cumulative_return and optional ic), with
+        dates stored as timestamps, so long histories load without parsing text.
+        """
+        returns_df = self._time_series_frame(factor_returns, period_ics)
+        self._ensure_log_dir()
+        file_name = f"{self.factor}_{self.run_id}_returns.parquet"
+        with self.lock():
+            self._ensure_not_indexed("returns", file_name)
+            returns_df.to_parquet(self.log_dir / file_name, index=False)
+            self._register_run_file("returns", file_name)
+
+    @staticmethod
+    def load_time_series_parquet(file_path) -> pd.DataFrame:
+        """Helper to load a returns series saved by save_time_series_parquet"""
+        returns_df = pd.read_parquet(file_path)
+        returns_df["date"] = pd.to_datetime(returns_df["date"])
+        return returns_df
+
+    @staticmethod
+    def _time_series_frame(factor_returns: dict, period_ics: Optional[dict] = None) -> pd.DataFrame:
+        """Helper to build the date / return / cumulative_return (/ ic) frame saved for a run"""
+        returns_df = cumulative_returns(factor_returns).rename(
+            columns={"value": "return", "cumulative_returns": "cumulative_return"}
         )
+        if period_ics is not None:
+            returns_df["ic"] = [period_ics.get(date) for date in factor_returns]
+        returns_df["date"] = pd.to_datetime(returns_df["date"]).dt.normalize()
+        return returns_df
+
+    def save_compositions(self, factor_assets: dict):
+        """Helper to save the long/short holdings at each rebalance to {factor}_{run_id}_compositions.json"""
+        compositions = [
+            {
+                "date": pd.Timestamp(date).strftime("%Y-%m-%d"),
+                "long_portfolio": portfolios.get("long_portfolio", {}),
+                "short_portfolio": portfolios.get("short_portfolio", {}),
+            }
+            for date, portfolios in sorted(factor_assets.items())
+        ]
+        self._ensure_log_dir()
+        file_name = f"{self.factor}_{self.run_id}_compositions.json"
+        with self.lock():
+            self._ensure_not_indexed("compositions", file_name)
+            with open(self.log_dir / file_name, "w") as f:
+                json.dump(compositions, f)
+            self._register_run_file("compositions", file_name)
+
+    def save_asset_returns(self, df: pd.DataFrame, returns_col: str = "price_pct_change_p1"):
+        """
+        Helper to save the per-asset period returns a run ranked on to {factor}_{run_id}_asset_returns.csv.
+
+        One row per (date, asset) in the filtered universe, held or not, so return
+        diagnostics can be run on the data behind a backtest after the fact.
+        """
+        asset_returns = df.dropna(subset=[returns_col])[["date", "asset", returns_col]].rename(
+            columns={returns_col: "return"}
+        )
+        asset_returns["date"] = pd.to_datetime(asset_returns["date"]).dt.strftime("%Y-%m-%d")
+        self._ensure_log_dir()
+        file_name = f"{self.factor}_{self.run_id}_asset_returns.csv"
+        with self.lock():
+            self._ensure_not_indexed("asset_returns", file_name)
+            asset_returns.sort_values(["date", "asset"]).to_csv(self.log_dir / file_name, index=False)
+            self._register_run_file("asset_returns", file_name)
+
+    @classmethod
+    def purge(cls, log_dir: str, factor: str, older_than: datetime) -> list:
+        """
+        Helper to delete a factor's runs created before `older_than`: log rows, saved files and manifest entries.
+
+        A run's age is its manifest creation time, else the timestamp in an auto-generated
+        run id, else its logged end_date. Runs with no known age are kept. Safe to repeat:
+        already-deleted files are skipped. Holds the factor's manifest_lock. Returns the purged run ids.
+        """
+        log_dir = Path(log_dir)
+        # Held through the whole purge, so no run is logged or indexed between reading and rewriting
+        with manifest_lock(log_dir, factor):
+            manifest_path = log_dir / f"{factor}_index.json"
+            manifest = {"factor": str(factor), "runs": {}}
+            if manifest_path.exists():
+                with open(manifest_path) as f:
+                    manifest = json.load(f)
+
+            def created_at(run_id: str, end_date: Optional[str] = None) -> Optional[datetime]:
+                if run_id in manifest["runs"]:
+                    return datetime.fromisoformat(manifest["runs"][run_id]["created_at"])
+                if (generated_at := parse_run_id_time(run_id)) is not None:
+                    return generated_at
+                try:
+                    return datetime.strptime(end_date, "%Y-%m-%d")
+                except (TypeError, ValueError):
+                    return None
+
+            expired = {
+                run_id for run_id in manifest["runs"] if (created := created_at(run_id)) and created < older_than
+            }
+
+            # Rewrite the log csv without expired rows
+            log_path = log_dir / f"{factor}.csv"
+            if log_path.exists():
+                with open(log_path, newline="") as f:
+                    rows = list(csv.reader(f))
+                if rows and "run_id" in rows[0]:
+                    header = rows[0]
+                    run_col = header.index("run_id")
+                    end_col = header.index("end_date") if "end_date" in header else None
+                    kept = []
+                    for row in rows[1:]:
+                        if not row:
+                            continue
+                        run_id = row[run_col]
+                        end_date = row[end_col] if end_col is not None and end_col < len(row) else None
+                        created = created_at(run_id, end_date)
+                        if created and created < older_than:
+                            expired.add(run_id)
+                        else:
+                            kept.append(row)
+                    if len(kept) < len(rows) - 1:
+                        replace_file(log_path, lambda f: csv.writer(f).writerows([header] + kept), newline="")
+
+            for run_id in expired:
+                files = manifest["runs"].pop(run_id, {}).get("files", {})
+                file_names = set(files.values()) | {
+                    f"{factor}_{run_id}_returns.csv",
+                    f"{factor}_{run_id}_returns.parquet",
+                    f"{factor}_{run_id}_compositions.json",
+                    f"{factor}_{run_id}_asset_returns.csv",
+                }
+                for file_name in file_names:
+                    (log_dir / file_name).unlink(missing_ok=True)
+
+            if expired and manifest_path.exists():
+                replace_file(manifest_path, lambda f: json.dump(manifest, f, indent=2))
+
+            return sorted(expired)
+
+    @classmethod
+    def delete_factor_logs(cls, log_dir: str, factor: str, other_factors: tuple = ()) -> list:
+        """
+        Helper to delete every log file of a factor: its log csv, manifest and each run's saved files.
+
+        Run files are matched by name, so files of another factor whose name extends this
+        one (momentum_v2's "momentum_v2_*" files also match "momentum_*") are left alone;
+        pass the other factor names as `other_factors`. Holds the factor's manifest_lock.
+        Returns the deleted file names.
+        """
+        log_dir = Path(log_dir)
+        others = [other for other in other_factors if other != factor and other.startswith(f"{factor}_")]
+        deleted = set()
+        with manifest_lock(log_dir, factor):
+            candidates = [log_dir / f"{factor}.csv", log_dir / f"{factor}_index.json"]
+            for suffix in ("returns.csv", "returns.parquet", "compositions.json", "asset_returns.csv"):
+                candidates.extend(
+                    path
+                    for path in log_dir.glob(f"{factor}_*_{suffix}")
+                    if not any(path.name.startswith(f"{other}_") for other in others)
+                )
+            for path in candidates:
+                if path.name not in deleted and path.exists():
+                    path.unlink(missing_ok=True)
+                    deleted.add(path.name)
+        return sorted(deleted)
+
+    @property
+    def manifest_path(self) -> Path:
+        """Path of the {factor}_index.json manifest listing each run's files"""
+        return self.log_dir / f"{self.factor}_index.json"
+
+    def load_manifest(self) -> dict:
+        """Helper to load the {factor}_index.json manifest of runs and their files"""
+        if not self.manifest_path.exists():
+            return {"factor": str(self.factor), "runs": {}}
+        with open(self.manifest_path) as f:
+            return json.load(f)
+
+    def _write_manifest(self, manifest: dict):
+        """Helper to replace the manifest atomically so readers never see a partial file. Hold lock()"""
+        replace_file(self.manifest_path, lambda f: json.dump(manifest, f, indent=2))
+
+    def _ensure_not_indexed(self, kind: str, file_name: str):
+        """Helper to refuse overwriting a file already saved for this run id. Hold lock() through the write"""
+        run = self.load_manifest()["runs"].get(self.run_id, {})
+        if kind in run.get("files", {}) or (self.log_dir / file_name).exists():
+            raise FileExistsError(f"{file_name} already exists for run {self.run_id}")
+
+    def _register_run_file(self, kind: str, file_name: str):
+        """Helper to record a saved file under its run in the manifest. Hold lock()"""
+        manifest = self.load_manifest()
+        run = manifest["runs"].setdefault(
+            self.run_id, {"created_at": datetime.now().isoformat(timespec="seconds"), "files": {}}
+        )
+        run["files"][kind] = file_name
+        self._write_manifest(manifest)
+
+    def save_config(self, config: dict, schema_version: int):
+        """
+        Helper to record the run's full config in the manifest, with the config schema
+        version it was written under, so the run can be reproduced later.
+        """
+        self._ensure_log_dir()
+        with self.lock():
+            manifest = self.load_manifest()
+            run = manifest["runs"].setdefault(
+                self.run_id, {"created_at": datetime.now().isoformat(timespec="seconds"), "files": {}}
+            )
+            run["config"] = config
+            run["config_schema_version"] = schema_version
+            self._write_manifest(manifest)
+
+    def find_orphaned_files(self) -> dict:
+        """
+        Helper to compare the manifest with the log directory.
+
+        Returns files on disk that no run lists ("unindexed") and files the
+        manifest lists that no longer exist ("missing").
+        """
+        # Read every factor's manifest, since "momentum_*" also matches momentum_v2's files
+        indexed = set()
+        for manifest_path in self.log_dir.glob("*_index.json"):
+            with open(manifest_path) as f:
+                runs = json.load(f).get("runs", {})
+            indexed.update(file_name for run in runs.values() for file_name in run.get("files", {}).values())
+        own_files = {
+            file_name
+            for run in self.load_manifest()["runs"].values()
+            for file_name in run.get("files", {}).values()
+        }
+        on_disk = {
+            path.name
+            for pattern in (
+                f"{self.factor}_*_returns.csv",
+                f"{self.factor}_*_returns.parquet",
+                f"{self.factor}_*_compositions.json",
+                f"{self.factor}_*_asset_returns.csv",
+            )
+            for path in self.log_dir.glob(pattern)
+        }
+        return {
+            "unindexed": sorted(on_disk - indexed),
+            "missing": sorted(file_name for file_name in own_files if not (self.log_dir / file_name).exists()),
+        }
 
 
 # Util Helper Functions
@@ -1051,15 +2475,36 @@ def get_risk_free_rate(
     return rf_df
 
 
+class EmptyUniverseError(ValueError):
+    """Raised when no assets survive universe discovery, before any data is fetched"""
+
+
+def normalize_dates(dates: pd.Series) -> pd.Series:
+    """
+    Floor timestamps to their calendar day in UTC, returned tz-naive.
+
+    Tz-aware values are converted to UTC first; naive values are taken as UTC.
+    Lets sources that stamp the same day differently (midnight vs. a candle
+    boundary a few hours off) join on date.
+    """
+    dates = pd.to_datetime(dates, utc=True)
+    return dates.dt.tz_localize(None).dt.floor("D")
+
+
 def fetch_merged_crypto_data(
     start_date: str,
     end_date: str,
     artemis_metrics: list[str] | None = None,
     api_key: str | None = None,
+    data_quality: dict | None = None,
+    required_metrics: list[str] | None = None,
+    join: str = "inner",
+    universe: list[str] | None = None,
+    progress: ProgressCallback | None = None,
 ) -> pd.DataFrame:
     """
     Fetch on-chain metrics from Artemis and price+volume from Coinbase,
-    then inner-merge on (date, asset).
+    then merge on (date, asset) after flooring both to the UTC calendar day.
 
     Args:
         start_date: YYYY-MM-DD
@@ -1067,35 +2512,60 @@ def fetch_merged_crypto_data(
         artemis_metrics: Metrics to fetch from Artemis (default: ["mc"]).
                          price and 24h_volume always come from Coinbase.
         api_key: Artemis API key. Defaults to module-level API_KEY.
+        data_quality: Optional dict populated with a "dropped_assets" mapping of
+                      asset -> reason for every asset excluded from the merge, and a
+                      "null_metric_series" mapping of kept asset -> metrics it has no values for.
+        required_metrics: Metrics an asset must have at least one value of to be kept
+                          (default: all of artemis_metrics). Assets missing only other
+                          metrics are kept with those columns null.
+        join: "inner" keeps only days with both metrics and a price; "left" keeps
+              every Artemis row, with price and volume null on days without a candle.
+              data_quality gets the number of such rows as "unmatched_metric_rows".
+        universe: Allowlist of assets to use instead of the full Artemis universe,
+                  resolved forgivingly to Artemis slugs (see resolve_artemis_symbol).
+                  Names that resolve to no Coinbase mapping are dropped as
+                  "no_coinbase_mapping" under the name given.
+        progress: Called with each fetch phase and its percent complete as the
+                  Artemis batches, then the Coinbase symbols, are fetched.
 
     Returns:
         DataFrame with index=date, columns=[asset, price, mc, 24h_volume, ...]
     """
     if artemis_metrics is None:
         artemis_metrics = ["mc"]
+    if required_metrics is None:
+        required_metrics = artemis_metrics
     if api_key is None:
         api_key = API_KEY
 
-    # 1. Fetch on-chain metrics from Artemis
-    api_data = ApiData(api_key)
-    artemis_df = api_data.get_metric_for_all_symbols(
-        metrics=artemis_metrics,
-        start_date=start_date,
-        end_date=end_date,
-    )
-    artemis_df = artemis_df.reset_index()
-    artemis_df["date"] = pd.to_datetime(artemis_df["date"])
-
-    # 2. Intersect Artemis symbols with Coinbase-mapped symbols
-    artemis_symbols = artemis_df["asset"].unique().tolist()
+    # 1. Discover the universe: Artemis symbols (or the allowlist) with a Coinbase mapping.
+    # Checked before any metric or price fetch so an empty universe fails fast.
+    api_data = ApiData(api_key).with_cache()
+    if universe is not None:
+        artemis_symbols = list(dict.fromkeys(resolve_artemis_symbol(name) or name for name in universe))
+    else:
+        artemis_symbols = api_data.list_crypto_symbols()
     coinbase_symbols = [s for s in artemis_symbols if s in ARTEMIS_TO_COINBASE_MAP]
 
     if not coinbase_symbols:
-        raise ValueError(
+        if universe is not None:
+            raise EmptyUniverseError(f"No assets in the universe allowlist have Coinbase mappings: {universe}")
+        raise EmptyUniverseError(
             "No Artemis symbols have Coinbase mappings. "
             "Check ARTEMIS_TO_COINBASE_MAP coverage."
         )
 
+    # 2. Fetch on-chain metrics from Artemis for the tradeable universe
+    artemis_df = api_data.get_metric_for_all_symbols(
+        metrics=artemis_metrics,
+        start_date=start_date,
+        end_date=end_date,
+        symbols=coinbase_symbols,
+        progress=progress,
+    )
+    artemis_df = artemis_df.reset_index()
+    artemis_df["date"] = normalize_dates(artemis_df["date"])
+
     logger.info(
         f"Fetching Coinbase data for {len(coinbase_symbols)} symbols "
         f"(out of {len(artemis_symbols)} Artemis symbols)"
@@ -1103,15 +2573,66 @@ def fetch_merged_crypto_data(
 
     # 3. Fetch price + volume from Coinbase
     cb = CoinbaseData()
-    coinbase_df = cb.get_price_volume_for_symbols(
-        coinbase_symbols, start_date, end_date
+    coinbase_df, skipped = cb.get_price_volume_for_symbols(
+        coinbase_symbols, start_date, end_date, progress=progress
     )
 
     if coinbase_df.empty:
         raise ValueError("No price/volume data returned from Coinbase.")
 
-    # 4. Inner merge on (date, asset)
-    merged = artemis_df.merge(coinbase_df, on=["date", "asset"], how="inner")
+    # 4. Drop assets missing one side of the join, recording why
+    dropped_assets = {
+        symbol: "no_coinbase_mapping"
+        for symbol in artemis_symbols
+        if symbol not in ARTEMIS_TO_COINBASE_MAP
+    }
+    for symbol in skipped:
+        dropped_assets.setdefault(symbol, "missing_prices")
+    priced_symbols = set(coinbase_df["asset"].unique())
+
+    # Artemis can return a metric with an empty or all-null series for an asset
+    # (the pivot fills gaps with NaN), which leaves it priced but unrankable on
+    # that metric: treat the series as missing, dropping the asset if it's required
+    metric_counts = (
+        artemis_df.reindex(columns=["asset", *artemis_metrics])
+        .groupby("asset")[artemis_metrics]
+        .count()
+    )
+    null_metric_series = {}
+    for symbol in priced_symbols:
+        if symbol not in metric_counts.index:
+            null_metrics = list(artemis_metrics)
+        else:
+            null_metrics = [m for m in artemis_metrics if metric_counts.at[symbol, m] == 0]
+        missing = [m for m in required_metrics if m in null_metrics]
+        if missing:
+            dropped_assets[symbol] = f"missing_metrics: {', '.join(missing)}"
+        elif null_metrics:
+            null_metric_series[symbol] = null_metrics
+
+    if dropped_assets:
+        logger.info(f"Dropping assets missing price or metric data: {dropped_assets}")
+    if null_metric_series:
+        logger.info(f"Keeping assets without values for optional metrics: {null_metric_series}")
+    if data_quality is not None:
+        data_quality["dropped_assets"] = dropped_assets
+        data_quality["null_metric_series"] = null_metric_series
+
+    coinbase_df = coinbase_df[~coinbase_df["asset"].isin(dropped_assets.keys())]
+
+    # 5. Merge on (date, asset) once both sides are on the same daily grid.
+    # Flooring can put two rows on one day; the later one wins.
+    coinbase_df = coinbase_df.assign(date=normalize_dates(coinbase_df["date"]))
+    coinbase_df = coinbase_df.drop_duplicates(subset=["date", "asset"], keep="last")
+    artemis_df = artemis_df.drop_duplicates(subset=["date", "asset"], keep="last")
+    artemis_df = artemis_df[~artemis_df["asset"].isin(dropped_assets.keys())]
+    merged = artemis_df.merge(coinbase_df, on=["date", "asset"], how="left", indicator=True)
+    unmatched = merged["_merge"] == "left_only"
+    if data_quality is not None:
+        data_quality["unmatched_metric_rows"] = int(unmatched.sum())
+    if join == "inner":
+        merged = merged[~unmatched]
+    merged = merged.drop(columns="_merge")
 
     if merged.empty:
         raise ValueError(
@@ -1123,10 +2644,50 @@ def fetch_merged_crypto_data(
     return merged
 
 
+def fetch_equity_data(
+    start_date: str,
+    end_date: str,
+    tickers: list[str] | None = None,
+    data_quality: dict | None = None,
+    progress: ProgressCallback | None = None,
+) -> pd.DataFrame:
+    """
+    Fetch daily price+volume for an equity universe from Yahoo Finance.
+
+    Args:
+        start_date: YYYY-MM-DD
+        end_date: YYYY-MM-DD
+        tickers: Yahoo tickers to fetch (default: EQUITY_TICKERS)
+        data_quality: Optional dict populated with a "dropped_assets" mapping of
+                      ticker -> reason for every ticker without prices.
+        progress: Called with "fetching_prices" and its percent complete as the tickers are fetched.
+
+    Returns:
+        DataFrame with index=date, columns=[asset, price, 24h_volume]
+    """
+    if tickers is None:
+        tickers = EQUITY_TICKERS
+    if not tickers:
+        raise EmptyUniverseError("No equity tickers to fetch.")
+
+    yahoo_df = YahooData().get_price_volume_for_symbols(tickers, start_date, end_date, progress=progress)
+    if yahoo_df.empty:
+        raise ValueError("No price/volume data returned from Yahoo Finance.")
+
+    priced_tickers = set(yahoo_df["asset"].unique())
+    dropped_assets = {ticker: "missing_prices" for ticker in tickers if ticker not in priced_tickers}
+    if dropped_assets:
+        logger.info(f"Dropping equity tickers without prices: {dropped_assets}")
+    if data_quality is not None:
+        data_quality["dropped_assets"] = dropped_assets
+
+    return yahoo_df.set_index("date")
+
+
 def cumulative_returns(factor_returns):
     """Helper to calculate the cumulative returns for a factor"""
     returns_df = pd.DataFrame(list(factor_returns.items()), columns=["date", "value"])
-    returns_df["cumulative_returns"] = (1 + returns_df["value"]).cumprod() - 1
+    returns_df["cumulative_returns"] = compound_returns(returns_df["value"])
     return returns_df
 
 
@@ -1414,15 +2975,8 @@ def calculate_monthly_metrics(
     if "sortino_ratio" in factor_model.results_dict:
         sortino_ratio = factor_model.results_dict["sortino_ratio"]
     elif len(returns_data) > 0:
-        downside_returns = returns_data[returns_data[returns_col] < 0][returns_col]
-        if len(downside_returns) > 0:
-            mean_return = returns_data[returns_col].mean()
-            downside_std = downside_returns.std()
-            if downside_std > 0:
-                sortino_ratio = (mean_return / downside_std) * np.sqrt(52)
-            else:
-                sortino_ratio = np.nan
-        else:
+        sortino_ratio = compute_sortino_ratio(returns_data[returns_col])
+        if sortino_ratio is None:
             sortino_ratio = np.nan
     else:
         sortino_ratio = np.nan
@@ -1533,7 +3087,7 @@ def calculate_monthly_metrics(
         ]["24h_volume"]
 
         if len(portfolio_volumes) > 0:
-            daily_volumes = portfolio_volumes / 7
+            daily_volumes = portfolio_volumes / factor_model.period_days
             avg_adv_week = daily_volumes.mean()
 
     # 12. Average Market Cap